@@ -1,6 +1,15 @@
 //! The data types that represent an AST of a GraphQL document.
 //!
 //! The root AST type is `Document`.
+//!
+//! Every node which contains text is generic over the type of that text. By
+//! default this is `String`, but the AST returned by `parse_borrowed` uses
+//! `Cow<'a, str>` so that names and strings can be borrowed from the source.
+//! Use `IntoOwned` to convert a borrowed AST into the default owned AST.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
 
 use super::Location;
 
@@ -9,13 +18,111 @@ use super::Location;
 pub trait Node {
   /// The location at which thie AST node is located in its source document.
   fn loc(&self) -> Option<&Location>;
+
+  /// The text of this AST node in the source document it was parsed from.
+  /// Returns `None` if the node has no location.
+  fn text<'a>(&self, source: &'a str) -> Option<&'a str> {
+    self.loc().and_then(|loc| loc.text(source))
+  }
+
+  /// The text of this AST node in the `Source` it was parsed from with
+  /// `parse_source`. Returns `None` if the node has no location or source.
+  fn source_text(&self) -> Option<&str> {
+    self.loc().and_then(Location::source_text)
+  }
+}
+
+/// Converts a node which may borrow its text from the source document into a
+/// node which owns all of its text.
+pub trait IntoOwned {
+  /// The owned version of this type.
+  type Owned;
+  /// Converts this value into its owned version, copying any borrowed text.
+  fn into_owned(self) -> Self::Owned;
+}
+
+impl IntoOwned for String {
+  type Owned = String;
+  fn into_owned(self) -> String {
+    self
+  }
+}
+
+impl<'a> IntoOwned for Cow<'a, str> {
+  type Owned = String;
+  fn into_owned(self) -> String {
+    Cow::into_owned(self)
+  }
+}
+
+impl IntoOwned for bool {
+  type Owned = bool;
+  fn into_owned(self) -> bool {
+    self
+  }
+}
+
+impl<T> IntoOwned for Option<T> where T: IntoOwned {
+  type Owned = Option<T::Owned>;
+  fn into_owned(self) -> Option<T::Owned> {
+    self.map(IntoOwned::into_owned)
+  }
+}
+
+impl<T> IntoOwned for Vec<T> where T: IntoOwned {
+  type Owned = Vec<T::Owned>;
+  fn into_owned(self) -> Vec<T::Owned> {
+    self.into_iter().map(IntoOwned::into_owned).collect()
+  }
+}
+
+impl<T> IntoOwned for Box<T> where T: IntoOwned {
+  type Owned = Box<T::Owned>;
+  fn into_owned(self) -> Box<T::Owned> {
+    Box::new((*self).into_owned())
+  }
 }
 
 /// Creates a node struct with all of the repetetive code that is required.
 ///
 /// The goal is to make this macro look as close to an actual struct definition
-/// as possible.
+/// as possible. Nodes which contain text are declared with an `<S>` type
+/// parameter for the type of that text. Fields may have `#[cfg]` attributes
+/// for experimental non-spec additions.
 macro_rules! node_struct {
+  (
+    pub struct $struct_name:ident<S> {
+      $($(#[$field_attr:meta])* $field_name:ident: $field_type:ty,)*
+    }
+  ) => (
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct $struct_name<S = String> {
+      pub loc: Option<Location>,
+      $(
+        $(#[$field_attr])*
+        pub $field_name: $field_type,
+      )*
+    }
+
+    impl<S> Node for $struct_name<S> {
+      fn loc(&self) -> Option<&Location> {
+        self.loc.as_ref()
+      }
+    }
+
+    impl<S> IntoOwned for $struct_name<S> where S: IntoOwned<Owned=String> {
+      type Owned = $struct_name;
+      fn into_owned(self) -> $struct_name {
+        $struct_name {
+          loc: self.loc,
+          $(
+            $(#[$field_attr])*
+            $field_name: self.$field_name.into_owned(),
+          )*
+        }
+      }
+    }
+  );
   (
     pub struct $struct_name:ident {
       $($field_name:ident: $field_type:ty,)*
@@ -34,7 +141,14 @@ macro_rules! node_struct {
         self.loc.as_ref()
       }
     }
-  )
+
+    impl IntoOwned for $struct_name {
+      type Owned = $struct_name;
+      fn into_owned(self) -> $struct_name {
+        self
+      }
+    }
+  );
 }
 
 /// Creates a node enum with all of the repetetive code that is required.
@@ -43,18 +157,18 @@ macro_rules! node_struct {
 /// as possible.
 macro_rules! node_enum {
   (
-    pub enum $enum_name:ident {
+    pub enum $enum_name:ident<S> {
       $($variant_name:ident($variant_type:ty),)*
     }
   ) => (
     #[derive(Clone, PartialEq, Debug)]
-    pub enum $enum_name {
+    pub enum $enum_name<S = String> {
       $(
         $variant_name($variant_type),
       )*
     }
 
-    impl Node for $enum_name {
+    impl<S> Node for $enum_name<S> {
       fn loc(&self) -> Option<&Location> {
         match *self {
           $(
@@ -63,6 +177,17 @@ macro_rules! node_enum {
         }
       }
     }
+
+    impl<S> IntoOwned for $enum_name<S> where S: IntoOwned<Owned=String> {
+      type Owned = $enum_name;
+      fn into_owned(self) -> $enum_name {
+        match self {
+          $(
+            $enum_name::$variant_name(node) => $enum_name::$variant_name(node.into_owned()),
+          )*
+        }
+      }
+    }
   )
 }
 
@@ -71,8 +196,8 @@ macro_rules! node_enum {
 ////////////////////////////////////////////////////////////////////////////////
 
 node_struct! {
-  pub struct Name {
-    value: String,
+  pub struct Name<S> {
+    value: S,
   }
 }
 
@@ -81,25 +206,25 @@ node_struct! {
 ////////////////////////////////////////////////////////////////////////////////
 
 node_struct! {
-  pub struct Document {
-    definitions: Vec<Definition>,
+  pub struct Document<S> {
+    definitions: Vec<Definition<S>>,
   }
 }
 
-// Because we have some expiremental non-spec additions to this `Definition`
+// Because we have some experimental non-spec additions to this `Definition`
 // node we don’t use the `node_enum!` macro and instead manually provide the
 // necessary implementations.
 #[derive(Clone, PartialEq, Debug)]
-pub enum Definition {
-  Operation(OperationDefinition),
-  Fragment(FragmentDefinition),
+pub enum Definition<S = String> {
+  Operation(OperationDefinition<S>),
+  Fragment(FragmentDefinition<S>),
 
   // The type system AST extension is an experimental non-spec addition.
   #[cfg(feature = "type_system")]
-  TypeSystem(TypeSystemDefinition),
+  TypeSystem(TypeSystemDefinition<S>),
 }
 
-impl Node for Definition {
+impl<S> Node for Definition<S> {
   fn loc(&self) -> Option<&Location> {
     match *self {
       Definition::Operation(ref node) => node.loc(),
@@ -112,13 +237,27 @@ impl Node for Definition {
   }
 }
 
+impl<S> IntoOwned for Definition<S> where S: IntoOwned<Owned=String> {
+  type Owned = Definition;
+  fn into_owned(self) -> Definition {
+    match self {
+      Definition::Operation(node) => Definition::Operation(node.into_owned()),
+      Definition::Fragment(node) => Definition::Fragment(node.into_owned()),
+
+      // The type system AST extension is an experimental non-spec addition.
+      #[cfg(feature = "type_system")]
+      Definition::TypeSystem(node) => Definition::TypeSystem(node.into_owned()),
+    }
+  }
+}
+
 node_struct! {
-  pub struct OperationDefinition {
+  pub struct OperationDefinition<S> {
     operation: OperationType,
-    name: Option<Name>,
-    variable_definitions: Vec<VariableDefinition>,
-    directives: Vec<Directive>,
-    selection_set: SelectionSet,
+    name: Option<Name<S>>,
+    variable_definitions: Vec<VariableDefinition<S>>,
+    directives: Vec<Directive<S>>,
+    selection_set: SelectionSet<S>,
   }
 }
 
@@ -126,54 +265,59 @@ node_struct! {
 pub enum OperationType {
   Query,
   Mutation,
-
-  // Subscriptions are an expiremental non-spec addition.
-  #[cfg(feature = "subscriptions")]
   Subscription,
 }
 
+impl IntoOwned for OperationType {
+  type Owned = OperationType;
+  fn into_owned(self) -> OperationType {
+    self
+  }
+}
+
 node_struct! {
-  pub struct VariableDefinition {
-    variable: Variable,
-    type_: Type,
-    default_value: Option<Value>,
+  pub struct VariableDefinition<S> {
+    variable: Variable<S>,
+    type_: Type<S>,
+    default_value: Option<Value<S>>,
+    directives: Vec<Directive<S>>,
   }
 }
 
 node_struct! {
-  pub struct Variable {
-    name: Name,
+  pub struct Variable<S> {
+    name: Name<S>,
   }
 }
 
 node_struct! {
-  pub struct SelectionSet {
-    selections: Vec<Selection>,
+  pub struct SelectionSet<S> {
+    selections: Vec<Selection<S>>,
   }
 }
 
 node_enum! {
-  pub enum Selection {
-    Field(Field),
-    FragmentSpread(FragmentSpread),
-    InlineFragment(InlineFragment),
+  pub enum Selection<S> {
+    Field(Field<S>),
+    FragmentSpread(FragmentSpread<S>),
+    InlineFragment(InlineFragment<S>),
   }
 }
 
 node_struct! {
-  pub struct Field {
-    alias: Option<Name>,
-    name: Name,
-    arguments: Vec<Argument>,
-    directives: Vec<Directive>,
-    selection_set: Option<SelectionSet>,
+  pub struct Field<S> {
+    alias: Option<Name<S>>,
+    name: Name<S>,
+    arguments: Vec<Argument<S>>,
+    directives: Vec<Directive<S>>,
+    selection_set: Option<SelectionSet<S>>,
   }
 }
 
 node_struct! {
-  pub struct Argument {
-    name: Name,
-    value: Value,
+  pub struct Argument<S> {
+    name: Name<S>,
+    value: Value<S>,
   }
 }
 
@@ -182,26 +326,32 @@ node_struct! {
 ////////////////////////////////////////////////////////////////////////////////
 
 node_struct! {
-  pub struct FragmentSpread {
-    name: Name,
-    directives: Vec<Directive>,
+  pub struct FragmentSpread<S> {
+    name: Name<S>,
+    // Fragment arguments are an experimental non-spec addition.
+    #[cfg(feature = "fragment_variables")]
+    arguments: Vec<Argument<S>>,
+    directives: Vec<Directive<S>>,
   }
 }
 
 node_struct! {
-  pub struct InlineFragment {
-    type_condition: Option<NamedType>,
-    directives: Vec<Directive>,
-    selection_set: SelectionSet,
+  pub struct InlineFragment<S> {
+    type_condition: Option<NamedType<S>>,
+    directives: Vec<Directive<S>>,
+    selection_set: SelectionSet<S>,
   }
 }
 
 node_struct! {
-  pub struct FragmentDefinition {
-    name: Name,
-    type_condition: NamedType,
-    directives: Vec<Directive>,
-    selection_set: SelectionSet,
+  pub struct FragmentDefinition<S> {
+    name: Name<S>,
+    // Fragment variables are an experimental non-spec addition.
+    #[cfg(feature = "fragment_variables")]
+    variable_definitions: Vec<VariableDefinition<S>>,
+    type_condition: NamedType<S>,
+    directives: Vec<Directive<S>>,
+    selection_set: SelectionSet<S>,
   }
 }
 
@@ -210,34 +360,74 @@ node_struct! {
 ////////////////////////////////////////////////////////////////////////////////
 
 node_enum! {
-  pub enum Value {
-    Variable(Variable),
-    Int(IntValue),
-    Float(FloatValue),
-    String(StringValue),
+  pub enum Value<S> {
+    Variable(Variable<S>),
+    Int(IntValue<S>),
+    Float(FloatValue<S>),
+    String(StringValue<S>),
     Boolean(BooleanValue),
     Null(NullValue),
-    Enum(EnumValue),
-    List(ListValue),
-    Object(ObjectValue),
+    Enum(EnumValue<S>),
+    List(ListValue<S>),
+    Object(ObjectValue<S>),
   }
 }
 
+// Numbers keep the exact text they had in the source so that no precision is
+// lost before a schema decides what numeric type a literal should be.
+
 node_struct! {
-  pub struct IntValue {
-    value: i32,
+  pub struct IntValue<S> {
+    value: S,
+  }
+}
+
+impl<S> IntValue<S> where S: AsRef<str> {
+  /// The exact source text of this integer with arbitrary precision.
+  pub fn as_str(&self) -> &str {
+    self.value.as_ref()
+  }
+
+  /// Converts this integer into an `i32` if it fits.
+  pub fn as_i32(&self) -> Option<i32> {
+    i32::from_str(self.as_str()).ok()
+  }
+
+  /// Converts this integer into an `i64` if it fits.
+  pub fn as_i64(&self) -> Option<i64> {
+    i64::from_str(self.as_str()).ok()
+  }
+
+  /// Converts this integer into the nearest `f64`. Returns `None` if the
+  /// integer is too large to be a finite `f64`.
+  pub fn as_f64(&self) -> Option<f64> {
+    f64::from_str(self.as_str()).ok().and_then(|float| if float.is_finite() { Some(float) } else { None })
   }
 }
 
 node_struct! {
-  pub struct FloatValue {
-    value: f32,
+  pub struct FloatValue<S> {
+    value: S,
+  }
+}
+
+impl<S> FloatValue<S> where S: AsRef<str> {
+  /// The exact source text of this float with arbitrary precision.
+  pub fn as_str(&self) -> &str {
+    self.value.as_ref()
+  }
+
+  /// Converts this float into the nearest `f64`. Returns `None` if the float
+  /// is too large to be a finite `f64`.
+  pub fn as_f64(&self) -> Option<f64> {
+    f64::from_str(self.as_str()).ok().and_then(|float| if float.is_finite() { Some(float) } else { None })
   }
 }
 
 node_struct! {
-  pub struct StringValue {
-    value: String,
+  pub struct StringValue<S> {
+    value: S,
+    block: bool,
   }
 }
 
@@ -252,27 +442,27 @@ node_struct! {
 }
 
 node_struct! {
-  pub struct EnumValue {
-    value: String,
+  pub struct EnumValue<S> {
+    value: S,
   }
 }
 
 node_struct! {
-  pub struct ListValue {
-    values: Vec<Value>,
+  pub struct ListValue<S> {
+    values: Vec<Value<S>>,
   }
 }
 
 node_struct! {
-  pub struct ObjectValue {
-    fields: Vec<ObjectField>,
+  pub struct ObjectValue<S> {
+    fields: Vec<ObjectField<S>>,
   }
 }
 
 node_struct! {
-  pub struct ObjectField {
-    name: Name,
-    value: Value,
+  pub struct ObjectField<S> {
+    name: Name<S>,
+    value: Value<S>,
   }
 }
 
@@ -281,33 +471,196 @@ node_struct! {
 ////////////////////////////////////////////////////////////////////////////////
 
 node_struct! {
-  pub struct Directive {
-    name: Name,
-    arguments: Vec<Argument>,
+  pub struct Directive<S> {
+    name: Name<S>,
+    arguments: Vec<Argument<S>>,
+  }
+}
+
+/// A place in a document where a directive may be used. Directive
+/// definitions list the locations where their directive may be used.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DirectiveLocation {
+  // Executable directive locations.
+  Query,
+  Mutation,
+  Subscription,
+  Field,
+  FragmentDefinition,
+  FragmentSpread,
+  InlineFragment,
+  VariableDefinition,
+
+  // Type system directive locations.
+  Schema,
+  Scalar,
+  Object,
+  FieldDefinition,
+  ArgumentDefinition,
+  Interface,
+  Union,
+  Enum,
+  EnumValue,
+  InputObject,
+  InputFieldDefinition,
+}
+
+impl DirectiveLocation {
+  /// Every directive location in the order the spec lists them.
+  pub const ALL: &'static [DirectiveLocation] = &[
+    DirectiveLocation::Query,
+    DirectiveLocation::Mutation,
+    DirectiveLocation::Subscription,
+    DirectiveLocation::Field,
+    DirectiveLocation::FragmentDefinition,
+    DirectiveLocation::FragmentSpread,
+    DirectiveLocation::InlineFragment,
+    DirectiveLocation::VariableDefinition,
+    DirectiveLocation::Schema,
+    DirectiveLocation::Scalar,
+    DirectiveLocation::Object,
+    DirectiveLocation::FieldDefinition,
+    DirectiveLocation::ArgumentDefinition,
+    DirectiveLocation::Interface,
+    DirectiveLocation::Union,
+    DirectiveLocation::Enum,
+    DirectiveLocation::EnumValue,
+    DirectiveLocation::InputObject,
+    DirectiveLocation::InputFieldDefinition,
+  ];
+
+  /// Gets the directive location with a name, like `FIELD`. Returns `None` if
+  /// there is no directive location with that name.
+  pub fn from_name(name: &str) -> Option<DirectiveLocation> {
+    DirectiveLocation::ALL.iter().find(|location| location.name() == name).cloned()
+  }
+
+  /// The name of this directive location as it is written in a directive
+  /// definition, like `FIELD`.
+  pub fn name(&self) -> &'static str {
+    match *self {
+      DirectiveLocation::Query => "QUERY",
+      DirectiveLocation::Mutation => "MUTATION",
+      DirectiveLocation::Subscription => "SUBSCRIPTION",
+      DirectiveLocation::Field => "FIELD",
+      DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+      DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+      DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+      DirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
+      DirectiveLocation::Schema => "SCHEMA",
+      DirectiveLocation::Scalar => "SCALAR",
+      DirectiveLocation::Object => "OBJECT",
+      DirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
+      DirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
+      DirectiveLocation::Interface => "INTERFACE",
+      DirectiveLocation::Union => "UNION",
+      DirectiveLocation::Enum => "ENUM",
+      DirectiveLocation::EnumValue => "ENUM_VALUE",
+      DirectiveLocation::InputObject => "INPUT_OBJECT",
+      DirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
+    }
+  }
+
+  /// Whether this location is in an executable document, as opposed to a
+  /// type system document.
+  pub fn is_executable(&self) -> bool {
+    matches!(
+      *self,
+      DirectiveLocation::Query |
+      DirectiveLocation::Mutation |
+      DirectiveLocation::Subscription |
+      DirectiveLocation::Field |
+      DirectiveLocation::FragmentDefinition |
+      DirectiveLocation::FragmentSpread |
+      DirectiveLocation::InlineFragment |
+      DirectiveLocation::VariableDefinition
+    )
+  }
+}
+
+impl fmt::Display for DirectiveLocation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+impl IntoOwned for DirectiveLocation {
+  type Owned = DirectiveLocation;
+  fn into_owned(self) -> DirectiveLocation {
+    self
+  }
+}
+
+/// A node which may have directives, so that directive placement can be
+/// checked against the locations of a directive definition.
+///
+/// Input value definitions don’t implement this trait because their location
+/// depends on their parent. Use `InputValueParent` to find their location.
+pub trait HasDirectives<S = String>: Node {
+  /// The directives on this node.
+  fn directives(&self) -> &[Directive<S>];
+
+  /// The location this node occupies for its directives.
+  fn directive_location(&self) -> DirectiveLocation;
+}
+
+/// A utility for implementing `HasDirectives` for a node which is always in
+/// the same directive location.
+macro_rules! impl_has_directives {
+  ($struct_name:ident, $location:ident) => (
+    impl<S> HasDirectives<S> for $struct_name<S> {
+      fn directives(&self) -> &[Directive<S>] {
+        &self.directives
+      }
+
+      fn directive_location(&self) -> DirectiveLocation {
+        DirectiveLocation::$location
+      }
+    }
+  )
+}
+
+impl<S> HasDirectives<S> for OperationDefinition<S> {
+  fn directives(&self) -> &[Directive<S>] {
+    &self.directives
+  }
+
+  fn directive_location(&self) -> DirectiveLocation {
+    match self.operation {
+      OperationType::Query => DirectiveLocation::Query,
+      OperationType::Mutation => DirectiveLocation::Mutation,
+      OperationType::Subscription => DirectiveLocation::Subscription,
+    }
   }
 }
 
+impl_has_directives!(Field, Field);
+impl_has_directives!(FragmentDefinition, FragmentDefinition);
+impl_has_directives!(FragmentSpread, FragmentSpread);
+impl_has_directives!(InlineFragment, InlineFragment);
+impl_has_directives!(VariableDefinition, VariableDefinition);
+
 ////////////////////////////////////////////////////////////////////////////////
 // Type Reference
 ////////////////////////////////////////////////////////////////////////////////
 
 node_enum! {
-  pub enum Type {
-    Named(NamedType),
-    List(ListType),
-    NonNull(NonNullType),
+  pub enum Type<S> {
+    Named(NamedType<S>),
+    List(ListType<S>),
+    NonNull(NonNullType<S>),
   }
 }
 
 node_enum! {
-  pub enum NullableType {
-    Named(NamedType),
-    List(ListType),
+  pub enum NullableType<S> {
+    Named(NamedType<S>),
+    List(ListType<S>),
   }
 }
 
-impl From<NullableType> for Type {
-  fn from(typ: NullableType) -> Type {
+impl<S> From<NullableType<S>> for Type<S> {
+  fn from(typ: NullableType<S>) -> Type<S> {
     match typ {
       NullableType::Named(named) => Type::Named(named),
       NullableType::List(list) => Type::List(list),
@@ -316,20 +669,20 @@ impl From<NullableType> for Type {
 }
 
 node_struct! {
-  pub struct NamedType {
-    name: Name,
+  pub struct NamedType<S> {
+    name: Name<S>,
   }
 }
 
 node_struct! {
-  pub struct ListType {
-    type_: Box<Type>,
+  pub struct ListType<S> {
+    type_: Box<Type<S>>,
   }
 }
 
 node_struct! {
-  pub struct NonNullType {
-    type_: Box<NullableType>,
+  pub struct NonNullType<S> {
+    type_: Box<NullableType<S>>,
   }
 }
 
@@ -347,123 +700,250 @@ mod type_system {
   use super::*;
 
   node_enum! {
-    pub enum TypeSystemDefinition {
-      Schema(SchemaDefinition),
-      Type(TypeDefinition),
-      TypeExtension(TypeExtensionDefinition),
-      Directive(DirectiveDefinition),
+    pub enum TypeSystemDefinition<S> {
+      Schema(SchemaDefinition<S>),
+      Type(TypeDefinition<S>),
+      SchemaExtension(SchemaExtension<S>),
+      TypeExtension(TypeExtension<S>),
+      Directive(DirectiveDefinition<S>),
     }
   }
 
   node_struct! {
-    pub struct SchemaDefinition {
-      directives: Vec<Directive>,
-      operation_types: Vec<OperationTypeDefinition>,
+    pub struct SchemaDefinition<S> {
+      description: Option<StringValue<S>>,
+      directives: Vec<Directive<S>>,
+      operation_types: Vec<OperationTypeDefinition<S>>,
     }
   }
 
   node_struct! {
-    pub struct OperationTypeDefinition {
+    pub struct OperationTypeDefinition<S> {
       operation: OperationType,
-      typ: NamedType,
+      typ: NamedType<S>,
     }
   }
 
   node_enum! {
-    pub enum TypeDefinition {
-      Scalar(ScalarTypeDefinition),
-      Object(ObjectTypeDefinition),
-      Interface(InterfaceTypeDefinition),
-      Union(UnionTypeDefinition),
-      Enum(EnumTypeDefinition),
-      InputObject(InputObjectTypeDefinition),
+    pub enum TypeDefinition<S> {
+      Scalar(ScalarTypeDefinition<S>),
+      Object(ObjectTypeDefinition<S>),
+      Interface(InterfaceTypeDefinition<S>),
+      Union(UnionTypeDefinition<S>),
+      Enum(EnumTypeDefinition<S>),
+      InputObject(InputObjectTypeDefinition<S>),
+    }
+  }
+
+  node_struct! {
+    pub struct ScalarTypeDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
     }
   }
 
   node_struct! {
-    pub struct ScalarTypeDefinition {
-      name: Name,
-      directives: Vec<Directive>,
+    pub struct ObjectTypeDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      interfaces: Vec<NamedType<S>>,
+      directives: Vec<Directive<S>>,
+      fields: Vec<FieldDefinition<S>>,
     }
   }
 
   node_struct! {
-    pub struct ObjectTypeDefinition {
-      name: Name,
-      interfaces: Vec<NamedType>,
-      directives: Vec<Directive>,
-      fields: Vec<FieldDefinition>,
+    pub struct FieldDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      arguments: Vec<InputValueDefinition<S>>,
+      typ: Type<S>,
+      directives: Vec<Directive<S>>,
     }
   }
 
   node_struct! {
-    pub struct FieldDefinition {
-      name: Name,
-      arguments: Vec<InputValueDefinition>,
-      typ: Type,
-      directives: Vec<Directive>,
+    pub struct InputValueDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      typ: Type<S>,
+      default_value: Option<Value<S>>,
+      directives: Vec<Directive<S>>,
     }
   }
 
   node_struct! {
-    pub struct InputValueDefinition {
-      name: Name,
-      typ: Type,
-      default_value: Option<Value>,
-      directives: Vec<Directive>,
+    pub struct InterfaceTypeDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      interfaces: Vec<NamedType<S>>,
+      directives: Vec<Directive<S>>,
+      fields: Vec<FieldDefinition<S>>,
     }
   }
 
   node_struct! {
-    pub struct InterfaceTypeDefinition {
-      name: Name,
-      directives: Vec<Directive>,
-      fields: Vec<FieldDefinition>,
+    pub struct UnionTypeDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
+      types: Vec<NamedType<S>>,
     }
   }
 
   node_struct! {
-    pub struct UnionTypeDefinition {
-      name: Name,
-      directives: Vec<Directive>,
-      types: Vec<NamedType>,
+    pub struct EnumTypeDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
+      values: Vec<EnumValueDefinition<S>>,
     }
   }
 
   node_struct! {
-    pub struct EnumTypeDefinition {
-      name: Name,
-      directives: Vec<Directive>,
-      values: Vec<EnumValueDefinition>,
+    pub struct EnumValueDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
     }
   }
 
   node_struct! {
-    pub struct EnumValueDefinition {
-      name: Name,
-      directives: Vec<Directive>,
+    pub struct InputObjectTypeDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
+      fields: Vec<InputValueDefinition<S>>,
     }
   }
 
   node_struct! {
-    pub struct InputObjectTypeDefinition {
-      name: Name,
-      directives: Vec<Directive>,
-      fields: Vec<InputValueDefinition>,
+    pub struct SchemaExtension<S> {
+      directives: Vec<Directive<S>>,
+      operation_types: Vec<OperationTypeDefinition<S>>,
+    }
+  }
+
+  node_enum! {
+    pub enum TypeExtension<S> {
+      Scalar(ScalarTypeExtension<S>),
+      Object(ObjectTypeExtension<S>),
+      Interface(InterfaceTypeExtension<S>),
+      Union(UnionTypeExtension<S>),
+      Enum(EnumTypeExtension<S>),
+      InputObject(InputObjectTypeExtension<S>),
     }
   }
 
   node_struct! {
-    pub struct TypeExtensionDefinition {
-      definition: ObjectTypeDefinition,
+    pub struct ScalarTypeExtension<S> {
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
     }
   }
 
   node_struct! {
-    pub struct DirectiveDefinition {
-      name: Name,
-      arguments: Vec<InputValueDefinition>,
-      locations: Vec<Name>,
+    pub struct ObjectTypeExtension<S> {
+      name: Name<S>,
+      interfaces: Vec<NamedType<S>>,
+      directives: Vec<Directive<S>>,
+      fields: Vec<FieldDefinition<S>>,
+    }
+  }
+
+  node_struct! {
+    pub struct InterfaceTypeExtension<S> {
+      name: Name<S>,
+      interfaces: Vec<NamedType<S>>,
+      directives: Vec<Directive<S>>,
+      fields: Vec<FieldDefinition<S>>,
+    }
+  }
+
+  node_struct! {
+    pub struct UnionTypeExtension<S> {
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
+      types: Vec<NamedType<S>>,
+    }
+  }
+
+  node_struct! {
+    pub struct EnumTypeExtension<S> {
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
+      values: Vec<EnumValueDefinition<S>>,
+    }
+  }
+
+  node_struct! {
+    pub struct InputObjectTypeExtension<S> {
+      name: Name<S>,
+      directives: Vec<Directive<S>>,
+      fields: Vec<InputValueDefinition<S>>,
+    }
+  }
+
+  node_struct! {
+    pub struct DirectiveDefinition<S> {
+      description: Option<StringValue<S>>,
+      name: Name<S>,
+      arguments: Vec<InputValueDefinition<S>>,
+      repeatable: bool,
+      locations: Vec<DirectiveLocation>,
+    }
+  }
+
+  impl_has_directives!(SchemaDefinition, Schema);
+  impl_has_directives!(SchemaExtension, Schema);
+  impl_has_directives!(ScalarTypeDefinition, Scalar);
+  impl_has_directives!(ScalarTypeExtension, Scalar);
+  impl_has_directives!(ObjectTypeDefinition, Object);
+  impl_has_directives!(ObjectTypeExtension, Object);
+  impl_has_directives!(FieldDefinition, FieldDefinition);
+  impl_has_directives!(InterfaceTypeDefinition, Interface);
+  impl_has_directives!(InterfaceTypeExtension, Interface);
+  impl_has_directives!(UnionTypeDefinition, Union);
+  impl_has_directives!(UnionTypeExtension, Union);
+  impl_has_directives!(EnumTypeDefinition, Enum);
+  impl_has_directives!(EnumTypeExtension, Enum);
+  impl_has_directives!(EnumValueDefinition, EnumValue);
+  impl_has_directives!(InputObjectTypeDefinition, InputObject);
+  impl_has_directives!(InputObjectTypeExtension, InputObject);
+
+  /// A node which has input value definitions, either as arguments or as
+  /// input fields. The location of an input value for its directives depends
+  /// on which kind of node it belongs to.
+  #[derive(PartialEq, Debug)]
+  pub enum InputValueParent<'a, S: 'a = String> {
+    FieldDefinition(&'a FieldDefinition<S>),
+    DirectiveDefinition(&'a DirectiveDefinition<S>),
+    InputObjectTypeDefinition(&'a InputObjectTypeDefinition<S>),
+    InputObjectTypeExtension(&'a InputObjectTypeExtension<S>),
+  }
+
+  impl<'a, S> InputValueParent<'a, S> {
+    /// The input values of this node.
+    pub fn input_values(&self) -> &'a [InputValueDefinition<S>] {
+      match *self {
+        InputValueParent::FieldDefinition(node) => &node.arguments,
+        InputValueParent::DirectiveDefinition(node) => &node.arguments,
+        InputValueParent::InputObjectTypeDefinition(node) => &node.fields,
+        InputValueParent::InputObjectTypeExtension(node) => &node.fields,
+      }
+    }
+
+    /// The location the input values of this node occupy for their
+    /// directives. Arguments are an `ARGUMENT_DEFINITION` and input fields are
+    /// an `INPUT_FIELD_DEFINITION`.
+    pub fn input_value_directive_location(&self) -> DirectiveLocation {
+      match *self {
+        InputValueParent::FieldDefinition(_) |
+        InputValueParent::DirectiveDefinition(_) => DirectiveLocation::ArgumentDefinition,
+        InputValueParent::InputObjectTypeDefinition(_) |
+        InputValueParent::InputObjectTypeExtension(_) => DirectiveLocation::InputFieldDefinition,
+      }
     }
   }
 }
@@ -0,0 +1,1213 @@
+//! Constructors and `From` conversions for building an AST in code.
+//!
+//! Every node can be created with a constructor which takes only what the
+//! node can’t do without, and the rest is added with builder methods. Nodes
+//! built this way have no locations.
+//!
+//! ```txt
+//! Document::new().definition(
+//!   OperationDefinition::query().name("User")
+//!     .variable(VariableDefinition::new("id", Type::non_null("ID")))
+//!     .select(Field::new("user").alias("u").arg("id", Variable::new("id"))
+//!       .select(Field::new("name")))
+//! )
+//! ```
+//!
+//! Rust values convert into GraphQL values, so integers, booleans, strings,
+//! vectors and options can be used anywhere a `Value` is expected. Floats
+//! which may not be finite are created with `FloatValue::from_f64`.
+//! Names and named types convert from strings.
+
+use super::ast::*;
+
+/// A utility for generating a builder method which adds a node to a list
+/// field.
+macro_rules! fn_pushes {
+  ($(#[$attr:meta])* $fn_name:ident($field_name:ident: $type_:ty)) => (
+    $(#[$attr])*
+    pub fn $fn_name<T>(mut self, node: T) -> Self where T: Into<$type_> {
+      self.$field_name.push(node.into());
+      self
+    }
+  )
+}
+
+/// A utility for generating the builder method which adds a directive.
+macro_rules! fn_directive {
+  () => (
+    fn_pushes!(
+      /// Adds a directive.
+      directive(directives: Directive)
+    );
+  )
+}
+
+/// A utility for generating the builder method which adds an argument.
+macro_rules! fn_arg {
+  () => (
+    /// Adds an argument.
+    pub fn arg<N, V>(mut self, name: N, value: V) -> Self where N: Into<Name>, V: Into<Value> {
+      self.arguments.push(Argument::new(name, value));
+      self
+    }
+  )
+}
+
+/// A utility for generating the builder method which adds a selection to a
+/// required selection set.
+macro_rules! fn_select {
+  () => (
+    /// Adds a selection to the selection set.
+    pub fn select<T>(mut self, selection: T) -> Self where T: Into<Selection> {
+      self.selection_set.selections.push(selection.into());
+      self
+    }
+  )
+}
+
+/// A utility for implementing `From` for a node enum from the node of one of
+/// its variants.
+macro_rules! impl_from {
+  ($enum_name:ident::$variant_name:ident($type_:ident)) => (
+    impl From<$type_> for $enum_name {
+      fn from(node: $type_) -> $enum_name {
+        $enum_name::$variant_name(node)
+      }
+    }
+  )
+}
+
+/// A utility for implementing `Default` for nodes with a constructor that
+/// takes no arguments.
+macro_rules! impl_default {
+  ($($type_:ident),*) => (
+    $(
+      impl Default for $type_ {
+        fn default() -> $type_ {
+          $type_::new()
+        }
+      }
+    )*
+  )
+}
+
+impl_default!(Document, SelectionSet, InlineFragment, NullValue, ObjectValue);
+
+////////////////////////////////////////////////////////////////////////////////
+// Name
+////////////////////////////////////////////////////////////////////////////////
+
+impl Name {
+  /// Creates a name.
+  pub fn new<V>(value: V) -> Self where V: Into<String> {
+    Name {
+      loc: None,
+      value: value.into(),
+    }
+  }
+}
+
+impl<'a> From<&'a str> for Name {
+  fn from(value: &'a str) -> Name {
+    Name::new(value)
+  }
+}
+
+impl From<String> for Name {
+  fn from(value: String) -> Name {
+    Name::new(value)
+  }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Document
+////////////////////////////////////////////////////////////////////////////////
+
+impl Document {
+  /// Creates a document without any definitions.
+  pub fn new() -> Self {
+    Document {
+      loc: None,
+      definitions: Vec::new(),
+    }
+  }
+
+  fn_pushes!(
+    /// Adds a definition.
+    definition(definitions: Definition)
+  );
+}
+
+impl_from!(Definition::Operation(OperationDefinition));
+impl_from!(Definition::Fragment(FragmentDefinition));
+
+impl OperationDefinition {
+  /// Creates an anonymous operation with an empty selection set.
+  pub fn new(operation: OperationType) -> Self {
+    OperationDefinition {
+      loc: None,
+      operation: operation,
+      name: None,
+      variable_definitions: Vec::new(),
+      directives: Vec::new(),
+      selection_set: SelectionSet::new(),
+    }
+  }
+
+  /// Creates an anonymous query.
+  pub fn query() -> Self {
+    OperationDefinition::new(OperationType::Query)
+  }
+
+  /// Creates an anonymous mutation.
+  pub fn mutation() -> Self {
+    OperationDefinition::new(OperationType::Mutation)
+  }
+
+  /// Creates an anonymous subscription.
+  pub fn subscription() -> Self {
+    OperationDefinition::new(OperationType::Subscription)
+  }
+
+  /// Names the operation.
+  pub fn name<N>(mut self, name: N) -> Self where N: Into<Name> {
+    self.name = Some(name.into());
+    self
+  }
+
+  fn_pushes!(
+    /// Adds a variable definition.
+    variable(variable_definitions: VariableDefinition)
+  );
+
+  fn_directive!();
+  fn_select!();
+}
+
+impl VariableDefinition {
+  /// Creates the definition of a variable with a type.
+  pub fn new<N, T>(name: N, type_: T) -> Self where N: Into<Name>, T: Into<Type> {
+    VariableDefinition {
+      loc: None,
+      variable: Variable::new(name),
+      type_: type_.into(),
+      default_value: None,
+      directives: Vec::new(),
+    }
+  }
+
+  /// Sets the value of the variable when none is provided.
+  pub fn default_value<V>(mut self, value: V) -> Self where V: Into<Value> {
+    self.default_value = Some(value.into());
+    self
+  }
+
+  fn_directive!();
+}
+
+impl Variable {
+  /// Creates a variable. The name does not include the `$`.
+  pub fn new<N>(name: N) -> Self where N: Into<Name> {
+    Variable {
+      loc: None,
+      name: name.into(),
+    }
+  }
+}
+
+impl SelectionSet {
+  /// Creates an empty selection set.
+  pub fn new() -> Self {
+    SelectionSet {
+      loc: None,
+      selections: Vec::new(),
+    }
+  }
+
+  fn_pushes!(
+    /// Adds a selection.
+    select(selections: Selection)
+  );
+}
+
+impl_from!(Selection::Field(Field));
+impl_from!(Selection::FragmentSpread(FragmentSpread));
+impl_from!(Selection::InlineFragment(InlineFragment));
+
+impl Field {
+  /// Creates a field without arguments or a selection set.
+  pub fn new<N>(name: N) -> Self where N: Into<Name> {
+    Field {
+      loc: None,
+      alias: None,
+      name: name.into(),
+      arguments: Vec::new(),
+      directives: Vec::new(),
+      selection_set: None,
+    }
+  }
+
+  /// Sets the alias the result of the field is given.
+  pub fn alias<N>(mut self, alias: N) -> Self where N: Into<Name> {
+    self.alias = Some(alias.into());
+    self
+  }
+
+  fn_arg!();
+  fn_directive!();
+
+  /// Adds a selection to the selection set, creating the selection set if the
+  /// field does not have one.
+  pub fn select<T>(mut self, selection: T) -> Self where T: Into<Selection> {
+    self.selection_set.get_or_insert_with(SelectionSet::new).selections.push(selection.into());
+    self
+  }
+}
+
+impl Argument {
+  /// Creates an argument.
+  pub fn new<N, V>(name: N, value: V) -> Self where N: Into<Name>, V: Into<Value> {
+    Argument {
+      loc: None,
+      name: name.into(),
+      value: value.into(),
+    }
+  }
+}
+
+impl FragmentSpread {
+  /// Creates a spread of a named fragment.
+  pub fn new<N>(name: N) -> Self where N: Into<Name> {
+    FragmentSpread {
+      loc: None,
+      name: name.into(),
+      // Fragment variables are an experimental non-spec addition.
+      #[cfg(feature = "fragment_variables")]
+      arguments: Vec::new(),
+      directives: Vec::new(),
+    }
+  }
+
+  // Fragment variables are an experimental non-spec addition.
+  #[cfg(feature = "fragment_variables")]
+  fn_arg!();
+
+  fn_directive!();
+}
+
+impl InlineFragment {
+  /// Creates an inline fragment without a type condition.
+  pub fn new() -> Self {
+    InlineFragment {
+      loc: None,
+      type_condition: None,
+      directives: Vec::new(),
+      selection_set: SelectionSet::new(),
+    }
+  }
+
+  /// Sets the type this fragment applies to.
+  pub fn on<T>(mut self, type_condition: T) -> Self where T: Into<NamedType> {
+    self.type_condition = Some(type_condition.into());
+    self
+  }
+
+  fn_directive!();
+  fn_select!();
+}
+
+impl FragmentDefinition {
+  /// Creates a fragment on a type with an empty selection set.
+  pub fn new<N, T>(name: N, type_condition: T) -> Self where N: Into<Name>, T: Into<NamedType> {
+    FragmentDefinition {
+      loc: None,
+      name: name.into(),
+      // Fragment variables are an experimental non-spec addition.
+      #[cfg(feature = "fragment_variables")]
+      variable_definitions: Vec::new(),
+      type_condition: type_condition.into(),
+      directives: Vec::new(),
+      selection_set: SelectionSet::new(),
+    }
+  }
+
+  // Fragment variables are an experimental non-spec addition.
+  #[cfg(feature = "fragment_variables")]
+  fn_pushes!(
+    /// Adds a variable definition.
+    variable(variable_definitions: VariableDefinition)
+  );
+
+  fn_directive!();
+  fn_select!();
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Values
+////////////////////////////////////////////////////////////////////////////////
+
+impl_from!(Value::Variable(Variable));
+impl_from!(Value::Int(IntValue));
+impl_from!(Value::Float(FloatValue));
+impl_from!(Value::String(StringValue));
+impl_from!(Value::Boolean(BooleanValue));
+impl_from!(Value::Null(NullValue));
+impl_from!(Value::Enum(EnumValue));
+impl_from!(Value::List(ListValue));
+impl_from!(Value::Object(ObjectValue));
+
+impl IntValue {
+  /// Creates an integer from its exact text, like `42`.
+  pub fn new<V>(value: V) -> Self where V: Into<String> {
+    IntValue {
+      loc: None,
+      value: value.into(),
+    }
+  }
+}
+
+impl FloatValue {
+  /// Creates a float from its exact text, like `4.2e1`.
+  pub fn new<V>(value: V) -> Self where V: Into<String> {
+    FloatValue {
+      loc: None,
+      value: value.into(),
+    }
+  }
+
+  /// Creates a float from a Rust float. Returns `None` if the float is not
+  /// finite since GraphQL can’t represent it.
+  pub fn from_f64(value: f64) -> Option<Self> {
+    if value.is_finite() {
+      // The debug format of a float always has a fraction or an exponent, so
+      // it is never mistaken for an integer.
+      Some(FloatValue::new(format!("{:?}", value)))
+    } else {
+      None
+    }
+  }
+}
+
+impl StringValue {
+  /// Creates a string which is printed with quotes.
+  pub fn new<V>(value: V) -> Self where V: Into<String> {
+    StringValue {
+      loc: None,
+      value: value.into(),
+      block: false,
+    }
+  }
+
+  /// Creates a string which is printed as a block string.
+  pub fn block<V>(value: V) -> Self where V: Into<String> {
+    StringValue {
+      loc: None,
+      value: value.into(),
+      block: true,
+    }
+  }
+}
+
+impl<'a> From<&'a str> for StringValue {
+  fn from(value: &'a str) -> StringValue {
+    StringValue::new(value)
+  }
+}
+
+impl From<String> for StringValue {
+  fn from(value: String) -> StringValue {
+    StringValue::new(value)
+  }
+}
+
+impl BooleanValue {
+  /// Creates a boolean.
+  pub fn new(value: bool) -> Self {
+    BooleanValue {
+      loc: None,
+      value: value,
+    }
+  }
+}
+
+impl NullValue {
+  /// Creates a null.
+  pub fn new() -> Self {
+    NullValue {
+      loc: None,
+    }
+  }
+}
+
+impl EnumValue {
+  /// Creates an enum value, like `RED`.
+  pub fn new<V>(value: V) -> Self where V: Into<String> {
+    EnumValue {
+      loc: None,
+      value: value.into(),
+    }
+  }
+}
+
+impl ListValue {
+  /// Creates a list of values.
+  pub fn new<I, T>(values: I) -> Self where I: IntoIterator<Item=T>, T: Into<Value> {
+    ListValue {
+      loc: None,
+      values: values.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl ObjectValue {
+  /// Creates an object without any fields.
+  pub fn new() -> Self {
+    ObjectValue {
+      loc: None,
+      fields: Vec::new(),
+    }
+  }
+
+  /// Adds a field.
+  pub fn field<N, V>(mut self, name: N, value: V) -> Self where N: Into<Name>, V: Into<Value> {
+    self.fields.push(ObjectField::new(name, value));
+    self
+  }
+}
+
+impl ObjectField {
+  /// Creates a field of an object.
+  pub fn new<N, V>(name: N, value: V) -> Self where N: Into<Name>, V: Into<Value> {
+    ObjectField {
+      loc: None,
+      name: name.into(),
+      value: value.into(),
+    }
+  }
+}
+
+/// A utility for implementing `From` for `Value` from a Rust integer.
+macro_rules! impl_from_int {
+  ($($int:ty),*) => (
+    $(
+      impl From<$int> for Value {
+        fn from(value: $int) -> Value {
+          Value::Int(IntValue::new(value.to_string()))
+        }
+      }
+    )*
+  )
+}
+
+impl_from_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<bool> for Value {
+  fn from(value: bool) -> Value {
+    Value::Boolean(BooleanValue::new(value))
+  }
+}
+
+impl<'a> From<&'a str> for Value {
+  fn from(value: &'a str) -> Value {
+    Value::String(StringValue::new(value))
+  }
+}
+
+impl From<String> for Value {
+  fn from(value: String) -> Value {
+    Value::String(StringValue::new(value))
+  }
+}
+
+impl<T> From<Vec<T>> for Value where T: Into<Value> {
+  fn from(values: Vec<T>) -> Value {
+    Value::List(ListValue::new(values))
+  }
+}
+
+/// `None` is `null`.
+impl<T> From<Option<T>> for Value where T: Into<Value> {
+  fn from(value: Option<T>) -> Value {
+    value.map_or_else(|| Value::Null(NullValue::new()), Into::into)
+  }
+}
+
+impl Directive {
+  /// Creates a directive without arguments. The name does not include the
+  /// `@`.
+  pub fn new<N>(name: N) -> Self where N: Into<Name> {
+    Directive {
+      loc: None,
+      name: name.into(),
+      arguments: Vec::new(),
+    }
+  }
+
+  fn_arg!();
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Type Reference
+////////////////////////////////////////////////////////////////////////////////
+
+impl Type {
+  /// Creates a named type.
+  pub fn named<N>(name: N) -> Self where N: Into<Name> {
+    Type::Named(NamedType::new(name))
+  }
+
+  /// Creates a list of a type.
+  pub fn list<T>(type_: T) -> Self where T: Into<Type> {
+    Type::List(ListType::new(type_))
+  }
+
+  /// Creates a non-null version of a type.
+  pub fn non_null<T>(type_: T) -> Self where T: Into<NullableType> {
+    Type::NonNull(NonNullType::new(type_))
+  }
+}
+
+impl_from!(Type::Named(NamedType));
+impl_from!(Type::List(ListType));
+impl_from!(Type::NonNull(NonNullType));
+impl_from!(NullableType::Named(NamedType));
+impl_from!(NullableType::List(ListType));
+
+impl<'a> From<&'a str> for Type {
+  fn from(name: &'a str) -> Type {
+    Type::named(name)
+  }
+}
+
+impl<'a> From<&'a str> for NullableType {
+  fn from(name: &'a str) -> NullableType {
+    NullableType::Named(NamedType::new(name))
+  }
+}
+
+impl NamedType {
+  /// Creates a named type.
+  pub fn new<N>(name: N) -> Self where N: Into<Name> {
+    NamedType {
+      loc: None,
+      name: name.into(),
+    }
+  }
+}
+
+impl<'a> From<&'a str> for NamedType {
+  fn from(name: &'a str) -> NamedType {
+    NamedType::new(name)
+  }
+}
+
+impl From<String> for NamedType {
+  fn from(name: String) -> NamedType {
+    NamedType::new(name)
+  }
+}
+
+impl ListType {
+  /// Creates a list of a type.
+  pub fn new<T>(type_: T) -> Self where T: Into<Type> {
+    ListType {
+      loc: None,
+      type_: Box::new(type_.into()),
+    }
+  }
+}
+
+impl NonNullType {
+  /// Creates a non-null version of a type.
+  pub fn new<T>(type_: T) -> Self where T: Into<NullableType> {
+    NonNullType {
+      loc: None,
+      type_: Box::new(type_.into()),
+    }
+  }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Type System Definition
+////////////////////////////////////////////////////////////////////////////////
+//
+// The type system AST extension is an experimental non-spec addition.
+
+#[cfg(feature = "type_system")]
+mod type_system {
+  use super::*;
+
+  /// A utility for generating the builder method which sets a description.
+  macro_rules! fn_description {
+    () => (
+      /// Sets the description. Strings are quoted descriptions, use
+      /// `StringValue::block` for a block string description.
+      pub fn description<V>(mut self, description: V) -> Self where V: Into<StringValue> {
+        self.description = Some(description.into());
+        self
+      }
+    )
+  }
+
+  /// A utility for implementing `From` for `TypeSystemDefinition` and
+  /// `Definition` from a node which converts into one of the type system
+  /// definition variants.
+  macro_rules! impl_from_type_system {
+    ($type_:ident) => (
+      impl From<$type_> for Definition {
+        fn from(node: $type_) -> Definition {
+          Definition::TypeSystem(node.into())
+        }
+      }
+    );
+    ($type_:ident via $via:ident) => (
+      impl From<$type_> for TypeSystemDefinition {
+        fn from(node: $type_) -> TypeSystemDefinition {
+          $via::from(node).into()
+        }
+      }
+
+      impl_from_type_system!($type_);
+    );
+  }
+
+  impl_default!(SchemaDefinition, SchemaExtension);
+
+  impl_from!(Definition::TypeSystem(TypeSystemDefinition));
+  impl_from!(TypeSystemDefinition::Schema(SchemaDefinition));
+  impl_from!(TypeSystemDefinition::Type(TypeDefinition));
+  impl_from!(TypeSystemDefinition::SchemaExtension(SchemaExtension));
+  impl_from!(TypeSystemDefinition::TypeExtension(TypeExtension));
+  impl_from!(TypeSystemDefinition::Directive(DirectiveDefinition));
+  impl_from_type_system!(SchemaDefinition);
+  impl_from_type_system!(TypeDefinition);
+  impl_from_type_system!(SchemaExtension);
+  impl_from_type_system!(TypeExtension);
+  impl_from_type_system!(DirectiveDefinition);
+
+  impl_from!(TypeDefinition::Scalar(ScalarTypeDefinition));
+  impl_from!(TypeDefinition::Object(ObjectTypeDefinition));
+  impl_from!(TypeDefinition::Interface(InterfaceTypeDefinition));
+  impl_from!(TypeDefinition::Union(UnionTypeDefinition));
+  impl_from!(TypeDefinition::Enum(EnumTypeDefinition));
+  impl_from!(TypeDefinition::InputObject(InputObjectTypeDefinition));
+  impl_from_type_system!(ScalarTypeDefinition via TypeDefinition);
+  impl_from_type_system!(ObjectTypeDefinition via TypeDefinition);
+  impl_from_type_system!(InterfaceTypeDefinition via TypeDefinition);
+  impl_from_type_system!(UnionTypeDefinition via TypeDefinition);
+  impl_from_type_system!(EnumTypeDefinition via TypeDefinition);
+  impl_from_type_system!(InputObjectTypeDefinition via TypeDefinition);
+
+  impl_from!(TypeExtension::Scalar(ScalarTypeExtension));
+  impl_from!(TypeExtension::Object(ObjectTypeExtension));
+  impl_from!(TypeExtension::Interface(InterfaceTypeExtension));
+  impl_from!(TypeExtension::Union(UnionTypeExtension));
+  impl_from!(TypeExtension::Enum(EnumTypeExtension));
+  impl_from!(TypeExtension::InputObject(InputObjectTypeExtension));
+  impl_from_type_system!(ScalarTypeExtension via TypeExtension);
+  impl_from_type_system!(ObjectTypeExtension via TypeExtension);
+  impl_from_type_system!(InterfaceTypeExtension via TypeExtension);
+  impl_from_type_system!(UnionTypeExtension via TypeExtension);
+  impl_from_type_system!(EnumTypeExtension via TypeExtension);
+  impl_from_type_system!(InputObjectTypeExtension via TypeExtension);
+
+  impl SchemaDefinition {
+    /// Creates a schema without any operation types.
+    pub fn new() -> Self {
+      SchemaDefinition {
+        loc: None,
+        description: None,
+        directives: Vec::new(),
+        operation_types: Vec::new(),
+      }
+    }
+
+    fn_description!();
+    fn_directive!();
+
+    /// Sets the root type of an operation.
+    pub fn operation<T>(mut self, operation: OperationType, typ: T) -> Self where T: Into<NamedType> {
+      self.operation_types.push(OperationTypeDefinition::new(operation, typ));
+      self
+    }
+  }
+
+  impl OperationTypeDefinition {
+    /// Creates the definition of the root type of an operation.
+    pub fn new<T>(operation: OperationType, typ: T) -> Self where T: Into<NamedType> {
+      OperationTypeDefinition {
+        loc: None,
+        operation: operation,
+        typ: typ.into(),
+      }
+    }
+  }
+
+  impl ScalarTypeDefinition {
+    /// Creates a scalar type.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      ScalarTypeDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        directives: Vec::new(),
+      }
+    }
+
+    fn_description!();
+    fn_directive!();
+  }
+
+  impl ObjectTypeDefinition {
+    /// Creates an object type without any fields.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      ObjectTypeDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        interfaces: Vec::new(),
+        directives: Vec::new(),
+        fields: Vec::new(),
+      }
+    }
+
+    fn_description!();
+
+    fn_pushes!(
+      /// Adds an interface the type implements.
+      implements(interfaces: NamedType)
+    );
+
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a field.
+      field(fields: FieldDefinition)
+    );
+  }
+
+  impl FieldDefinition {
+    /// Creates the definition of a field with a type.
+    pub fn new<N, T>(name: N, typ: T) -> Self where N: Into<Name>, T: Into<Type> {
+      FieldDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        arguments: Vec::new(),
+        typ: typ.into(),
+        directives: Vec::new(),
+      }
+    }
+
+    fn_description!();
+
+    fn_pushes!(
+      /// Adds an argument.
+      arg(arguments: InputValueDefinition)
+    );
+
+    fn_directive!();
+  }
+
+  impl InputValueDefinition {
+    /// Creates the definition of an argument or input field with a type.
+    pub fn new<N, T>(name: N, typ: T) -> Self where N: Into<Name>, T: Into<Type> {
+      InputValueDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        typ: typ.into(),
+        default_value: None,
+        directives: Vec::new(),
+      }
+    }
+
+    fn_description!();
+
+    /// Sets the value used when none is provided.
+    pub fn default_value<V>(mut self, value: V) -> Self where V: Into<Value> {
+      self.default_value = Some(value.into());
+      self
+    }
+
+    fn_directive!();
+  }
+
+  impl InterfaceTypeDefinition {
+    /// Creates an interface type without any fields.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      InterfaceTypeDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        interfaces: Vec::new(),
+        directives: Vec::new(),
+        fields: Vec::new(),
+      }
+    }
+
+    fn_description!();
+
+    fn_pushes!(
+      /// Adds an interface the type implements.
+      implements(interfaces: NamedType)
+    );
+
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a field.
+      field(fields: FieldDefinition)
+    );
+  }
+
+  impl UnionTypeDefinition {
+    /// Creates a union type without any members.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      UnionTypeDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        directives: Vec::new(),
+        types: Vec::new(),
+      }
+    }
+
+    fn_description!();
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a member type.
+      member(types: NamedType)
+    );
+  }
+
+  impl EnumTypeDefinition {
+    /// Creates an enum type without any values.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      EnumTypeDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        directives: Vec::new(),
+        values: Vec::new(),
+      }
+    }
+
+    fn_description!();
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a value.
+      value(values: EnumValueDefinition)
+    );
+  }
+
+  impl EnumValueDefinition {
+    /// Creates the definition of an enum value.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      EnumValueDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        directives: Vec::new(),
+      }
+    }
+
+    fn_description!();
+    fn_directive!();
+  }
+
+  impl<'a> From<&'a str> for EnumValueDefinition {
+    fn from(name: &'a str) -> EnumValueDefinition {
+      EnumValueDefinition::new(name)
+    }
+  }
+
+  impl InputObjectTypeDefinition {
+    /// Creates an input object type without any fields.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      InputObjectTypeDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        directives: Vec::new(),
+        fields: Vec::new(),
+      }
+    }
+
+    fn_description!();
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a field.
+      field(fields: InputValueDefinition)
+    );
+  }
+
+  impl SchemaExtension {
+    /// Creates a schema extension.
+    pub fn new() -> Self {
+      SchemaExtension {
+        loc: None,
+        directives: Vec::new(),
+        operation_types: Vec::new(),
+      }
+    }
+
+    fn_directive!();
+
+    /// Sets the root type of an operation.
+    pub fn operation<T>(mut self, operation: OperationType, typ: T) -> Self where T: Into<NamedType> {
+      self.operation_types.push(OperationTypeDefinition::new(operation, typ));
+      self
+    }
+  }
+
+  impl ScalarTypeExtension {
+    /// Creates an extension of a scalar type.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      ScalarTypeExtension {
+        loc: None,
+        name: name.into(),
+        directives: Vec::new(),
+      }
+    }
+
+    fn_directive!();
+  }
+
+  impl ObjectTypeExtension {
+    /// Creates an extension of an object type.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      ObjectTypeExtension {
+        loc: None,
+        name: name.into(),
+        interfaces: Vec::new(),
+        directives: Vec::new(),
+        fields: Vec::new(),
+      }
+    }
+
+    fn_pushes!(
+      /// Adds an interface the type implements.
+      implements(interfaces: NamedType)
+    );
+
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a field.
+      field(fields: FieldDefinition)
+    );
+  }
+
+  impl InterfaceTypeExtension {
+    /// Creates an extension of an interface type.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      InterfaceTypeExtension {
+        loc: None,
+        name: name.into(),
+        interfaces: Vec::new(),
+        directives: Vec::new(),
+        fields: Vec::new(),
+      }
+    }
+
+    fn_pushes!(
+      /// Adds an interface the type implements.
+      implements(interfaces: NamedType)
+    );
+
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a field.
+      field(fields: FieldDefinition)
+    );
+  }
+
+  impl UnionTypeExtension {
+    /// Creates an extension of a union type.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      UnionTypeExtension {
+        loc: None,
+        name: name.into(),
+        directives: Vec::new(),
+        types: Vec::new(),
+      }
+    }
+
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a member type.
+      member(types: NamedType)
+    );
+  }
+
+  impl EnumTypeExtension {
+    /// Creates an extension of an enum type.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      EnumTypeExtension {
+        loc: None,
+        name: name.into(),
+        directives: Vec::new(),
+        values: Vec::new(),
+      }
+    }
+
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a value.
+      value(values: EnumValueDefinition)
+    );
+  }
+
+  impl InputObjectTypeExtension {
+    /// Creates an extension of an input object type.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      InputObjectTypeExtension {
+        loc: None,
+        name: name.into(),
+        directives: Vec::new(),
+        fields: Vec::new(),
+      }
+    }
+
+    fn_directive!();
+
+    fn_pushes!(
+      /// Adds a field.
+      field(fields: InputValueDefinition)
+    );
+  }
+
+  impl DirectiveDefinition {
+    /// Creates a directive definition without any locations. The name does
+    /// not include the `@`.
+    pub fn new<N>(name: N) -> Self where N: Into<Name> {
+      DirectiveDefinition {
+        loc: None,
+        description: None,
+        name: name.into(),
+        arguments: Vec::new(),
+        repeatable: false,
+        locations: Vec::new(),
+      }
+    }
+
+    fn_description!();
+
+    fn_pushes!(
+      /// Adds an argument.
+      arg(arguments: InputValueDefinition)
+    );
+
+    /// Allows the directive to be used more than once in a location.
+    pub fn repeatable(mut self) -> Self {
+      self.repeatable = true;
+      self
+    }
+
+    fn_pushes!(
+      /// Adds a location the directive may be used in.
+      location(locations: DirectiveLocation)
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::super::parse_without_location;
+  use super::super::ast::*;
+
+  #[test]
+  fn test_build_operation() {
+    let document = Document::new()
+      .definition(
+        OperationDefinition::query().name("Q")
+          .variable(VariableDefinition::new("a", Type::non_null(ListType::new("Int"))).default_value(vec![1, 2]))
+          .directive(Directive::new("b").arg("c", true))
+          .select(
+            Field::new("d").alias("e")
+              .arg("f", Variable::new("a"))
+              .arg("g", ObjectValue::new().field("h", "i").field("j", None::<i32>))
+              .arg("k", EnumValue::new("L"))
+              .select(Field::new("m"))
+              .select(FragmentSpread::new("N"))
+              .select(InlineFragment::new().on("O").directive(Directive::new("p")).select(Field::new("q")))
+          )
+      )
+      .definition(OperationDefinition::mutation().select(Field::new("r").arg("s", FloatValue::from_f64(1.5).unwrap())))
+      .definition(OperationDefinition::subscription().select(Field::new("t").arg("u", StringValue::block("v"))))
+      .definition(FragmentDefinition::new("N", "O").select(Field::new("w")));
+    let source = concat!(
+      "query Q($a: [Int]! = [1, 2]) @b(c: true) { e: d(f: $a, g: {h: \"i\", j: null}, k: L) { m ...N ... on O @p { q } } }\n",
+      "mutation { r(s: 1.5) }\n",
+      "subscription { t(u: \"\"\"v\"\"\") }\n",
+      "fragment N on O { w }\n",
+    );
+    assert_eq!(document, parse_without_location(source.chars()).unwrap());
+  }
+
+  #[test]
+  fn test_value_from() {
+    assert_eq!(Value::from(-42), Value::Int(IntValue::new("-42")));
+    assert_eq!(Value::from(9007199254740993u64), Value::Int(IntValue::new("9007199254740993")));
+    assert_eq!(Value::from(false), Value::Boolean(BooleanValue::new(false)));
+    assert_eq!(Value::from(String::from("a")), Value::String(StringValue::new("a")));
+    assert_eq!(Value::from(Some("a")), Value::String(StringValue::new("a")));
+    assert_eq!(Value::from(None::<&str>), Value::Null(NullValue::new()));
+    assert_eq!(Value::from(vec![vec!["a"]]), Value::List(ListValue::new(vec![ListValue::new(vec!["a"])])));
+  }
+
+  #[test]
+  fn test_float_from_f64() {
+    assert_eq!(FloatValue::from_f64(1.0), Some(FloatValue::new("1.0")));
+    assert_eq!(FloatValue::from_f64(6.02214076e23), Some(FloatValue::new("6.02214076e23")));
+    assert_eq!(FloatValue::from_f64(f64::NAN), None);
+    assert_eq!(FloatValue::from_f64(f64::NEG_INFINITY), None);
+  }
+
+  #[cfg(feature = "fragment_variables")]
+  #[test]
+  fn test_build_fragment_variables() {
+    let document = Document::new()
+      .definition(OperationDefinition::query().select(FragmentSpread::new("A").arg("b", 1)))
+      .definition(FragmentDefinition::new("A", "C").variable(VariableDefinition::new("b", "Int")).select(Field::new("d")));
+    assert_eq!(document, parse_without_location("{ ...A(b: 1) } fragment A($b: Int) on C { d }".chars()).unwrap());
+  }
+
+  #[cfg(feature = "type_system")]
+  #[test]
+  fn test_build_type_system() {
+    let document = Document::new()
+      .definition(SchemaDefinition::new().description("a").operation(OperationType::Query, "Q"))
+      .definition(SchemaExtension::new().directive(Directive::new("b")))
+      .definition(
+        ObjectTypeDefinition::new("Q").implements("I").directive(Directive::new("c"))
+          .field(FieldDefinition::new("d", Type::list(Type::non_null("String")))
+            .arg(InputValueDefinition::new("e", "Int").default_value(1)))
+      )
+      .definition(InterfaceTypeDefinition::new("I").field(FieldDefinition::new("d", "String")))
+      .definition(UnionTypeDefinition::new("U").member("Q").member("R"))
+      .definition(EnumTypeDefinition::new("E").description(StringValue::block("f")).value("G").value(EnumValueDefinition::new("H").directive(Directive::new("i"))))
+      .definition(InputObjectTypeDefinition::new("J").field(InputValueDefinition::new("k", "Int")))
+      .definition(ScalarTypeDefinition::new("S"))
+      .definition(ObjectTypeExtension::new("Q").field(FieldDefinition::new("l", "Int")))
+      .definition(EnumTypeExtension::new("E").value("M"))
+      .definition(DirectiveDefinition::new("n").arg(InputValueDefinition::new("o", "Int")).repeatable()
+        .location(DirectiveLocation::Field).location(DirectiveLocation::EnumValue));
+    let source = concat!(
+      "\"a\" schema { query: Q } extend schema @b\n",
+      "type Q implements I @c { d(e: Int = 1): [String!] }\n",
+      "interface I { d: String } union U = Q | R\n",
+      "\"\"\"f\"\"\" enum E { G H @i } input J { k: Int } scalar S\n",
+      "extend type Q { l: Int } extend enum E { M }\n",
+      "directive @n(o: Int) repeatable on FIELD | ENUM_VALUE\n",
+    );
+    assert_eq!(document, parse_without_location(source.chars()).unwrap());
+  }
+}
@@ -0,0 +1,280 @@
+//! A lossless concrete syntax tree (CST) of a GraphQL document.
+//!
+//! The AST throws away everything that has no meaning, like whitespace, commas
+//! and comments. The CST keeps every token of the source along with the trivia
+//! around it so that a document can be printed back exactly as it was written.
+//! The CST is viewed through the AST: every AST node covers a range of tokens
+//! which may be edited before printing the tree again.
+
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+
+use super::Position;
+use super::ast::{self, Node};
+use super::lexer::TokenKind;
+use super::tokenizer::{tokenize, LexemeKind};
+use super::parser::{parse, Error};
+
+/// Source text which has no meaning to the parser. Trivia is attached to the
+/// tokens around it.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Trivia {
+  /// A run of spaces, tabs, line terminators and byte order marks.
+  Whitespace(String),
+  /// A comma, which GraphQL ignores just like whitespace.
+  Comma,
+  /// A comment including its leading `#`, but not the newline ending it.
+  Comment(String),
+}
+
+impl fmt::Display for Trivia {
+  /// Writes the exact source text of this trivia.
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Trivia::Whitespace(ref text) => write!(f, "{}", text),
+      Trivia::Comma => write!(f, ","),
+      Trivia::Comment(ref text) => write!(f, "{}", text),
+    }
+  }
+}
+
+/// A token in the CST with its exact source text and the trivia around it.
+///
+/// Trivia on the same line after a token is the token’s trailing trivia.
+/// Everything else belongs to the leading trivia of the next token.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SyntaxToken {
+  /// The kind of the token as the lexer produced it.
+  pub kind: TokenKind,
+  /// The exact source text of the token. This is what gets printed, so it may
+  /// be changed to edit the document.
+  pub text: String,
+  /// The trivia before this token.
+  pub leading_trivia: Vec<Trivia>,
+  /// The trivia after this token.
+  pub trailing_trivia: Vec<Trivia>,
+  /// The position of the first character of the token.
+  pub start: Position,
+  /// The position of the last character of the token.
+  pub end: Position,
+}
+
+impl fmt::Display for SyntaxToken {
+  /// Writes the token with all of its trivia.
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for trivia in &self.leading_trivia {
+      try!(write!(f, "{}", trivia));
+    }
+    try!(write!(f, "{}", self.text));
+    for trivia in &self.trailing_trivia {
+      try!(write!(f, "{}", trivia));
+    }
+    Ok(())
+  }
+}
+
+/// A lossless syntax tree of a GraphQL document.
+///
+/// Printing the tree with `to_string` gives back the source text exactly, and
+/// any edits made to the tokens. The AST is not updated by edits, to get an
+/// updated AST parse the printed text again.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SyntaxTree {
+  document: ast::Document,
+  tokens: Vec<SyntaxToken>,
+}
+
+/// Parses a source text into a lossless syntax tree.
+///
+/// The source is lexed twice, once by the parser and once by the tokenizer
+/// which also keeps the trivia. Both are linear in the length of the source
+/// and use the same lexer, so the tokens line up with the AST without the
+/// parser having to keep track of trivia it otherwise has no use for.
+pub fn parse_cst(source: &str) -> Result<SyntaxTree, Error> {
+  let document = try!(parse(source.chars()));
+
+  // First split the source into tokens and trivia.
+  let mut pieces: Vec<Result<SyntaxToken, Trivia>> = Vec::new();
+  for lexeme in tokenize(source) {
+    pieces.push(match lexeme.kind {
+      LexemeKind::Token(TokenKind::Comment(_)) => Err(Trivia::Comment(String::from(lexeme.text))),
+      LexemeKind::Token(kind) => Ok(SyntaxToken {
+        kind: kind.into_owned(),
+        text: String::from(lexeme.text),
+        leading_trivia: Vec::new(),
+        trailing_trivia: Vec::new(),
+        start: lexeme.start,
+        end: lexeme.end,
+      }),
+      LexemeKind::Whitespace => Err(Trivia::Whitespace(String::from(lexeme.text))),
+      LexemeKind::Comma => Err(Trivia::Comma),
+      // The source was parsed without errors by the same lexer so this should
+      // never happen.
+      LexemeKind::Error(_) => return Err(Error::Unreachable),
+    });
+  }
+
+  // Then attach the trivia to the tokens. Trivia after the last token is all
+  // trailing trivia of the last token.
+  let mut tokens: Vec<SyntaxToken> = Vec::new();
+  let mut leading: Vec<Trivia> = Vec::new();
+  let mut trailing = false;
+  for piece in pieces {
+    match piece {
+      Ok(mut token) => {
+        token.leading_trivia = mem::take(&mut leading);
+        tokens.push(token);
+        trailing = true;
+      },
+      Err(trivia) => match tokens.last_mut() {
+        Some(ref mut token) if trailing => match trivia {
+          // A line terminator ends the trailing trivia of a token.
+          Trivia::Whitespace(ref text) if text.contains(is_line_terminator) => {
+            let newline = text.find(is_line_terminator).unwrap_or(0);
+            if newline > 0 {
+              token.trailing_trivia.push(Trivia::Whitespace(String::from(&text[..newline])));
+            }
+            leading.push(Trivia::Whitespace(String::from(&text[newline..])));
+            trailing = false;
+          },
+          trivia => token.trailing_trivia.push(trivia),
+        },
+        _ => leading.push(trivia),
+      },
+    }
+  }
+  if let Some(token) = tokens.last_mut() {
+    token.trailing_trivia.append(&mut leading);
+  }
+
+  Ok(SyntaxTree {
+    document: document,
+    tokens: tokens,
+  })
+}
+
+/// Whether a character ends a line. A `\r\n` pair ends a line at the `\r`.
+fn is_line_terminator(c: char) -> bool {
+  c == '\n' || c == '\r'
+}
+
+impl SyntaxTree {
+  /// The AST of the document this tree was parsed from. Use the nodes of this
+  /// AST to find the tokens they were parsed from with `node_tokens`.
+  pub fn document(&self) -> &ast::Document {
+    &self.document
+  }
+
+  /// Every token in the document in source order.
+  pub fn tokens(&self) -> &[SyntaxToken] {
+    &self.tokens
+  }
+
+  /// Every token in the document in source order, for editing.
+  pub fn tokens_mut(&mut self) -> &mut [SyntaxToken] {
+    &mut self.tokens
+  }
+
+  /// The tokens which a node of this tree’s AST was parsed from. The node
+  /// must come from `document`.
+  pub fn node_tokens<N>(&self, node: &N) -> &[SyntaxToken] where N: Node {
+    let range = self.node_range(node);
+    &self.tokens[range]
+  }
+
+  /// The tokens which a node of this tree’s AST was parsed from, for
+  /// editing. The node must come from `document`.
+  pub fn node_tokens_mut<N>(&mut self, node: &N) -> &mut [SyntaxToken] where N: Node {
+    let range = self.node_range(node);
+    &mut self.tokens[range]
+  }
+
+  /// Finds the range of tokens covered by the location of a node.
+  fn node_range<N>(&self, node: &N) -> Range<usize> where N: Node {
+    match node.loc() {
+      Some(loc) => {
+        let start = self.tokens.binary_search_by_key(&loc.start.index, |token| token.start.index).unwrap_or_else(|i| i);
+        let end = match self.tokens.binary_search_by_key(&loc.end.index, |token| token.end.index) {
+          Ok(i) => i + 1,
+          Err(i) => i,
+        };
+        start..end.max(start)
+      },
+      None => 0..0,
+    }
+  }
+}
+
+impl fmt::Display for SyntaxTree {
+  /// Writes every token with its trivia. Unless the tokens were edited this is
+  /// exactly the source text the tree was parsed from.
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for token in &self.tokens {
+      try!(write!(f, "{}", token));
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_lossless() {
+    let source = concat!(
+      "# A query.\n",
+      "query Q($a: [Int!] = [1,2]) @d(x: {y: \"z\\n\"}) {\n",
+      "  a: b(c: $a),, d # trailing\n",
+      "\n",
+      "  ... on T { e }\n",
+      "  f(g: \"\"\"\n",
+      "    block\n",
+      "  \"\"\")\n",
+      "}\n",
+      "\n",
+      "fragment F on T { g }  # the end\n",
+      "\n",
+    );
+    assert_eq!(parse_cst(source).unwrap().to_string(), source);
+  }
+
+  #[test]
+  fn test_lossless_crlf() {
+    let source = "\u{FEFF}# A query.\r\nquery Q {\r\n  a, # b\r\n  c(d: \"\"\"\r\n  e\r\n  \"\"\")\r}\r\n";
+    assert_eq!(parse_cst(source).unwrap().to_string(), source);
+    let tree = parse_cst("{ a # b\r\n  c }").unwrap();
+    assert_eq!(tree.tokens()[1].trailing_trivia, vec![Trivia::Whitespace(String::from(" ")), Trivia::Comment(String::from("# b"))]);
+    assert_eq!(tree.tokens()[2].leading_trivia, vec![Trivia::Whitespace(String::from("\r\n  "))]);
+  }
+
+  #[test]
+  fn test_trivia() {
+    let tree = parse_cst("# x\n{ a, # y\n  b }\n").unwrap();
+    let trivia = tree.tokens().iter().map(|token| (token.text.as_str(), token.leading_trivia.clone(), token.trailing_trivia.clone())).collect::<Vec<_>>();
+    assert_eq!(trivia, vec![
+      ("{", vec![Trivia::Comment(String::from("# x")), Trivia::Whitespace(String::from("\n"))], vec![Trivia::Whitespace(String::from(" "))]),
+      ("a", vec![], vec![Trivia::Comma, Trivia::Whitespace(String::from(" ")), Trivia::Comment(String::from("# y"))]),
+      ("b", vec![Trivia::Whitespace(String::from("\n  "))], vec![Trivia::Whitespace(String::from(" "))]),
+      ("}", vec![], vec![Trivia::Whitespace(String::from("\n"))]),
+    ]);
+  }
+
+  #[test]
+  fn test_node_tokens() {
+    let mut tree = parse_cst("query Q {\n  a(b: 1) # keep\n  c\n}\n").unwrap();
+    let (a, c) = match tree.document().definitions[0] {
+      ast::Definition::Operation(ref operation) => (operation.selection_set.selections[0].clone(), operation.selection_set.selections[1].clone()),
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    };
+    assert_eq!(tree.node_tokens(&a).iter().map(|token| token.text.as_str()).collect::<Vec<_>>(), vec!["a", "(", "b", ":", "1", ")"]);
+    tree.node_tokens_mut(&c)[0].text = String::from("renamed");
+    assert_eq!(tree.to_string(), "query Q {\n  a(b: 1) # keep\n  renamed\n}\n");
+  }
+
+  #[test]
+  fn test_error() {
+    assert!(parse_cst("{ a(b 1) }").is_err());
+  }
+}
@@ -0,0 +1,290 @@
+use super::Location;
+use super::lexer::Error as LexerError;
+use super::parser::{Error, SourceError};
+
+/// How serious a diagnostic is.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Severity {
+  /// The source document is invalid.
+  Error,
+  /// The source document is valid, but something may not be right.
+  Warning,
+}
+
+/// A problem found in a source document along with everything needed to show
+/// that problem to a human.
+///
+/// Diagnostics are created from parse errors with `From`, but may also be
+/// created directly by anything else that checks a source document.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+  /// How serious this diagnostic is.
+  pub severity: Severity,
+  /// A stable code which identifies the kind of this diagnostic.
+  pub code: &'static str,
+  /// A human readable message describing the problem.
+  pub message: String,
+  /// The range in the source document where the problem is, if known.
+  pub location: Option<Location>,
+}
+
+impl Diagnostic {
+  /// Creates a new error diagnostic.
+  pub fn error(code: &'static str, message: String, location: Option<Location>) -> Self {
+    Diagnostic {
+      severity: Severity::Error,
+      code: code,
+      message: message,
+      location: location,
+    }
+  }
+
+  /// Creates a new warning diagnostic.
+  pub fn warning(code: &'static str, message: String, location: Option<Location>) -> Self {
+    Diagnostic {
+      severity: Severity::Warning,
+      code: code,
+      message: message,
+      location: location,
+    }
+  }
+
+  /// Renders this diagnostic as plain text with the offending line from the
+  /// source document underlined. The source must be the same document the
+  /// diagnostic’s location refers to. If the location refers to a `Source`
+  /// the name of the source is shown before the line and column.
+  ///
+  /// ```txt
+  /// error[E0002]: Unexpected `}`, expected `)` or a name.
+  ///  --> 1:12
+  ///   |
+  /// 1 | { foo(a: 1 }
+  ///   |            ^
+  /// ```
+  pub fn render(&self, source: &str) -> String {
+    self.render_with_style(source, &PLAIN)
+  }
+
+  /// Renders this diagnostic like `render`, but with ANSI escape codes to
+  /// colour the output for a terminal.
+  pub fn render_ansi(&self, source: &str) -> String {
+    self.render_with_style(source, &ANSI)
+  }
+
+  fn render_with_style(&self, source: &str, style: &Style) -> String {
+    let mut output = String::new();
+
+    let (severity, severity_style) = match self.severity {
+      Severity::Error => ("error", style.error),
+      Severity::Warning => ("warning", style.warning),
+    };
+
+    output.push_str(&format!(
+      "{}{}[{}]{}{}: {}{}\n",
+      severity_style,
+      severity,
+      self.code,
+      style.reset,
+      style.bold,
+      self.message,
+      style.reset,
+    ));
+
+    let location = match self.location {
+      Some(ref location) => location,
+      None => return output,
+    };
+
+    let start = location.start;
+    let end = location.end;
+    let line_number = start.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+    // Lines may end with `\r\n` or `\r` as well as `\n`, so normalize the line
+    // terminators before finding the line.
+    let source = source.replace("\r\n", "\n").replace('\r', "\n");
+    let line = source.split('\n').nth(start.line.saturating_sub(1)).unwrap_or("");
+
+    // Columns are 1-indexed, but a position on a newline has a column of 0 so
+    // we make sure the column is at least 1.
+    let start_column = start.column.max(1);
+    let end_column = if end.line == start.line {
+      end.column.max(start_column)
+    } else {
+      line.chars().count().max(start_column)
+    };
+
+    // Keep tabs in the padding so the caret lines up with the source line.
+    let padding = line.chars().take(start_column - 1).map(|c| if c == '\t' { '\t' } else { ' ' }).collect::<String>();
+    let carets = "^".repeat(end_column - start_column + 1);
+
+    let name = location.source.as_ref().map_or(String::new(), |source| format!("{}:", source));
+    output.push_str(&format!("{}{}-->{} {}{}:{}\n", gutter, style.gutter, style.reset, name, start.line, start.column));
+    output.push_str(&format!("{} {}|{}\n", gutter, style.gutter, style.reset));
+    output.push_str(&format!("{}{} |{} {}\n", style.gutter, line_number, style.reset, line));
+    output.push_str(&format!("{} {}|{} {}{}{}{}\n", gutter, style.gutter, style.reset, padding, severity_style, carets, style.reset));
+
+    output
+  }
+}
+
+impl<'a> From<&'a Error> for Diagnostic {
+  fn from(error: &'a Error) -> Diagnostic {
+    Diagnostic::error(error.code(), error.message(), error.location())
+  }
+}
+
+impl<'a> From<&'a SourceError> for Diagnostic {
+  fn from(error: &'a SourceError) -> Diagnostic {
+    Diagnostic::error(error.error.code(), error.error.message(), error.location())
+  }
+}
+
+impl<'a> From<&'a LexerError> for Diagnostic {
+  fn from(error: &'a LexerError) -> Diagnostic {
+    let location = error.position().map(|position| Location::new(position, position));
+    Diagnostic::error(error.code(), error.message(), location)
+  }
+}
+
+/// The escape codes used to style a rendered diagnostic.
+struct Style {
+  error: &'static str,
+  warning: &'static str,
+  gutter: &'static str,
+  bold: &'static str,
+  reset: &'static str,
+}
+
+const PLAIN: Style = Style {
+  error: "",
+  warning: "",
+  gutter: "",
+  bold: "",
+  reset: "",
+};
+
+const ANSI: Style = Style {
+  error: "\x1b[1;31m",
+  warning: "\x1b[1;33m",
+  gutter: "\x1b[1;34m",
+  bold: "\x1b[1m",
+  reset: "\x1b[0m",
+};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::{parse, parse_source, parse_sources, Source};
+
+  fn render(source: &str) -> String {
+    Diagnostic::from(&parse(source.chars()).unwrap_err()).render(source)
+  }
+
+  #[test]
+  fn test_render_unexpected_token() {
+    assert_eq!(render("{ foo(a: 1 }"), concat!(
+      "error[E0002]: Unexpected `}`, expected `)` or a name.\n",
+      " --> 1:12\n",
+      "  |\n",
+      "1 | { foo(a: 1 }\n",
+      "  |            ^\n",
+    ));
+    assert_eq!(render("query {\n  foo\n  bar baz(\n}"), concat!(
+      "error[E0002]: Unexpected `}`, expected a name.\n",
+      " --> 4:1\n",
+      "  |\n",
+      "4 | }\n",
+      "  | ^\n",
+    ));
+  }
+
+  #[test]
+  fn test_render_crlf() {
+    assert_eq!(render("query {\r\n  foo\r\n  bar baz(\r}"), concat!(
+      "error[E0002]: Unexpected `}`, expected a name.\n",
+      " --> 4:1\n",
+      "  |\n",
+      "4 | }\n",
+      "  | ^\n",
+    ));
+  }
+
+  #[test]
+  fn test_render_token_width() {
+    assert_eq!(render("{ foo(a 123) }"), concat!(
+      "error[E0002]: Unexpected integer `123`, expected `:`.\n",
+      " --> 1:9\n",
+      "  |\n",
+      "1 | { foo(a 123) }\n",
+      "  |         ^^^\n",
+    ));
+  }
+
+  #[test]
+  fn test_render_tabs() {
+    assert_eq!(render("{\n\tfoo(\t}"), concat!(
+      "error[E0002]: Unexpected `}`, expected a name.\n",
+      " --> 2:7\n",
+      "  |\n",
+      "2 | \tfoo(\t}\n",
+      "  | \t    \t^\n",
+    ));
+  }
+
+  #[test]
+  fn test_render_lexer_error() {
+    assert_eq!(render("{ foo % }"), concat!(
+      "error[E0101]: Unexpected character '%'.\n",
+      " --> 1:7\n",
+      "  |\n",
+      "1 | { foo % }\n",
+      "  |       ^\n",
+    ));
+  }
+
+  #[test]
+  fn test_render_source_name() {
+    let source = Source::new("query.graphql", "{ foo }");
+    let document = parse_source(&source).unwrap();
+    let diagnostic = Diagnostic::warning("W0001", String::from("Something is odd."), document.loc.clone());
+    assert_eq!(diagnostic.render(source.body()), concat!(
+      "warning[W0001]: Something is odd.\n",
+      " --> query.graphql:1:1\n",
+      "  |\n",
+      "1 | { foo }\n",
+      "  | ^^^^^^^\n",
+    ));
+  }
+
+  #[test]
+  fn test_render_source_error() {
+    let sources = [Source::new("a.graphql", "{ a }"), Source::new("b.graphql", "{ b(c 1) }")];
+    let error = parse_sources(&sources).unwrap_err();
+    assert_eq!(Diagnostic::from(&error).render(error.source.body()), concat!(
+      "error[E0002]: Unexpected integer `1`, expected `:`.\n",
+      " --> b.graphql:1:7\n",
+      "  |\n",
+      "1 | { b(c 1) }\n",
+      "  |       ^\n",
+    ));
+  }
+
+  #[test]
+  fn test_render_without_location() {
+    let diagnostic = Diagnostic::warning("W0001", String::from("Something is odd."), None);
+    assert_eq!(diagnostic.render(""), "warning[W0001]: Something is odd.\n");
+  }
+
+  #[test]
+  fn test_render_ansi() {
+    let source = "{ foo(a 1) }";
+    let diagnostic = Diagnostic::from(&parse(source.chars()).unwrap_err());
+    assert_eq!(diagnostic.render_ansi(source), concat!(
+      "\x1b[1;31merror[E0002]\x1b[0m\x1b[1m: Unexpected integer `1`, expected `:`.\x1b[0m\n",
+      " \x1b[1;34m-->\x1b[0m 1:9\n",
+      "  \x1b[1;34m|\x1b[0m\n",
+      "\x1b[1;34m1 |\x1b[0m { foo(a 1) }\n",
+      "  \x1b[1;34m|\x1b[0m         \x1b[1;31m^\x1b[0m\n",
+    ));
+  }
+}
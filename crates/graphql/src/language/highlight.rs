@@ -0,0 +1,346 @@
+//! Syntax highlighting for GraphQL source text.
+//!
+//! The tokenizer classifies every piece of the source, and the AST from a
+//! recovering parse adds the context the tokens alone don’t have. A name may
+//! be a field, a type, an argument or a keyword depending on where it is.
+
+use std::collections::HashMap;
+
+use super::ast::*;
+use super::lexer::TokenKind;
+use super::parser::{parse_with_recovery, Error, Limit};
+use super::tokenizer::{tokenize, LexemeKind};
+use super::visitor::{visit, Visitor};
+
+/// What a piece of highlighted source text is.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Highlight {
+  /// A keyword, like `query`, `fragment` or `on`.
+  Keyword,
+  /// The name of an operation definition.
+  Operation,
+  /// The name of a fragment in a definition or a spread.
+  Fragment,
+  /// The name or alias of a field, or the name of a field definition.
+  Field,
+  /// The name of an argument or an input object field.
+  Argument,
+  /// The name of a type.
+  Type,
+  /// A variable, including its `$`.
+  Variable,
+  /// A directive name, including its `@`.
+  Directive,
+  /// A boolean, null or enum value.
+  Constant,
+  /// A string, block string or description.
+  String,
+  /// An integer or float.
+  Number,
+  /// A comment.
+  Comment,
+  /// Punctuation, like braces and colons.
+  Punctuation,
+  /// Any other name, like one in a document with syntax errors.
+  Name,
+  /// Characters which could not be lexed.
+  Error,
+}
+
+impl Highlight {
+  /// The CSS class used for this highlight in HTML.
+  pub fn class(&self) -> &'static str {
+    match *self {
+      Highlight::Keyword => "gql-keyword",
+      Highlight::Operation => "gql-operation",
+      Highlight::Fragment => "gql-fragment",
+      Highlight::Field => "gql-field",
+      Highlight::Argument => "gql-argument",
+      Highlight::Type => "gql-type",
+      Highlight::Variable => "gql-variable",
+      Highlight::Directive => "gql-directive",
+      Highlight::Constant => "gql-constant",
+      Highlight::String => "gql-string",
+      Highlight::Number => "gql-number",
+      Highlight::Comment => "gql-comment",
+      Highlight::Punctuation => "gql-punctuation",
+      Highlight::Name => "gql-name",
+      Highlight::Error => "gql-error",
+    }
+  }
+
+  /// The ANSI escape code used for this highlight in a terminal.
+  pub fn ansi(&self) -> &'static str {
+    match *self {
+      Highlight::Keyword => "\x1b[35m",
+      Highlight::Operation => "\x1b[1;34m",
+      Highlight::Fragment => "\x1b[1;33m",
+      Highlight::Field => "\x1b[34m",
+      Highlight::Argument => "\x1b[36m",
+      Highlight::Type => "\x1b[33m",
+      Highlight::Variable => "\x1b[31m",
+      Highlight::Directive => "\x1b[1;35m",
+      Highlight::Constant => "\x1b[1;31m",
+      Highlight::String => "\x1b[32m",
+      Highlight::Number => "\x1b[1;32m",
+      Highlight::Comment => "\x1b[90m",
+      Highlight::Punctuation => "",
+      Highlight::Name => "",
+      Highlight::Error => "\x1b[4;31m",
+    }
+  }
+}
+
+/// The names which are keywords when they are not used as anything else.
+const KEYWORDS: &[&str] = &[
+  "query", "mutation", "subscription", "fragment", "on",
+  "schema", "scalar", "type", "interface", "union", "enum", "input", "extend", "directive", "implements", "repeatable",
+];
+
+/// Splits a source text into highlighted pieces. Joining the text of all the
+/// pieces gives back the source. Whitespace and commas have no highlight.
+///
+/// The source does not need to be valid, any parts of the source which can’t
+/// be parsed are highlighted using only their tokens. So is all of a source
+/// which is nested too deeply to parse.
+pub fn highlight<'a>(source: &'a str) -> Vec<(Option<Highlight>, &'a str)> {
+  let (document, errors) = parse_with_recovery(source.chars());
+  let mut names = Names { highlights: HashMap::new() };
+  let too_deep = errors.iter().any(|error| matches!(*error, Error::LimitExceeded(_, Limit::Depth(_))));
+  if !too_deep {
+    visit(&mut names, document);
+  }
+
+  tokenize(source).map(|lexeme| {
+    let highlight = match lexeme.kind {
+      LexemeKind::Token(TokenKind::Name(ref name)) => Some(match names.highlights.get(&lexeme.start.offset) {
+        Some(&highlight) => highlight,
+        None if KEYWORDS.contains(&name.as_ref()) => Highlight::Keyword,
+        None => Highlight::Name,
+      }),
+      LexemeKind::Token(TokenKind::Dollar) => Some(Highlight::Variable),
+      LexemeKind::Token(TokenKind::At) => Some(Highlight::Directive),
+      LexemeKind::Token(TokenKind::Int(_)) |
+      LexemeKind::Token(TokenKind::Float(_)) => Some(Highlight::Number),
+      LexemeKind::Token(TokenKind::String(_)) |
+      LexemeKind::Token(TokenKind::BlockString(_)) => Some(Highlight::String),
+      LexemeKind::Token(TokenKind::Comment(_)) => Some(Highlight::Comment),
+      LexemeKind::Token(_) => Some(Highlight::Punctuation),
+      LexemeKind::Error(_) => Some(Highlight::Error),
+      LexemeKind::Whitespace |
+      LexemeKind::Comma => None,
+    };
+    (highlight, lexeme.text)
+  }).collect()
+}
+
+/// Highlights a source text with ANSI escape codes for a terminal.
+pub fn highlight_ansi(source: &str) -> String {
+  let mut output = String::new();
+  for (highlight, text) in highlight(source) {
+    match highlight.map(|highlight| highlight.ansi()) {
+      Some(code) if !code.is_empty() => {
+        output.push_str(code);
+        output.push_str(text);
+        output.push_str("\x1b[0m");
+      },
+      _ => output.push_str(text),
+    }
+  }
+  output
+}
+
+/// Highlights a source text as HTML. Every highlighted piece is wrapped in a
+/// `<span>` with the class of its highlight, and all text is escaped. The
+/// output is meant to go inside of a `<pre>` element.
+///
+/// ```txt
+/// <span class="gql-keyword">query</span> <span class="gql-punctuation">{</span> …
+/// ```
+pub fn highlight_html(source: &str) -> String {
+  let mut output = String::new();
+  for (highlight, text) in highlight(source) {
+    match highlight {
+      Some(highlight) => {
+        output.push_str("<span class=\"");
+        output.push_str(highlight.class());
+        output.push_str("\">");
+        push_html(&mut output, text);
+        output.push_str("</span>");
+      },
+      None => push_html(&mut output, text),
+    }
+  }
+  output
+}
+
+/// Pushes text to some HTML output, escaping the characters that HTML gives
+/// meaning to.
+fn push_html(output: &mut String, text: &str) {
+  for c in text.chars() {
+    match c {
+      '&' => output.push_str("&amp;"),
+      '<' => output.push_str("&lt;"),
+      '>' => output.push_str("&gt;"),
+      '"' => output.push_str("&quot;"),
+      '\'' => output.push_str("&#39;"),
+      c => output.push(c),
+    }
+  }
+}
+
+/// A visitor which finds out what every name in a document is, by the byte
+/// offset of the name.
+struct Names {
+  highlights: HashMap<usize, Highlight>,
+}
+
+impl Names {
+  fn mark<N>(&mut self, node: &N, highlight: Highlight) where N: Node {
+    if let Some(loc) = node.loc() {
+      self.highlights.insert(loc.start.offset, highlight);
+    }
+  }
+}
+
+/// A utility for generating a visitor function which marks the name of a
+/// node.
+macro_rules! fn_marks {
+  ($type_:ty, $enter_fn:ident, $highlight:expr) => (
+    fn $enter_fn(&mut self, node: $type_) -> $type_ {
+      self.mark(&node.name, $highlight);
+      node
+    }
+  )
+}
+
+impl Visitor for Names {
+  fn_marks!(Variable, enter_variable, Highlight::Variable);
+  fn_marks!(Argument, enter_argument, Highlight::Argument);
+  fn_marks!(FragmentSpread, enter_fragment_spread, Highlight::Fragment);
+  fn_marks!(FragmentDefinition, enter_fragment_definition, Highlight::Fragment);
+  fn_marks!(ObjectField, enter_object_field, Highlight::Argument);
+  fn_marks!(Directive, enter_directive, Highlight::Directive);
+  fn_marks!(NamedType, enter_named_type, Highlight::Type);
+
+  fn enter_operation_definition(&mut self, node: OperationDefinition) -> OperationDefinition {
+    if let Some(ref name) = node.name {
+      self.mark(name, Highlight::Operation);
+    }
+    node
+  }
+
+  fn enter_field(&mut self, node: Field) -> Field {
+    if let Some(ref alias) = node.alias {
+      self.mark(alias, Highlight::Field);
+    }
+    self.mark(&node.name, Highlight::Field);
+    node
+  }
+
+  fn enter_boolean_value(&mut self, node: BooleanValue) -> BooleanValue {
+    self.mark(&node, Highlight::Constant);
+    node
+  }
+
+  fn enter_null_value(&mut self, node: NullValue) -> NullValue {
+    self.mark(&node, Highlight::Constant);
+    node
+  }
+
+  fn enter_enum_value(&mut self, node: EnumValue) -> EnumValue {
+    self.mark(&node, Highlight::Constant);
+    node
+  }
+
+  // The type system AST extension is an experimental non-spec addition.
+  #[cfg(feature = "type_system")] fn_marks!(ScalarTypeDefinition, enter_scalar_type_definition, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(ObjectTypeDefinition, enter_object_type_definition, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(FieldDefinition, enter_field_definition, Highlight::Field);
+  #[cfg(feature = "type_system")] fn_marks!(InputValueDefinition, enter_input_value_definition, Highlight::Argument);
+  #[cfg(feature = "type_system")] fn_marks!(InterfaceTypeDefinition, enter_interface_type_definition, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(UnionTypeDefinition, enter_union_type_definition, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(EnumTypeDefinition, enter_enum_type_definition, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(EnumValueDefinition, enter_enum_value_definition, Highlight::Constant);
+  #[cfg(feature = "type_system")] fn_marks!(InputObjectTypeDefinition, enter_input_object_type_definition, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(ScalarTypeExtension, enter_scalar_type_extension, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(ObjectTypeExtension, enter_object_type_extension, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(InterfaceTypeExtension, enter_interface_type_extension, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(UnionTypeExtension, enter_union_type_extension, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(EnumTypeExtension, enter_enum_type_extension, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(InputObjectTypeExtension, enter_input_object_type_extension, Highlight::Type);
+  #[cfg(feature = "type_system")] fn_marks!(DirectiveDefinition, enter_directive_definition, Highlight::Directive);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_highlight() {
+    let source = "query Q($a: Int = 1) { type: on(b: {c: true}) @d ...F }\nfragment F on T { e(f: ENUM, g: \"h\") } # i";
+    assert_eq!(highlight(source), vec![
+      (Some(Highlight::Keyword), "query"), (None, " "), (Some(Highlight::Operation), "Q"),
+      (Some(Highlight::Punctuation), "("), (Some(Highlight::Variable), "$"), (Some(Highlight::Variable), "a"),
+      (Some(Highlight::Punctuation), ":"), (None, " "), (Some(Highlight::Type), "Int"), (None, " "),
+      (Some(Highlight::Punctuation), "="), (None, " "), (Some(Highlight::Number), "1"), (Some(Highlight::Punctuation), ")"), (None, " "),
+      (Some(Highlight::Punctuation), "{"), (None, " "), (Some(Highlight::Field), "type"), (Some(Highlight::Punctuation), ":"), (None, " "),
+      (Some(Highlight::Field), "on"), (Some(Highlight::Punctuation), "("), (Some(Highlight::Argument), "b"), (Some(Highlight::Punctuation), ":"), (None, " "),
+      (Some(Highlight::Punctuation), "{"), (Some(Highlight::Argument), "c"), (Some(Highlight::Punctuation), ":"), (None, " "),
+      (Some(Highlight::Constant), "true"), (Some(Highlight::Punctuation), "}"), (Some(Highlight::Punctuation), ")"), (None, " "),
+      (Some(Highlight::Directive), "@"), (Some(Highlight::Directive), "d"), (None, " "),
+      (Some(Highlight::Punctuation), "..."), (Some(Highlight::Fragment), "F"), (None, " "), (Some(Highlight::Punctuation), "}"), (None, "\n"),
+      (Some(Highlight::Keyword), "fragment"), (None, " "), (Some(Highlight::Fragment), "F"), (None, " "), (Some(Highlight::Keyword), "on"), (None, " "),
+      (Some(Highlight::Type), "T"), (None, " "), (Some(Highlight::Punctuation), "{"), (None, " "), (Some(Highlight::Field), "e"),
+      (Some(Highlight::Punctuation), "("), (Some(Highlight::Argument), "f"), (Some(Highlight::Punctuation), ":"), (None, " "),
+      (Some(Highlight::Constant), "ENUM"), (None, ","), (None, " "), (Some(Highlight::Argument), "g"), (Some(Highlight::Punctuation), ":"), (None, " "),
+      (Some(Highlight::String), "\"h\""), (Some(Highlight::Punctuation), ")"), (None, " "), (Some(Highlight::Punctuation), "}"), (None, " "),
+      (Some(Highlight::Comment), "# i"),
+    ]);
+  }
+
+  #[test]
+  fn test_highlight_invalid() {
+    assert_eq!(highlight("{ a( % }"), vec![
+      (Some(Highlight::Punctuation), "{"), (None, " "), (Some(Highlight::Name), "a"), (Some(Highlight::Punctuation), "("), (None, " "),
+      (Some(Highlight::Error), "%"), (None, " "), (Some(Highlight::Punctuation), "}"),
+    ]);
+  }
+
+  #[test]
+  fn test_highlight_deep() {
+    let source = format!("{{ a(b: {}", "[".repeat(200000));
+    let spans = highlight(&source);
+    assert_eq!(spans.len(), 200007);
+    assert_eq!(spans[2], (Some(Highlight::Name), "a"));
+    assert_eq!(spans[4], (Some(Highlight::Name), "b"));
+    assert_eq!(spans[200006], (Some(Highlight::Punctuation), "["));
+  }
+
+  #[test]
+  fn test_highlight_ansi() {
+    assert_eq!(highlight_ansi("{ a(b: 1) }"), "{ \x1b[34ma\x1b[0m(\x1b[36mb\x1b[0m: \x1b[1;32m1\x1b[0m) }");
+  }
+
+  #[test]
+  fn test_highlight_html() {
+    assert_eq!(highlight_html("{ a(b: \"<&>\") }"), concat!(
+      "<span class=\"gql-punctuation\">{</span> <span class=\"gql-field\">a</span>",
+      "<span class=\"gql-punctuation\">(</span><span class=\"gql-argument\">b</span><span class=\"gql-punctuation\">:</span> ",
+      "<span class=\"gql-string\">&quot;&lt;&amp;&gt;&quot;</span><span class=\"gql-punctuation\">)</span> <span class=\"gql-punctuation\">}</span>",
+    ));
+  }
+
+  #[cfg(feature = "type_system")]
+  #[test]
+  fn test_highlight_type_system() {
+    let spans = highlight("\"a\" type A implements B { c(d: Int = E): [A!] @f }\nenum E { G }");
+    let names = spans.into_iter().filter(|&(highlight, _)| highlight.is_some() && highlight != Some(Highlight::Punctuation)).collect::<Vec<_>>();
+    assert_eq!(names, vec![
+      (Some(Highlight::String), "\"a\""), (Some(Highlight::Keyword), "type"), (Some(Highlight::Type), "A"), (Some(Highlight::Keyword), "implements"),
+      (Some(Highlight::Type), "B"), (Some(Highlight::Field), "c"), (Some(Highlight::Argument), "d"), (Some(Highlight::Type), "Int"),
+      (Some(Highlight::Constant), "E"), (Some(Highlight::Type), "A"), (Some(Highlight::Directive), "@"), (Some(Highlight::Directive), "f"),
+      (Some(Highlight::Keyword), "enum"), (Some(Highlight::Type), "E"), (Some(Highlight::Constant), "G"),
+    ]);
+  }
+}
@@ -0,0 +1,971 @@
+//! Serde serialization of the AST in the JSON shape of the graphql-js AST.
+//!
+//! Every node is an object with a `kind`, like `{"kind": "Name", "value":
+//! "foo"}`, and fields named as graphql-js names them. Fields which are `None`
+//! are left out, just like `undefined` fields are left out by
+//! `JSON.stringify`, and fields which are missing are read as `None`, empty
+//! lists or `false`.
+//!
+//! A location is written with the `start` and `end` character offsets of
+//! graphql-js, along with our full start and end positions. Locations written
+//! by graphql-js don’t have positions so they are read as `None`. The source
+//! of a location is never written.
+//!
+//! Nodes are deserialized through a generic JSON value, so they can only be
+//! read from self-describing formats like JSON.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::ser::{Serialize, Serializer, SerializeMap, SerializeSeq};
+use serde::de::{self, Deserialize, Deserializer, Visitor, SeqAccess, MapAccess};
+
+use super::{Position, Location};
+use super::ast::*;
+
+/// A JSON value which nodes are converted to before serializing, and from
+/// after deserializing.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Json {
+  Null,
+  Bool(bool),
+  Number(u64),
+  String(String),
+  Array(Vec<Json>),
+  Object(Vec<(String, Json)>),
+}
+
+impl Serialize for Json {
+  fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error> where T: Serializer {
+    match *self {
+      Json::Null => serializer.serialize_unit(),
+      Json::Bool(value) => serializer.serialize_bool(value),
+      Json::Number(value) => serializer.serialize_u64(value),
+      Json::String(ref value) => serializer.serialize_str(value),
+      Json::Array(ref values) => {
+        let mut seq = try!(serializer.serialize_seq(Some(values.len())));
+        for value in values {
+          try!(seq.serialize_element(value));
+        }
+        seq.end()
+      },
+      Json::Object(ref fields) => {
+        let mut map = try!(serializer.serialize_map(Some(fields.len())));
+        for &(ref key, ref value) in fields {
+          try!(map.serialize_entry(key, value));
+        }
+        map.end()
+      },
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for Json {
+  fn deserialize<D>(deserializer: D) -> Result<Json, D::Error> where D: Deserializer<'de> {
+    deserializer.deserialize_any(JsonVisitor)
+  }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+  type Value = Json;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a GraphQL AST")
+  }
+
+  fn visit_unit<E>(self) -> Result<Json, E> {
+    Ok(Json::Null)
+  }
+
+  fn visit_none<E>(self) -> Result<Json, E> {
+    Ok(Json::Null)
+  }
+
+  fn visit_some<D>(self, deserializer: D) -> Result<Json, D::Error> where D: Deserializer<'de> {
+    Json::deserialize(deserializer)
+  }
+
+  fn visit_bool<E>(self, value: bool) -> Result<Json, E> {
+    Ok(Json::Bool(value))
+  }
+
+  fn visit_u64<E>(self, value: u64) -> Result<Json, E> {
+    Ok(Json::Number(value))
+  }
+
+  fn visit_i64<E>(self, value: i64) -> Result<Json, E> where E: de::Error {
+    if value >= 0 {
+      Ok(Json::Number(value as u64))
+    } else {
+      Err(E::invalid_value(de::Unexpected::Signed(value), &"a non-negative integer"))
+    }
+  }
+
+  fn visit_f64<E>(self, value: f64) -> Result<Json, E> where E: de::Error {
+    // JavaScript has no integers, so some tools write offsets as floats. The
+    // bound is 2^64, since `u64::MAX` as a float rounds up to it.
+    if value >= 0.0 && value.fract() == 0.0 && value < 18446744073709551616.0 {
+      Ok(Json::Number(value as u64))
+    } else {
+      Err(E::invalid_value(de::Unexpected::Float(value), &"a non-negative integer"))
+    }
+  }
+
+  fn visit_str<E>(self, value: &str) -> Result<Json, E> {
+    Ok(Json::String(String::from(value)))
+  }
+
+  fn visit_string<E>(self, value: String) -> Result<Json, E> {
+    Ok(Json::String(value))
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<Json, A::Error> where A: SeqAccess<'de> {
+    let mut values = Vec::new();
+    while let Some(value) = try!(seq.next_element()) {
+      values.push(value);
+    }
+    Ok(Json::Array(values))
+  }
+
+  fn visit_map<A>(self, mut map: A) -> Result<Json, A::Error> where A: MapAccess<'de> {
+    let mut fields = Vec::new();
+    while let Some(field) = try!(map.next_entry()) {
+      fields.push(field);
+    }
+    Ok(Json::Object(fields))
+  }
+}
+
+/// A value which is converted to and from JSON in the graphql-js shape.
+///
+/// This trait is public so that it may bound the text type of nodes, but it
+/// can’t be named outside of this crate.
+pub trait JsonNode: Sized {
+  /// Converts this value into JSON.
+  fn to_json(&self) -> Json;
+
+  /// Converts JSON into this value, or returns a message saying why it
+  /// couldn’t.
+  fn from_json(json: Json) -> Result<Self, String>;
+
+  /// Whether JSON with this `kind` can be converted into this value. Used to
+  /// pick the variant of a node enum.
+  fn has_kind(_kind: &str) -> bool {
+    false
+  }
+}
+
+impl JsonNode for String {
+  fn to_json(&self) -> Json {
+    Json::String(self.clone())
+  }
+
+  fn from_json(json: Json) -> Result<String, String> {
+    match json {
+      Json::String(value) => Ok(value),
+      _ => Err(String::from("expected a string")),
+    }
+  }
+}
+
+impl<'a> JsonNode for Cow<'a, str> {
+  fn to_json(&self) -> Json {
+    Json::String(String::from(self.as_ref()))
+  }
+
+  fn from_json(json: Json) -> Result<Cow<'a, str>, String> {
+    String::from_json(json).map(Cow::Owned)
+  }
+}
+
+impl JsonNode for bool {
+  fn to_json(&self) -> Json {
+    Json::Bool(*self)
+  }
+
+  fn from_json(json: Json) -> Result<bool, String> {
+    match json {
+      Json::Bool(value) => Ok(value),
+      Json::Null => Ok(false),
+      _ => Err(String::from("expected a boolean")),
+    }
+  }
+}
+
+impl<T> JsonNode for Option<T> where T: JsonNode {
+  fn to_json(&self) -> Json {
+    self.as_ref().map_or(Json::Null, JsonNode::to_json)
+  }
+
+  fn from_json(json: Json) -> Result<Option<T>, String> {
+    match json {
+      Json::Null => Ok(None),
+      json => T::from_json(json).map(Some),
+    }
+  }
+}
+
+impl<T> JsonNode for Vec<T> where T: JsonNode {
+  fn to_json(&self) -> Json {
+    Json::Array(self.iter().map(JsonNode::to_json).collect())
+  }
+
+  fn from_json(json: Json) -> Result<Vec<T>, String> {
+    match json {
+      Json::Array(values) => values.into_iter().map(T::from_json).collect(),
+      Json::Null => Ok(Vec::new()),
+      _ => Err(String::from("expected a list")),
+    }
+  }
+}
+
+impl<T> JsonNode for Box<T> where T: JsonNode {
+  fn to_json(&self) -> Json {
+    (**self).to_json()
+  }
+
+  fn from_json(json: Json) -> Result<Box<T>, String> {
+    T::from_json(json).map(Box::new)
+  }
+}
+
+impl JsonNode for OperationType {
+  fn to_json(&self) -> Json {
+    Json::String(String::from(match *self {
+      OperationType::Query => "query",
+      OperationType::Mutation => "mutation",
+      OperationType::Subscription => "subscription",
+    }))
+  }
+
+  fn from_json(json: Json) -> Result<OperationType, String> {
+    match json {
+      Json::String(ref operation) if operation == "query" => Ok(OperationType::Query),
+      Json::String(ref operation) if operation == "mutation" => Ok(OperationType::Mutation),
+      Json::String(ref operation) if operation == "subscription" => Ok(OperationType::Subscription),
+      _ => Err(String::from("expected an operation type")),
+    }
+  }
+}
+
+/// graphql-js parses directive locations as names.
+impl JsonNode for DirectiveLocation {
+  fn to_json(&self) -> Json {
+    Json::Object(vec![
+      (String::from("kind"), Json::String(String::from("Name"))),
+      (String::from("value"), Json::String(String::from(self.name()))),
+    ])
+  }
+
+  fn from_json(json: Json) -> Result<DirectiveLocation, String> {
+    let name = try!(Name::<String>::from_json(json));
+    DirectiveLocation::from_name(&name.value).ok_or_else(|| format!("unknown directive location `{}`", name.value))
+  }
+}
+
+impl JsonNode for Position {
+  fn to_json(&self) -> Json {
+    Json::Object(vec![
+      (String::from("index"), Json::Number(self.index as u64)),
+      (String::from("offset"), Json::Number(self.offset as u64)),
+      (String::from("line"), Json::Number(self.line as u64)),
+      (String::from("column"), Json::Number(self.column as u64)),
+    ])
+  }
+
+  fn from_json(json: Json) -> Result<Position, String> {
+    let mut fields = match json {
+      Json::Object(fields) => fields,
+      _ => return Err(String::from("expected a position")),
+    };
+    let mut number = |key: &str| match take(&mut fields, key) {
+      Json::Number(number) => Ok(number as usize),
+      _ => Err(format!("`{}`: expected a number", key)),
+    };
+    Ok(Position {
+      index: try!(number("index")),
+      offset: try!(number("offset")),
+      line: try!(number("line")),
+      column: try!(number("column")),
+    })
+  }
+}
+
+/// Converts a location into JSON. The end offset of graphql-js is just past
+/// the last character.
+fn loc_to_json(loc: &Option<Location>) -> Json {
+  match *loc {
+    Some(ref loc) => Json::Object(vec![
+      (String::from("start"), Json::Number(loc.start.index as u64)),
+      (String::from("end"), Json::Number(loc.end.index as u64 + 1)),
+      (String::from("startPosition"), loc.start.to_json()),
+      (String::from("endPosition"), loc.end.to_json()),
+    ]),
+    None => Json::Null,
+  }
+}
+
+/// Converts JSON into a location. Locations without positions can’t be
+/// converted so they are `None`.
+fn loc_from_json(json: Json) -> Result<Option<Location>, String> {
+  let mut fields = match json {
+    Json::Object(fields) => fields,
+    Json::Null => return Ok(None),
+    _ => return Err(String::from("expected a location")),
+  };
+  match (take(&mut fields, "startPosition"), take(&mut fields, "endPosition")) {
+    (Json::Null, _) | (_, Json::Null) => Ok(None),
+    (start, end) => Ok(Some(Location::new(try!(Position::from_json(start)), try!(Position::from_json(end))))),
+  }
+}
+
+/// Takes the value of a field out of the fields of an object. A missing field
+/// is `null`.
+fn take(fields: &mut Vec<(String, Json)>, key: &str) -> Json {
+  match fields.iter().position(|&(ref field_key, _)| field_key == key) {
+    Some(i) => fields.swap_remove(i).1,
+    None => Json::Null,
+  }
+}
+
+/// Takes the value of a field out of the fields of a node and converts it.
+fn field<T>(fields: &mut Vec<(String, Json)>, key: &str) -> Result<T, String> where T: JsonNode {
+  T::from_json(take(fields, key)).map_err(|error| format!("`{}`: {}", key, error))
+}
+
+/// Gets the fields of a node with a kind from JSON.
+fn node_fields(kind: &str, json: Json) -> Result<Vec<(String, Json)>, String> {
+  match json {
+    Json::Object(mut fields) => match take(&mut fields, "kind") {
+      Json::String(ref node_kind) if node_kind == kind => Ok(fields),
+      _ => Err(format!("expected a `{}` node", kind)),
+    },
+    _ => Err(format!("expected a `{}` node", kind)),
+  }
+}
+
+/// Gets the kind of a node from JSON.
+fn node_kind(json: &Json) -> Option<&str> {
+  match *json {
+    Json::Object(ref fields) => fields.iter().filter_map(|&(ref key, ref value)| match *value {
+      Json::String(ref kind) if key == "kind" => Some(kind.as_str()),
+      _ => None,
+    }).next(),
+    _ => None,
+  }
+}
+
+/// Implements `Serialize` and `Deserialize` through `JsonNode`.
+macro_rules! impl_serde {
+  ($type_name:ident<S>) => (
+    impl<S> Serialize for $type_name<S> where S: JsonNode {
+      fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error> where T: Serializer {
+        self.to_json().serialize(serializer)
+      }
+    }
+
+    impl<'de, S> Deserialize<'de> for $type_name<S> where S: JsonNode {
+      fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        Json::deserialize(deserializer).and_then(|json| Self::from_json(json).map_err(de::Error::custom))
+      }
+    }
+  );
+  ($type_name:ident) => (
+    impl Serialize for $type_name {
+      fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error> where T: Serializer {
+        self.to_json().serialize(serializer)
+      }
+    }
+
+    impl<'de> Deserialize<'de> for $type_name {
+      fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        Json::deserialize(deserializer).and_then(|json| Self::from_json(json).map_err(de::Error::custom))
+      }
+    }
+  );
+}
+
+/// Implements `JsonNode`, `Serialize` and `Deserialize` for a node struct
+/// with its graphql-js kind and the graphql-js key of every field.
+macro_rules! json_struct {
+  ($kind:expr, $struct_name:ident<S> { $($(#[$field_attr:meta])* $field_name:ident: $key:expr,)* }) => (
+    impl<S> JsonNode for $struct_name<S> where S: JsonNode {
+      fn to_json(&self) -> Json {
+        let mut fields = vec![(String::from("kind"), Json::String(String::from($kind)))];
+        $(
+          $(#[$field_attr])*
+          fields.push((String::from($key), self.$field_name.to_json()));
+        )*
+        fields.push((String::from("loc"), loc_to_json(&self.loc)));
+        fields.retain(|&(_, ref value)| *value != Json::Null);
+        Json::Object(fields)
+      }
+
+      fn from_json(json: Json) -> Result<Self, String> {
+        let mut _fields = try!(node_fields($kind, json));
+        Ok($struct_name {
+          loc: try!(loc_from_json(take(&mut _fields, "loc"))),
+          $(
+            $(#[$field_attr])*
+            $field_name: try!(field(&mut _fields, $key)),
+          )*
+        })
+      }
+
+      fn has_kind(kind: &str) -> bool {
+        kind == $kind
+      }
+    }
+
+    impl_serde!($struct_name<S>);
+  );
+  ($kind:expr, $struct_name:ident { $($field_name:ident: $key:expr,)* }) => (
+    impl JsonNode for $struct_name {
+      fn to_json(&self) -> Json {
+        let mut fields = vec![(String::from("kind"), Json::String(String::from($kind)))];
+        $(
+          fields.push((String::from($key), self.$field_name.to_json()));
+        )*
+        fields.push((String::from("loc"), loc_to_json(&self.loc)));
+        fields.retain(|&(_, ref value)| *value != Json::Null);
+        Json::Object(fields)
+      }
+
+      fn from_json(json: Json) -> Result<Self, String> {
+        let mut _fields = try!(node_fields($kind, json));
+        Ok($struct_name {
+          loc: try!(loc_from_json(take(&mut _fields, "loc"))),
+          $(
+            $field_name: try!(field(&mut _fields, $key)),
+          )*
+        })
+      }
+
+      fn has_kind(kind: &str) -> bool {
+        kind == $kind
+      }
+    }
+
+    impl_serde!($struct_name);
+  );
+}
+
+/// Implements `JsonNode`, `Serialize` and `Deserialize` for a node enum. The
+/// variant is picked by the kind of the node.
+macro_rules! json_enum {
+  ($enum_name:ident<S> { $($(#[$variant_attr:meta])* $variant_name:ident($variant_type:ty),)* }) => (
+    impl<S> JsonNode for $enum_name<S> where S: JsonNode {
+      fn to_json(&self) -> Json {
+        match *self {
+          $(
+            $(#[$variant_attr])*
+            $enum_name::$variant_name(ref node) => node.to_json(),
+          )*
+        }
+      }
+
+      fn from_json(json: Json) -> Result<Self, String> {
+        let kind = node_kind(&json).map(String::from).unwrap_or_default();
+        $(
+          $(#[$variant_attr])*
+          {
+            if <$variant_type as JsonNode>::has_kind(&kind) {
+              return <$variant_type as JsonNode>::from_json(json).map($enum_name::$variant_name);
+            }
+          }
+        )*
+        Err(format!("unexpected kind `{}`", kind))
+      }
+
+      fn has_kind(kind: &str) -> bool {
+        $(
+          $(#[$variant_attr])*
+          {
+            if <$variant_type as JsonNode>::has_kind(kind) {
+              return true;
+            }
+          }
+        )*
+        false
+      }
+    }
+
+    impl_serde!($enum_name<S>);
+  );
+}
+
+json_struct!("Name", Name<S> {
+  value: "value",
+});
+
+json_struct!("Document", Document<S> {
+  definitions: "definitions",
+});
+
+json_enum!(Definition<S> {
+  Operation(OperationDefinition<S>),
+  Fragment(FragmentDefinition<S>),
+  #[cfg(feature = "type_system")]
+  TypeSystem(TypeSystemDefinition<S>),
+});
+
+json_struct!("OperationDefinition", OperationDefinition<S> {
+  operation: "operation",
+  name: "name",
+  variable_definitions: "variableDefinitions",
+  directives: "directives",
+  selection_set: "selectionSet",
+});
+
+json_struct!("VariableDefinition", VariableDefinition<S> {
+  variable: "variable",
+  type_: "type",
+  default_value: "defaultValue",
+  directives: "directives",
+});
+
+json_struct!("Variable", Variable<S> {
+  name: "name",
+});
+
+json_struct!("SelectionSet", SelectionSet<S> {
+  selections: "selections",
+});
+
+json_enum!(Selection<S> {
+  Field(Field<S>),
+  FragmentSpread(FragmentSpread<S>),
+  InlineFragment(InlineFragment<S>),
+});
+
+json_struct!("Field", Field<S> {
+  alias: "alias",
+  name: "name",
+  arguments: "arguments",
+  directives: "directives",
+  selection_set: "selectionSet",
+});
+
+json_struct!("Argument", Argument<S> {
+  name: "name",
+  value: "value",
+});
+
+json_struct!("FragmentSpread", FragmentSpread<S> {
+  name: "name",
+  #[cfg(feature = "fragment_variables")]
+  arguments: "arguments",
+  directives: "directives",
+});
+
+json_struct!("InlineFragment", InlineFragment<S> {
+  type_condition: "typeCondition",
+  directives: "directives",
+  selection_set: "selectionSet",
+});
+
+json_struct!("FragmentDefinition", FragmentDefinition<S> {
+  name: "name",
+  #[cfg(feature = "fragment_variables")]
+  variable_definitions: "variableDefinitions",
+  type_condition: "typeCondition",
+  directives: "directives",
+  selection_set: "selectionSet",
+});
+
+json_enum!(Value<S> {
+  Variable(Variable<S>),
+  Int(IntValue<S>),
+  Float(FloatValue<S>),
+  String(StringValue<S>),
+  Boolean(BooleanValue),
+  Null(NullValue),
+  Enum(EnumValue<S>),
+  List(ListValue<S>),
+  Object(ObjectValue<S>),
+});
+
+json_struct!("IntValue", IntValue<S> {
+  value: "value",
+});
+
+json_struct!("FloatValue", FloatValue<S> {
+  value: "value",
+});
+
+json_struct!("StringValue", StringValue<S> {
+  value: "value",
+  block: "block",
+});
+
+json_struct!("BooleanValue", BooleanValue {
+  value: "value",
+});
+
+json_struct!("NullValue", NullValue {});
+
+json_struct!("EnumValue", EnumValue<S> {
+  value: "value",
+});
+
+json_struct!("ListValue", ListValue<S> {
+  values: "values",
+});
+
+json_struct!("ObjectValue", ObjectValue<S> {
+  fields: "fields",
+});
+
+json_struct!("ObjectField", ObjectField<S> {
+  name: "name",
+  value: "value",
+});
+
+json_struct!("Directive", Directive<S> {
+  name: "name",
+  arguments: "arguments",
+});
+
+json_enum!(Type<S> {
+  Named(NamedType<S>),
+  List(ListType<S>),
+  NonNull(NonNullType<S>),
+});
+
+json_enum!(NullableType<S> {
+  Named(NamedType<S>),
+  List(ListType<S>),
+});
+
+json_struct!("NamedType", NamedType<S> {
+  name: "name",
+});
+
+json_struct!("ListType", ListType<S> {
+  type_: "type",
+});
+
+json_struct!("NonNullType", NonNullType<S> {
+  type_: "type",
+});
+
+// The type system AST extension is an experimental non-spec addition.
+#[cfg(feature = "type_system")]
+mod type_system {
+  use super::*;
+
+  json_enum!(TypeSystemDefinition<S> {
+    Schema(SchemaDefinition<S>),
+    Type(TypeDefinition<S>),
+    SchemaExtension(SchemaExtension<S>),
+    TypeExtension(TypeExtension<S>),
+    Directive(DirectiveDefinition<S>),
+  });
+
+  json_struct!("SchemaDefinition", SchemaDefinition<S> {
+    description: "description",
+    directives: "directives",
+    operation_types: "operationTypes",
+  });
+
+  json_struct!("OperationTypeDefinition", OperationTypeDefinition<S> {
+    operation: "operation",
+    typ: "type",
+  });
+
+  json_enum!(TypeDefinition<S> {
+    Scalar(ScalarTypeDefinition<S>),
+    Object(ObjectTypeDefinition<S>),
+    Interface(InterfaceTypeDefinition<S>),
+    Union(UnionTypeDefinition<S>),
+    Enum(EnumTypeDefinition<S>),
+    InputObject(InputObjectTypeDefinition<S>),
+  });
+
+  json_struct!("ScalarTypeDefinition", ScalarTypeDefinition<S> {
+    description: "description",
+    name: "name",
+    directives: "directives",
+  });
+
+  json_struct!("ObjectTypeDefinition", ObjectTypeDefinition<S> {
+    description: "description",
+    name: "name",
+    interfaces: "interfaces",
+    directives: "directives",
+    fields: "fields",
+  });
+
+  json_struct!("FieldDefinition", FieldDefinition<S> {
+    description: "description",
+    name: "name",
+    arguments: "arguments",
+    typ: "type",
+    directives: "directives",
+  });
+
+  json_struct!("InputValueDefinition", InputValueDefinition<S> {
+    description: "description",
+    name: "name",
+    typ: "type",
+    default_value: "defaultValue",
+    directives: "directives",
+  });
+
+  json_struct!("InterfaceTypeDefinition", InterfaceTypeDefinition<S> {
+    description: "description",
+    name: "name",
+    interfaces: "interfaces",
+    directives: "directives",
+    fields: "fields",
+  });
+
+  json_struct!("UnionTypeDefinition", UnionTypeDefinition<S> {
+    description: "description",
+    name: "name",
+    directives: "directives",
+    types: "types",
+  });
+
+  json_struct!("EnumTypeDefinition", EnumTypeDefinition<S> {
+    description: "description",
+    name: "name",
+    directives: "directives",
+    values: "values",
+  });
+
+  json_struct!("EnumValueDefinition", EnumValueDefinition<S> {
+    description: "description",
+    name: "name",
+    directives: "directives",
+  });
+
+  json_struct!("InputObjectTypeDefinition", InputObjectTypeDefinition<S> {
+    description: "description",
+    name: "name",
+    directives: "directives",
+    fields: "fields",
+  });
+
+  json_struct!("SchemaExtension", SchemaExtension<S> {
+    directives: "directives",
+    operation_types: "operationTypes",
+  });
+
+  json_enum!(TypeExtension<S> {
+    Scalar(ScalarTypeExtension<S>),
+    Object(ObjectTypeExtension<S>),
+    Interface(InterfaceTypeExtension<S>),
+    Union(UnionTypeExtension<S>),
+    Enum(EnumTypeExtension<S>),
+    InputObject(InputObjectTypeExtension<S>),
+  });
+
+  json_struct!("ScalarTypeExtension", ScalarTypeExtension<S> {
+    name: "name",
+    directives: "directives",
+  });
+
+  json_struct!("ObjectTypeExtension", ObjectTypeExtension<S> {
+    name: "name",
+    interfaces: "interfaces",
+    directives: "directives",
+    fields: "fields",
+  });
+
+  json_struct!("InterfaceTypeExtension", InterfaceTypeExtension<S> {
+    name: "name",
+    interfaces: "interfaces",
+    directives: "directives",
+    fields: "fields",
+  });
+
+  json_struct!("UnionTypeExtension", UnionTypeExtension<S> {
+    name: "name",
+    directives: "directives",
+    types: "types",
+  });
+
+  json_struct!("EnumTypeExtension", EnumTypeExtension<S> {
+    name: "name",
+    directives: "directives",
+    values: "values",
+  });
+
+  json_struct!("InputObjectTypeExtension", InputObjectTypeExtension<S> {
+    name: "name",
+    directives: "directives",
+    fields: "fields",
+  });
+
+  json_struct!("DirectiveDefinition", DirectiveDefinition<S> {
+    description: "description",
+    name: "name",
+    arguments: "arguments",
+    repeatable: "repeatable",
+    locations: "locations",
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json;
+  use super::super::{parse, parse_without_location, parse_borrowed};
+  use super::super::ast::*;
+
+  #[test]
+  fn test_serialize() {
+    let document = parse_without_location("query Q($a: [Int!] = [1]) { b: c(d: $a, e: {f: null}) ... on T { g } }".chars()).unwrap();
+    assert_eq!(serde_json::to_value(&document).unwrap(), json!({
+      "kind": "Document",
+      "definitions": [{
+        "kind": "OperationDefinition",
+        "operation": "query",
+        "name": {"kind": "Name", "value": "Q"},
+        "variableDefinitions": [{
+          "kind": "VariableDefinition",
+          "variable": {"kind": "Variable", "name": {"kind": "Name", "value": "a"}},
+          "type": {
+            "kind": "ListType",
+            "type": {"kind": "NonNullType", "type": {"kind": "NamedType", "name": {"kind": "Name", "value": "Int"}}},
+          },
+          "defaultValue": {"kind": "ListValue", "values": [{"kind": "IntValue", "value": "1"}]},
+          "directives": [],
+        }],
+        "directives": [],
+        "selectionSet": {
+          "kind": "SelectionSet",
+          "selections": [
+            {
+              "kind": "Field",
+              "alias": {"kind": "Name", "value": "b"},
+              "name": {"kind": "Name", "value": "c"},
+              "arguments": [
+                {
+                  "kind": "Argument",
+                  "name": {"kind": "Name", "value": "d"},
+                  "value": {"kind": "Variable", "name": {"kind": "Name", "value": "a"}},
+                },
+                {
+                  "kind": "Argument",
+                  "name": {"kind": "Name", "value": "e"},
+                  "value": {"kind": "ObjectValue", "fields": [{
+                    "kind": "ObjectField",
+                    "name": {"kind": "Name", "value": "f"},
+                    "value": {"kind": "NullValue"},
+                  }]},
+                },
+              ],
+              "directives": [],
+            },
+            {
+              "kind": "InlineFragment",
+              "typeCondition": {"kind": "NamedType", "name": {"kind": "Name", "value": "T"}},
+              "directives": [],
+              "selectionSet": {"kind": "SelectionSet", "selections": [{
+                "kind": "Field",
+                "name": {"kind": "Name", "value": "g"},
+                "arguments": [],
+                "directives": [],
+              }]},
+            },
+          ],
+        },
+      }],
+    }));
+  }
+
+  #[test]
+  fn test_location() {
+    let document = parse("{ a }".chars()).unwrap();
+    let json = serde_json::to_value(&document).unwrap();
+    assert_eq!(json["loc"], json!({
+      "start": 0,
+      "end": 5,
+      "startPosition": {"index": 0, "offset": 0, "line": 1, "column": 1},
+      "endPosition": {"index": 4, "offset": 4, "line": 1, "column": 5},
+    }));
+    assert_eq!(serde_json::from_value::<Document>(json).unwrap(), document);
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let source = concat!(
+      "query Q($a: Int = 1 @b) @c { d(e: [1.5, \"f\", \"\"\"g\"\"\", true, H, {i: $a}]) ...J }\n",
+      "mutation { k } subscription { l }\n",
+      "fragment J on M @n { o }\n",
+    );
+    let document = parse(source.chars()).unwrap();
+    let json = serde_json::to_string(&document).unwrap();
+    assert_eq!(serde_json::from_str::<Document>(&json).unwrap(), document);
+    let borrowed = parse_borrowed(source).unwrap();
+    assert_eq!(serde_json::to_string(&borrowed).unwrap(), json);
+    assert_eq!(serde_json::from_str::<Document<::std::borrow::Cow<str>>>(&json).unwrap().into_owned(), document);
+  }
+
+  #[test]
+  fn test_deserialize_graphql_js() {
+    // What graphql-js writes, including locations without positions and
+    // `null` where we would leave a field out.
+    let json = r#"{
+      "kind": "Document",
+      "definitions": [{
+        "kind": "OperationDefinition",
+        "operation": "query",
+        "name": null,
+        "variableDefinitions": [],
+        "directives": [],
+        "selectionSet": {
+          "kind": "SelectionSet",
+          "selections": [{
+            "kind": "Field",
+            "alias": null,
+            "name": {"kind": "Name", "value": "a", "loc": {"start": 2, "end": 3}},
+            "arguments": [{
+              "kind": "Argument",
+              "name": {"kind": "Name", "value": "b"},
+              "value": {"kind": "StringValue", "value": "c", "block": false}
+            }],
+            "directives": [],
+            "selectionSet": null,
+            "loc": {"start": 2, "end": 11}
+          }]
+        },
+        "loc": {"start": 0, "end": 13}
+      }],
+      "loc": {"start": 0, "end": 13}
+    }"#;
+    assert_eq!(serde_json::from_str::<Document>(json).unwrap(), parse_without_location("{ a(b: \"c\") }".chars()).unwrap());
+  }
+
+  #[test]
+  fn test_deserialize_errors() {
+    let error = serde_json::from_str::<Document>(r#"{"kind": "Name", "value": "a"}"#).unwrap_err();
+    assert_eq!(error.to_string(), "expected a `Document` node");
+    let error = serde_json::from_str::<Document>(r#"{"kind": "Document", "definitions": [{"kind": "Field"}]}"#).unwrap_err();
+    assert_eq!(error.to_string(), "`definitions`: unexpected kind `Field`");
+    let error = serde_json::from_str::<Name>(r#"{"kind": "Name"}"#).unwrap_err();
+    assert_eq!(error.to_string(), "`value`: expected a string");
+    let error = serde_json::from_str::<Name>(r#"{"kind": "Name", "value": "a", "loc": {"start": 0, "end": 18446744073709551616.0}}"#).unwrap_err();
+    assert_eq!(error.to_string(), "invalid value: floating point `1.8446744073709552e+19`, expected a non-negative integer at line 1 column 80");
+  }
+
+  #[cfg(feature = "type_system")]
+  #[test]
+  fn test_type_system() {
+    let source = concat!(
+      "\"a\" schema { query: Q } extend schema @b\n",
+      "type Q implements I @c { d(e: Int = 1): [String!] }\n",
+      "interface I { d: String } union U = Q | R enum E { F } input G { h: Int }\n",
+      "scalar S extend type Q { i: Int } extend enum E { J }\n",
+      "directive @k(l: Int) repeatable on FIELD | ENUM_VALUE\n",
+    );
+    let document = parse(source.chars()).unwrap();
+    let json = serde_json::to_value(&document).unwrap();
+    assert_eq!(json["definitions"][10]["locations"], json!([{"kind": "Name", "value": "FIELD"}, {"kind": "Name", "value": "ENUM_VALUE"}]));
+    assert_eq!(json["definitions"][0]["operationTypes"][0]["type"]["name"]["value"], json!("Q"));
+    assert_eq!(serde_json::from_value::<Document>(json).unwrap(), document);
+  }
+}
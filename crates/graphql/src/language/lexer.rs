@@ -1,25 +1,28 @@
+use std::borrow::Cow;
 use std::char;
-use std::num::{ParseIntError, ParseFloatError};
+use std::error;
+use std::fmt;
 use std::iter::Peekable;
-use std::str::FromStr;
 
 use super::Position;
 
 /// Represents a range of characters representing a lexical token within a
 /// Source.
+///
+/// Tokens are generic over the type of their text, just like AST nodes.
 #[derive(PartialEq, Debug)]
-pub struct Token {
+pub struct Token<S = String> {
   /// The kind of Token.
-  pub kind: TokenKind,
+  pub kind: TokenKind<S>,
   /// The character offset at which this Node begins.
   pub start: Position,
   /// The character offset at which this Node ends.
   pub end: Position,
 }
 
-impl Token {
+impl<S> Token<S> {
   /// Creates a new token of the provided kind.
-  pub fn new(kind: TokenKind, start: Position, end: Position) -> Self {
+  pub fn new(kind: TokenKind<S>, start: Position, end: Position) -> Self {
     Token {
       kind: kind,
       start: start,
@@ -28,9 +31,20 @@ impl Token {
   }
 }
 
+impl<S> Token<S> where S: Into<String> {
+  /// Converts this token into a token which owns its text.
+  pub fn into_owned(self) -> Token {
+    Token {
+      kind: self.kind.into_owned(),
+      start: self.start,
+      end: self.end,
+    }
+  }
+}
+
 /// The kind of Token.
-#[derive(PartialEq, Debug)]
-pub enum TokenKind {
+#[derive(Clone, PartialEq, Debug)]
+pub enum TokenKind<S = String> {
   /// `!`
   Bang,
   /// `$`
@@ -57,20 +71,112 @@ pub enum TokenKind {
   RightBrace,
   /// `|`
   Pipe,
+  /// `&`
+  Amp,
   /// An unquoted string.
-  Name(String),
-  /// An integer, or number with no fractional part.
-  Int(i32),
-  /// A float, or a number with a fractional part.
-  Float(f32),
+  Name(S),
+  /// An integer, or number with no fractional part. The value is the exact
+  /// source text of the number so that no precision is lost.
+  Int(S),
+  /// A float, or a number with a fractional part. The value is the exact
+  /// source text of the number so that no precision is lost.
+  Float(S),
   /// A quoted string.
-  String(String),
+  String(S),
+  /// A triple quoted block string. The value has already had its common
+  /// indentation and leading/trailing blank lines removed.
+  BlockString(S),
   /// A comment which has no semantic meaning.
-  Comment(String),
+  Comment(S),
+}
+
+impl<S> TokenKind<S> where S: Into<String> {
+  /// Converts this token kind into a token kind which owns its text.
+  pub fn into_owned(self) -> TokenKind {
+    match self {
+      TokenKind::Bang => TokenKind::Bang,
+      TokenKind::Dollar => TokenKind::Dollar,
+      TokenKind::LeftParen => TokenKind::LeftParen,
+      TokenKind::RightParen => TokenKind::RightParen,
+      TokenKind::Ellipsis => TokenKind::Ellipsis,
+      TokenKind::Colon => TokenKind::Colon,
+      TokenKind::Equals => TokenKind::Equals,
+      TokenKind::At => TokenKind::At,
+      TokenKind::LeftBracket => TokenKind::LeftBracket,
+      TokenKind::RightBracket => TokenKind::RightBracket,
+      TokenKind::LeftBrace => TokenKind::LeftBrace,
+      TokenKind::RightBrace => TokenKind::RightBrace,
+      TokenKind::Pipe => TokenKind::Pipe,
+      TokenKind::Amp => TokenKind::Amp,
+      TokenKind::Name(name) => TokenKind::Name(name.into()),
+      TokenKind::Int(value) => TokenKind::Int(value.into()),
+      TokenKind::Float(value) => TokenKind::Float(value.into()),
+      TokenKind::String(value) => TokenKind::String(value.into()),
+      TokenKind::BlockString(value) => TokenKind::BlockString(value.into()),
+      TokenKind::Comment(comment) => TokenKind::Comment(comment.into()),
+    }
+  }
+}
+
+impl<S> TokenKind<S> where S: AsRef<str> {
+  /// Checks if this token kind is equal to a token kind which owns its text.
+  pub fn matches(&self, other: &TokenKind) -> bool {
+    match (self, other) {
+      (&TokenKind::Bang, &TokenKind::Bang) => true,
+      (&TokenKind::Dollar, &TokenKind::Dollar) => true,
+      (&TokenKind::LeftParen, &TokenKind::LeftParen) => true,
+      (&TokenKind::RightParen, &TokenKind::RightParen) => true,
+      (&TokenKind::Ellipsis, &TokenKind::Ellipsis) => true,
+      (&TokenKind::Colon, &TokenKind::Colon) => true,
+      (&TokenKind::Equals, &TokenKind::Equals) => true,
+      (&TokenKind::At, &TokenKind::At) => true,
+      (&TokenKind::LeftBracket, &TokenKind::LeftBracket) => true,
+      (&TokenKind::RightBracket, &TokenKind::RightBracket) => true,
+      (&TokenKind::LeftBrace, &TokenKind::LeftBrace) => true,
+      (&TokenKind::RightBrace, &TokenKind::RightBrace) => true,
+      (&TokenKind::Pipe, &TokenKind::Pipe) => true,
+      (&TokenKind::Amp, &TokenKind::Amp) => true,
+      (&TokenKind::Name(ref a), &TokenKind::Name(ref b)) => a.as_ref() == b,
+      (&TokenKind::Int(ref a), &TokenKind::Int(ref b)) => a.as_ref() == b,
+      (&TokenKind::Float(ref a), &TokenKind::Float(ref b)) => a.as_ref() == b,
+      (&TokenKind::String(ref a), &TokenKind::String(ref b)) => a.as_ref() == b,
+      (&TokenKind::BlockString(ref a), &TokenKind::BlockString(ref b)) => a.as_ref() == b,
+      (&TokenKind::Comment(ref a), &TokenKind::Comment(ref b)) => a.as_ref() == b,
+      _ => false,
+    }
+  }
+}
+
+impl<S> fmt::Display for TokenKind<S> where S: AsRef<str> {
+  /// Describes the token for use in error messages.
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      TokenKind::Bang => write!(f, "`!`"),
+      TokenKind::Dollar => write!(f, "`$`"),
+      TokenKind::LeftParen => write!(f, "`(`"),
+      TokenKind::RightParen => write!(f, "`)`"),
+      TokenKind::Ellipsis => write!(f, "`...`"),
+      TokenKind::Colon => write!(f, "`:`"),
+      TokenKind::Equals => write!(f, "`=`"),
+      TokenKind::At => write!(f, "`@`"),
+      TokenKind::LeftBracket => write!(f, "`[`"),
+      TokenKind::RightBracket => write!(f, "`]`"),
+      TokenKind::LeftBrace => write!(f, "`{{`"),
+      TokenKind::RightBrace => write!(f, "`}}`"),
+      TokenKind::Pipe => write!(f, "`|`"),
+      TokenKind::Amp => write!(f, "`&`"),
+      TokenKind::Name(ref name) => write!(f, "name `{}`", name.as_ref()),
+      TokenKind::Int(ref value) => write!(f, "integer `{}`", value.as_ref()),
+      TokenKind::Float(ref value) => write!(f, "float `{}`", value.as_ref()),
+      TokenKind::String(ref value) => write!(f, "string {:?}", value.as_ref()),
+      TokenKind::BlockString(_) => write!(f, "block string"),
+      TokenKind::Comment(_) => write!(f, "comment"),
+    }
+  }
 }
 
 /// An error that may be generated by the Lexer iterator.
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum Error {
   /// The source ended unexpectedly.
   UnexpectedEnding(Position),
@@ -82,12 +188,6 @@ pub enum Error {
   ExpectedNonZeroDigit(Position),
   /// We expected a digit character and got something else.
   ExpectedDigit(Position, char),
-  /// We had an integer parsing error. We expose the internal error object in
-  /// addition to the position.
-  InvalidInteger(Position, ParseIntError),
-  /// We had a float parsing error. We expose the internal error object in
-  /// addition to the position.
-  InvalidFloat(Position, ParseFloatError),
   /// We have a string that was not terminated.
   UnterminatedString(Position),
   /// There was an invalid escape character.
@@ -99,21 +199,114 @@ pub enum Error {
   Unreachable,
 }
 
+impl Error {
+  /// A stable code which identifies the kind of this error. Codes never change
+  /// meaning between versions so they may be used to look up documentation or
+  /// to filter errors.
+  pub fn code(&self) -> &'static str {
+    match *self {
+      Error::UnexpectedEnding(_) => "E0100",
+      Error::UnknownChar(_, _) => "E0101",
+      Error::ExpectedEllipsisChar(_, _) => "E0102",
+      Error::ExpectedNonZeroDigit(_) => "E0103",
+      Error::ExpectedDigit(_, _) => "E0104",
+      Error::UnterminatedString(_) => "E0105",
+      Error::InvalidEscapeChar(_, _) => "E0106",
+      Error::InvalidUnicodeSequenceChar(_, _) => "E0107",
+      Error::Unreachable => "E0999",
+    }
+  }
+
+  /// The position in the source document at which this error occurred.
+  pub fn position(&self) -> Option<Position> {
+    match *self {
+      Error::UnexpectedEnding(position) |
+      Error::UnknownChar(position, _) |
+      Error::ExpectedEllipsisChar(position, _) |
+      Error::ExpectedNonZeroDigit(position) |
+      Error::ExpectedDigit(position, _) |
+      Error::UnterminatedString(position) |
+      Error::InvalidEscapeChar(position, _) |
+      Error::InvalidUnicodeSequenceChar(position, _) => Some(position),
+      Error::Unreachable => None,
+    }
+  }
+
+  /// A human readable message describing this error without its position.
+  pub fn message(&self) -> String {
+    match *self {
+      Error::UnexpectedEnding(_) => String::from("Unexpected end of input."),
+      Error::UnknownChar(_, c) => format!("Unexpected character {:?}.", c),
+      Error::ExpectedEllipsisChar(_, c) => format!("Expected `...` but found {:?}.", c),
+      Error::ExpectedNonZeroDigit(_) => String::from("Numbers may not have a leading zero."),
+      Error::ExpectedDigit(_, c) => format!("Expected a digit but found {:?}.", c),
+      Error::UnterminatedString(_) => String::from("Unterminated string."),
+      Error::InvalidEscapeChar(_, c) => format!("Invalid escape sequence `\\{}` in string.", c),
+      Error::InvalidUnicodeSequenceChar(_, c) => format!("Invalid character {:?} in unicode escape sequence.", c),
+      Error::Unreachable => String::from("An unreachable error occurred. This is a bug."),
+    }
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.position() {
+      Some(position) => write!(f, "{} ({}:{})", self.message(), position.line, position.column),
+      None => write!(f, "{}", self.message()),
+    }
+  }
+}
+
+impl error::Error for Error {}
+
 /// A lexer turns a string of source text into an iterator of tokens. These
 /// tokens will then be fed into the GraphQL parser.
-pub struct Lexer<I: Iterator<Item=char>> {
+pub struct Lexer<I: Input> {
   chars: Chars<I>,
   done: bool,
-  peeked: Option<Result<Token, Error>>,
+  peeked: Option<Result<Token<I::Text>, Error>>,
+  /// If we are recovering from errors, the errors we have skipped over so
+  /// far. Otherwise `None` and the first error ends the iterator.
+  errors: Option<Vec<Error>>,
 }
 
-impl<I> Lexer<I> where I: Iterator<Item=char> {
+impl<I> Lexer<IterInput<I>> where I: Iterator<Item=char> {
   /// Creates a new Lexer that is ready to lex an iterator of characters.
   pub fn new<J>(iter: J) -> Self where J: IntoIterator<Item=char, IntoIter=I> {
+    Lexer::from_input(IterInput::new(iter.into_iter()), false)
+  }
+
+  /// Creates a new Lexer which never emits an error. Instead errors are
+  /// collected, the offending characters are skipped, and lexing continues
+  /// with the next token. Use `take_errors` to get the collected errors.
+  pub fn recovering<J>(iter: J) -> Self where J: IntoIterator<Item=char, IntoIter=I> {
+    Lexer::from_input(IterInput::new(iter.into_iter()), true)
+  }
+}
+
+impl<'a> Lexer<StrInput<'a>> {
+  /// Creates a new Lexer that is ready to lex a string slice. The text of the
+  /// tokens will be borrowed from the slice whenever possible.
+  pub fn borrowed(source: &'a str) -> Self {
+    Lexer::from_input(StrInput::new(source), false)
+  }
+}
+
+impl<I> Lexer<I> where I: Input {
+  fn from_input(input: I, recovering: bool) -> Self {
     Lexer {
-      chars: Chars::new(iter.into_iter()),
+      chars: Chars::new(input),
       done: false,
       peeked: None,
+      errors: if recovering { Some(Vec::new()) } else { None },
+    }
+  }
+
+  /// Takes all of the errors that have been recovered from so far.
+  pub fn take_errors(&mut self) -> Vec<Error> {
+    match self.errors {
+      Some(ref mut errors) => errors.drain(..).collect(),
+      None => Vec::new(),
     }
   }
 
@@ -122,8 +315,28 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
     self.chars.pos
   }
 
+  /// Stops reading the source at `max_length` bytes. If the source is longer
+  /// the lexer sees its end there, and `length_exceeded` is the position of
+  /// the first character past the maximum.
+  pub fn limit_length(&mut self, max_length: Option<usize>) {
+    self.chars.max_length = max_length;
+  }
+
+  /// The position of the first character past the maximum length, if the
+  /// source is longer than the maximum and the lexer got there.
+  pub fn length_exceeded(&self) -> Option<Position> {
+    self.chars.length_exceeded
+  }
+
+  /// Continues lexing after an error was returned. Every error consumes at
+  /// least one character, so lexing continues after the offending
+  /// characters.
+  pub fn resume(&mut self) {
+    self.done = false;
+  }
+
   /// Returns a reference to the next token without advancing the iterator.
-  pub fn peek(&mut self) -> Option<Result<&Token, &Error>> {
+  pub fn peek(&mut self) -> Option<Result<&Token<I::Text>, &Error>> {
     if self.peeked.is_none() {
       self.peeked = self.next();
     }
@@ -131,50 +344,60 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
   }
 }
 
-impl<I> Iterator for Lexer<I> where I: Iterator<Item=char> {
-  type Item = Result<Token, Error>;
+impl<I> Iterator for Lexer<I> where I: Input {
+  type Item = Result<Token<I::Text>, Error>;
 
-  fn next(&mut self) -> Option<Result<Token, Error>> {
+  fn next(&mut self) -> Option<Result<Token<I::Text>, Error>> {
+    // If we have peeked, we should return the peeked value. This comes before
+    // checking if we are done so that a peeked error is not lost.
+    if self.peeked.is_some() {
+      return self.peeked.take();
+    }
     // If we are done, only emit `None` from now on.
     if self.done {
       return None;
     }
-    // If we have peeked, we should return the peeked value.
-    if let Some(_) = self.peeked {
-      return self.peeked.take();
-    }
-    match self.next_token() {
-      // If we got a token, return that token.
-      Ok(Some(token)) => {
-        Some(Ok(token))
-      },
-      // If we did not get a token, we are done without error.
-      Ok(None) => {
-        self.done = true;
-        None
-      },
-      // If we got an error, return the error and mark ourselves as done.
-      Err(error) => {
-        self.done = true;
-        Some(Err(error))
-      },
+    loop {
+      match self.next_token() {
+        // If we got a token, return that token.
+        Ok(Some(token)) => {
+          return Some(Ok(token));
+        },
+        // If we did not get a token, we are done without error.
+        Ok(None) => {
+          self.done = true;
+          return None;
+        },
+        // If we are recovering, remember the error and try again. Every error
+        // consumes at least one character so this loop will always end.
+        Err(error) => if let Some(ref mut errors) = self.errors {
+          errors.push(error);
+        } else {
+          // Otherwise return the error and mark ourselves as done.
+          self.done = true;
+          return Some(Err(error));
+        },
+      }
     }
   }
 }
 
-impl<I> Lexer<I> where I: Iterator<Item=char> {
+impl<I> Lexer<I> where I: Input {
   /// Used by the iterator `next` method to get the next token. If we get an
   /// `Err`, the iterator finishes with an error. If we get `Ok(None)` the
   /// iterator has completed succesfully.
-  fn next_token(&mut self) -> Result<Option<Token>, Error> {
-    // Loop of ignoring stuff.
+  fn next_token(&mut self) -> Result<Option<Token<I::Text>>, Error> {
+    // Loop of ignoring stuff. The spec ignores the unicode byte order mark,
+    // whitespace, line terminators and commas anywhere between tokens.
     loop {
       match self.chars.peek() {
         // If we have an ignored token, consume it and continue.
-        Some(&'\t') |
-        Some(&' ') |
-        Some(&',') |
-        Some(&'\n') => { self.chars.next(); },
+        Some('\u{FEFF}') |
+        Some('\t') |
+        Some(' ') |
+        Some(',') |
+        Some('\n') |
+        Some('\r') => { self.chars.next(); },
 
         // If this is not an ignored token, break out of our loop.
         _ => break,
@@ -196,19 +419,18 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
       Some('{') => Ok(Some(Token::new(TokenKind::LeftBrace, self.chars.pos, self.chars.pos))),
       Some('}') => Ok(Some(Token::new(TokenKind::RightBrace, self.chars.pos, self.chars.pos))),
       Some('|') => Ok(Some(Token::new(TokenKind::Pipe, self.chars.pos, self.chars.pos))),
+      Some('&') => Ok(Some(Token::new(TokenKind::Amp, self.chars.pos, self.chars.pos))),
 
       // If we match a hashtag, we try to match a comment.
       Some('#') => {
         let start = self.chars.pos;
-        let mut comment = String::new();
+        self.chars.start_text(false);
+        // The comment is everything up until the next line terminator. The
+        // line terminator is left to be ignored like any other.
         loop {
-          let prev = self.chars.pos;
-          // If we have a newline, return the comment string. Otherwise, add
-          // the character to the comment.
-          match self.chars.next() {
-            Some('\n') => return Ok(Some(Token::new(TokenKind::Comment(comment), start, prev))),
-            Some(c) => comment.push(c),
-            None => return Ok(Some(Token::new(TokenKind::Comment(comment), start, self.chars.pos))),
+          match self.chars.peek() {
+            Some('\n') | Some('\r') | None => return Ok(Some(Token::new(TokenKind::Comment(self.chars.text()), start, self.chars.pos))),
+            Some(_) => { self.chars.next(); },
           }
         }
       },
@@ -235,12 +457,11 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
       // If we have a letter or underscore we need to construct a name.
       // Numbers are not included because if we detect a number we need to
       // parse an integer or float.
-      Some(c1 @ 'a' ... 'z') |
-      Some(c1 @ 'A' ... 'Z') |
-      Some(c1 @ '_') => {
+      Some('a' ... 'z') |
+      Some('A' ... 'Z') |
+      Some('_') => {
         let start = self.chars.pos;
-        let mut name = String::new();
-        name.push(c1);
+        self.chars.start_text(true);
         loop {
           // Peek at the next letter. If it is a letter, number, or underscore
           // consume the next value and add it to our name. Otherwise return a
@@ -249,13 +470,11 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
           // We peek because if our letter does not match, we want it to be
           // available next time we call `next` on `self.chars`.
           match self.chars.peek() {
-            Some(&'a' ... 'z') |
-            Some(&'A' ... 'Z') |
-            Some(&'_') |
-            Some(&'0' ... '9') => {
-              name.push(try!(self.chars.next().ok_or(Error::Unreachable)));
-            },
-            _ => return Ok(Some(Token::new(TokenKind::Name(name), start, self.chars.pos))),
+            Some('a' ... 'z') |
+            Some('A' ... 'Z') |
+            Some('_') |
+            Some('0' ... '9') => { self.chars.next(); },
+            _ => return Ok(Some(Token::new(TokenKind::Name(self.chars.text()), start, self.chars.pos))),
           }
         }
       },
@@ -263,6 +482,9 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
       Some(c1 @ '0' ... '9') |
       Some(c1 @ '-') => {
         let start = self.chars.pos;
+        // The exact text of the number is recorded from the input. These
+        // strings are only used to validate the number’s syntax.
+        self.chars.start_text(true);
         let mut integer_string = String::new();
         let mut fraction_string: Option<String> = None;
         let mut exponent_string: Option<String> = None;
@@ -273,11 +495,11 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
           match self.chars.peek() {
             // If we got a number, we should add the number to the appropriate
             // string.
-            Some(&'0' ... '9') => {
+            Some('0' ... '9') => {
               // If the number is 0, and we got another number we want to throw
               // an error because GraphQL does not allow numbers like `012` or
-              // `000`.
-              if integer_string == "0" || integer_string == "-0" {
+              // `000`. Zeros are fine in the fraction and exponent, as in `0.01`.
+              if fraction_string.is_none() && exponent_string.is_none() && (integer_string == "0" || integer_string == "-0") {
                 return Err(Error::ExpectedNonZeroDigit(self.chars.pos));
               }
 
@@ -293,7 +515,7 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
             },
 
             // If we got a dot, we need to switch into fraction mode.
-            Some(&'.') => {
+            Some('.') => {
               // Consume the dot.
               let c = try!(self.chars.next().ok_or(Error::Unreachable));
 
@@ -307,8 +529,8 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
             },
 
             // If we got an `e`, we need to switch into exponent mode.
-            Some(&'e') |
-            Some(&'E') => {
+            Some('e') |
+            Some('E') => {
               // Consume the dot.
               let c = try!(self.chars.next().ok_or(Error::Unreachable));
 
@@ -319,11 +541,11 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
 
               // Positive or negative sign characters need special handling.
               match self.chars.peek() {
-                Some(&'+') => {
+                Some('+') => {
                   self.chars.next();
                   exponent_string = Some(String::new());
                 },
-                Some(&'-') => {
+                Some('-') => {
                   self.chars.next();
                   exponent_string = Some({
                     let mut s = String::new();
@@ -338,8 +560,8 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
             // If we got a letter, we need to return an error. Why? Well because
             // `e` has a special behavior. If we can terminate a number with a
             // letter, this leads to an odd case for `e`s.
-            Some(&'a' ... 'z') |
-            Some(&'A' ... 'Z') => {
+            Some('a' ... 'z') |
+            Some('A' ... 'Z') => {
               // Consume the letter.
               let c = try!(self.chars.next().ok_or(Error::Unreachable));
 
@@ -358,17 +580,11 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
               }
 
               // Compute the token kind, whether it is an integer or a float.
-              let kind = match (fraction_string.as_ref(), exponent_string.as_ref()) {
-                // Parse as an integer if we have no fraction or exponent.
-                (None, None) => {
-                  TokenKind::Int(try!(i32::from_str(&integer_string).map_err(|error| Error::InvalidInteger(start, error))))
-                },
-
-                // Parse as a float if we have a fraction or exponent.
-                (fraction_string, exponent_string) => {
-                  let float_string = String::new() + &integer_string + "." + fraction_string.map(String::as_str).unwrap_or("0") + "e" + exponent_string.map(String::as_str).unwrap_or("0");
-                  TokenKind::Float(try!(f32::from_str(&float_string).map_err(|error| Error::InvalidFloat(start, error))))
-                },
+              let kind = match (fraction_string, exponent_string) {
+                // An integer if we have no fraction or exponent.
+                (None, None) => TokenKind::Int(self.chars.text()),
+                // A float if we have a fraction or exponent.
+                _ => TokenKind::Float(self.chars.text()),
               };
 
               return Ok(Some(Token::new(kind, start, self.chars.pos)));
@@ -382,17 +598,45 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
       // special logic for escaping strings.
       Some('"') => {
         let start = self.chars.pos;
-        let mut string = String::new();
+
+        // Two double quotes in a row is either an empty string, or if there
+        // is a third double quote, the start of a block string.
+        if self.chars.peek() == Some('"') {
+          self.chars.next();
+          if self.chars.peek() == Some('"') {
+            self.chars.next();
+            return self.next_block_string(start);
+          }
+          return Ok(Some(Token::new(TokenKind::String(From::from(String::new())), start, self.chars.pos)));
+        }
+
+        // Until we see an escape sequence the value of the string is exactly
+        // its source text, which the input may be able to borrow. After that
+        // we build an owned value.
+        self.chars.start_text(false);
+        let mut escaped: Option<String> = None;
         loop {
           let last_pos = self.chars.pos;
-          match self.chars.next() {
+          match self.chars.peek() {
             // End the string if we see another double quote.
-            Some('"') => return Ok(Some(Token::new(TokenKind::String(string), start, self.chars.pos))),
-            // Error if there is a newline in the string.
-            Some('\n') => return Err(Error::UnterminatedString(last_pos)),
+            Some('"') => {
+              let value = match escaped {
+                Some(string) => From::from(string),
+                None => self.chars.text(),
+              };
+              self.chars.next();
+              return Ok(Some(Token::new(TokenKind::String(value), start, self.chars.pos)));
+            },
+            Some('\\') if escaped.is_none() => escaped = Some(self.chars.text().into()),
+            _ => (),
+          }
+          match self.chars.next() {
+            // Error if there is a line terminator in the string.
+            Some('\n') | Some('\r') => return Err(Error::UnterminatedString(last_pos)),
             // If the user is trying to escape something. Do some special
             // logic.
             Some('\\') => {
+              let string = try!(escaped.as_mut().ok_or(Error::Unreachable));
               match self.chars.next() {
                 // For the following escaped characters, insert the character
                 // the escape sequence represents into the string.
@@ -431,8 +675,11 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
                 None => return Err(Error::UnexpectedEnding(self.chars.pos)),
               }
             },
-            // Otherwise add the character to the string.
-            Some(c) => string.push(c),
+            // Otherwise add the character to the string if we are building an
+            // owned value.
+            Some(c) => if let Some(ref mut string) = escaped {
+              string.push(c);
+            },
             // Error if we got nothing. The string must be closed!
             None => return Err(Error::UnexpectedEnding(self.chars.pos)),
           }
@@ -445,64 +692,284 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
       None => Ok(None),
     }
   }
+
+  /// Lexes the rest of a block string after the opening `"""` has been
+  /// consumed. Block strings may span many lines and the only escape sequence
+  /// they support is `\"""`.
+  fn next_block_string(&mut self, start: Position) -> Result<Option<Token<I::Text>>, Error> {
+    let mut raw = String::new();
+    loop {
+      match self.chars.next() {
+        // If we see a double quote, check if it is the first of the three
+        // double quotes that end the block string.
+        Some('"') => {
+          if self.chars.peek() == Some('"') {
+            self.chars.next();
+            if self.chars.peek() == Some('"') {
+              self.chars.next();
+              let value = block_string_value(&raw);
+              return Ok(Some(Token::new(TokenKind::BlockString(From::from(value)), start, self.chars.pos)));
+            }
+            raw.push_str("\"\"");
+          } else {
+            raw.push('"');
+          }
+        },
+        // A backslash followed by three double quotes is an escaped triple
+        // quote. Any other backslash is just a backslash.
+        Some('\\') => {
+          let mut quotes = 0;
+          while quotes < 3 && self.chars.peek() == Some('"') {
+            self.chars.next();
+            quotes += 1;
+          }
+          if quotes == 3 {
+            raw.push_str("\"\"\"");
+          } else {
+            raw.push('\\');
+            for _ in 0..quotes {
+              raw.push('"');
+            }
+          }
+        },
+        // Otherwise add the character, including newlines, to the string.
+        Some(c) => raw.push(c),
+        // Error if we got nothing. The block string must be closed!
+        None => return Err(Error::UnexpectedEnding(self.chars.pos)),
+      }
+    }
+  }
+}
+
+/// Produces the value of a block string from its raw source text by removing
+/// the common indentation of every line but the first, and then removing any
+/// leading or trailing blank lines.
+///
+/// This is the `BlockStringValue()` algorithm from the GraphQL specification.
+pub fn block_string_value(raw: &str) -> String {
+  let raw = raw.replace("\r\n", "\n").replace('\r', "\n");
+  let mut lines: Vec<&str> = raw.split('\n').collect();
+
+  // Find the smallest indentation of all the lines after the first which are
+  // not entirely whitespace.
+  let common_indent = lines.iter().skip(1).filter_map(|line| {
+    let indent = line.chars().take_while(|&c| c == ' ' || c == '\t').count();
+    if indent < line.len() { Some(indent) } else { None }
+  }).min();
+
+  // Remove the common indentation from every line after the first.
+  if let Some(common_indent) = common_indent {
+    for line in lines.iter_mut().skip(1) {
+      *line = if line.len() < common_indent { "" } else { &line[common_indent..] };
+    }
+  }
+
+  // Remove leading and trailing lines which are only whitespace.
+  let is_blank = |line: &&str| line.chars().all(|c| c == ' ' || c == '\t');
+  while lines.first().map_or(false, &is_blank) {
+    lines.remove(0);
+  }
+  while lines.last().map_or(false, &is_blank) {
+    lines.pop();
+  }
+
+  lines.join("\n")
 }
 
-/// An iterator struct which adds some extra convenience features on top of a
-/// `char` iterator for our lexer. Including a `peek()` method.
-struct Chars<I: Iterator<Item=char>> {
-  /// The internal peekable iterable.
+/// Where the lexer reads its characters from. The input also decides the type
+/// of the text in tokens, and records that text while a token is lexed.
+pub trait Input {
+  /// The type of the text in tokens lexed from this input.
+  type Text: AsRef<str> + From<String> + Into<String>;
+
+  /// Consumes the next character.
+  fn next(&mut self) -> Option<char>;
+
+  /// Returns the next character without consuming it.
+  fn peek(&mut self) -> Option<char>;
+
+  /// Starts recording the text of a token. If `last` is true the character
+  /// which was just consumed is the first character of the text.
+  fn start_text(&mut self, last: bool);
+
+  /// Returns the text consumed since `start_text` was called.
+  fn text(&mut self) -> Self::Text;
+}
+
+/// Reads characters from any iterator. The text of tokens is collected into
+/// owned strings.
+pub struct IterInput<I: Iterator<Item=char>> {
   iter: Peekable<I>,
+  /// The character which was consumed last.
+  last: Option<char>,
+  /// The text recorded so far, if we are recording.
+  text: Option<String>,
+}
+
+impl<I> IterInput<I> where I: Iterator<Item=char> {
+  fn new(iter: I) -> Self {
+    IterInput {
+      iter: iter.peekable(),
+      last: None,
+      text: None,
+    }
+  }
+}
+
+impl<I> Input for IterInput<I> where I: Iterator<Item=char> {
+  type Text = String;
+
+  fn next(&mut self) -> Option<char> {
+    let next = self.iter.next();
+    if let (Some(c), Some(ref mut text)) = (next, self.text.as_mut()) {
+      text.push(c);
+    }
+    self.last = next;
+    next
+  }
+
+  fn peek(&mut self) -> Option<char> {
+    self.iter.peek().cloned()
+  }
+
+  fn start_text(&mut self, last: bool) {
+    let mut text = String::new();
+    if let (true, Some(c)) = (last, self.last) {
+      text.push(c);
+    }
+    self.text = Some(text);
+  }
+
+  fn text(&mut self) -> String {
+    self.text.take().unwrap_or_default()
+  }
+}
+
+/// Reads characters from a string slice. The text of tokens is borrowed from
+/// the slice.
+pub struct StrInput<'a> {
+  source: &'a str,
+  /// The byte offset of the next character.
+  offset: usize,
+  /// The byte offset of the character which was consumed last.
+  last: usize,
+  /// The byte offset at which the text being recorded starts.
+  start: usize,
+}
+
+impl<'a> StrInput<'a> {
+  fn new(source: &'a str) -> Self {
+    StrInput {
+      source: source,
+      offset: 0,
+      last: 0,
+      start: 0,
+    }
+  }
+}
+
+impl<'a> Input for StrInput<'a> {
+  type Text = Cow<'a, str>;
+
+  fn next(&mut self) -> Option<char> {
+    let next = self.peek();
+    if let Some(c) = next {
+      self.last = self.offset;
+      self.offset += c.len_utf8();
+    }
+    next
+  }
+
+  fn peek(&mut self) -> Option<char> {
+    self.source[self.offset..].chars().next()
+  }
+
+  fn start_text(&mut self, last: bool) {
+    self.start = if last { self.last } else { self.offset };
+  }
+
+  fn text(&mut self) -> Cow<'a, str> {
+    Cow::Borrowed(&self.source[self.start..self.offset])
+  }
+}
+
+/// A struct which adds position tracking on top of an `Input` for our lexer.
+struct Chars<I: Input> {
+  /// The input we read characters from.
+  input: I,
   /// The current item which consumers can look at.
   current: Option<char>,
   /// The position of the iterator. Every time `next()` is called, this field is
   /// updated.
   pos: Position,
+  /// The maximum byte offset of a character, if any. Characters past it are
+  /// never read from the input.
+  max_length: Option<usize>,
+  /// The position of the first character past the maximum length, once we
+  /// have seen it.
+  length_exceeded: Option<Position>,
 }
 
-impl<I> Chars<I> where I: Iterator<Item=char> {
+impl<I> Chars<I> where I: Input {
   /// Creates a new `Chars` iterator.
-  fn new(iter: I) -> Self {
+  fn new(input: I) -> Self {
     Chars {
-      iter: iter.peekable(),
+      input: input,
       current: None,
       pos: Position {
         index: 0,
+        offset: 0,
         line: 1,
         column: 1,
       },
+      max_length: None,
+      length_exceeded: None,
+    }
+  }
+
+  /// Peek at the next item in our iterator without consuming that item. If
+  /// the item is past the maximum length then we are at the end.
+  fn peek(&mut self) -> Option<char> {
+    if self.length_exceeded.is_some() {
+      return None;
     }
+    let next = self.input.peek();
+    if let (Some(max_length), Some(c)) = (self.max_length, next) {
+      let mut pos = self.pos;
+      if let Some(current) = self.current {
+        advance(&mut pos, current, c);
+      }
+      if pos.offset >= max_length {
+        self.length_exceeded = Some(pos);
+        return None;
+      }
+    }
+    next
   }
 
-  /// Peek at the next item in our iterator without consuming that item.
-  fn peek(&mut self) -> Option<&char> {
-    self.iter.peek()
+  /// Starts recording the text of a token. See `Input::start_text`.
+  fn start_text(&mut self, last: bool) {
+    self.input.start_text(last)
+  }
+
+  /// Returns the recorded text of a token. See `Input::text`.
+  fn text(&mut self) -> I::Text {
+    self.input.text()
   }
 }
 
-impl<I> Iterator for Chars<I> where I: Iterator<Item=char> {
+impl<I> Iterator for Chars<I> where I: Input {
   type Item = char;
 
   fn next(&mut self) -> Option<char> {
-    // Get the next item from our internal iterator.
-    let next = self.iter.next();
-
+    // Get the next item from our input, unless it is past the maximum length.
+    let next = if self.peek().is_some() { self.input.next() } else { None };
     // If this is not the first item, and there is an item, increment the
-    // position. The reason we have the `self.current != None` check is we want
-    // our position to be 0 after the first `next()` call, but because the
-    // position is of type `usize` we can’t start it at -1.
-    if self.current != None {
-      if let Some(c) = next {
-        self.pos.index += 1;
-
-        // If the character is a newline, increment the line number and reset the
-        // column number. Otherwise just increment the column number.
-        if c == '\n' {
-          self.pos.line += 1;
-          self.pos.column = 0;
-        } else {
-          self.pos.column += 1;
-        }
-      }
+    // position. The reason we check for a current item is we want our
+    // position to be 0 after the first `next()` call, but because the position
+    // is of type `usize` we can’t start it at -1.
+    if let (Some(current), Some(c)) = (self.current, next) {
+      advance(&mut self.pos, current, c);
     }
 
     // Set the current item.
@@ -512,9 +979,30 @@ impl<I> Iterator for Chars<I> where I: Iterator<Item=char> {
   }
 }
 
+/// Moves a position from the `current` character to the `next` character.
+/// The byte offset moves past the current character, which may be more than
+/// one byte long.
+pub fn advance(pos: &mut Position, current: char, next: char) {
+  pos.index += 1;
+  pos.offset += current.len_utf8();
+
+  // If the character is a line terminator, increment the line number and
+  // reset the column number. Otherwise just increment the column number. A
+  // `\r\n` pair is a single line terminator so the `\n` stays on the line
+  // the `\r` started.
+  match (current, next) {
+    ('\r', '\n') => (),
+    (_, '\n') | (_, '\r') => {
+      pos.line += 1;
+      pos.column = 0;
+    },
+    _ => pos.column += 1,
+  }
+}
+
 #[cfg(test)]
 mod tests {
-  use std::str::FromStr;
+  use std::borrow::Cow;
   use super::super::Position;
   use super::{Lexer, Token, TokenKind, Error};
 
@@ -524,6 +1012,7 @@ mod tests {
   fn pos1(index: usize) -> Position {
     Position {
       index: index,
+      offset: index,
       line: 1,
       column: index + 1,
     }
@@ -543,11 +1032,12 @@ mod tests {
     assert_eq!(Lexer::new("{".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![Token { kind: TokenKind::LeftBrace, start: pos1(0), end: pos1(0) }]);
     assert_eq!(Lexer::new("}".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![Token { kind: TokenKind::RightBrace, start: pos1(0), end: pos1(0) }]);
     assert_eq!(Lexer::new("|".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![Token { kind: TokenKind::Pipe, start: pos1(0), end: pos1(0) }]);
+    assert_eq!(Lexer::new("&".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![Token { kind: TokenKind::Amp, start: pos1(0), end: pos1(0) }]);
   }
 
   #[test]
   fn test_punctuator_many() {
-    assert_eq!(Lexer::new("!$():=@[]{}|".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+    assert_eq!(Lexer::new("!$():=@[]{}|&".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
       Token { kind: TokenKind::Bang, start: pos1(0), end: pos1(0) },
       Token { kind: TokenKind::Dollar, start: pos1(1), end: pos1(1) },
       Token { kind: TokenKind::LeftParen, start: pos1(2), end: pos1(2) },
@@ -560,6 +1050,7 @@ mod tests {
       Token { kind: TokenKind::LeftBrace, start: pos1(9), end: pos1(9) },
       Token { kind: TokenKind::RightBrace, start: pos1(10), end: pos1(10) },
       Token { kind: TokenKind::Pipe, start: pos1(11), end: pos1(11) },
+      Token { kind: TokenKind::Amp, start: pos1(12), end: pos1(12) },
     ]);
   }
 
@@ -573,8 +1064,35 @@ mod tests {
   #[test]
   fn test_punctuator_comment_many() {
     assert_eq!(Lexer::new("# Hello, world!\n# This is another comment!".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Comment(String::from(" Hello, world!")), start: Position { index: 0, line: 1, column: 1 }, end: Position { index: 14, line: 1, column: 15 } },
-      Token { kind: TokenKind::Comment(String::from(" This is another comment!")), start: Position { index: 16, line: 2, column: 1 }, end: Position { index: 41, line: 2, column: 26 } },
+      Token { kind: TokenKind::Comment(String::from(" Hello, world!")), start: Position { index: 0, offset: 0, line: 1, column: 1 }, end: Position { index: 14, offset: 14, line: 1, column: 15 } },
+      Token { kind: TokenKind::Comment(String::from(" This is another comment!")), start: Position { index: 16, offset: 16, line: 2, column: 1 }, end: Position { index: 41, offset: 41, line: 2, column: 26 } },
+    ]);
+  }
+
+  #[test]
+  fn test_punctuator_comment_crlf() {
+    assert_eq!(Lexer::new("# Hello\r\n# world\r".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::Comment(String::from(" Hello")), start: Position { index: 0, offset: 0, line: 1, column: 1 }, end: Position { index: 6, offset: 6, line: 1, column: 7 } },
+      Token { kind: TokenKind::Comment(String::from(" world")), start: Position { index: 9, offset: 9, line: 2, column: 1 }, end: Position { index: 15, offset: 15, line: 2, column: 7 } },
+    ]);
+  }
+
+  #[test]
+  fn test_line_terminators() {
+    assert_eq!(Lexer::new("a\r\nb\rc\nd\r\n\r\ne".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::Name(String::from("a")), start: Position { index: 0, offset: 0, line: 1, column: 1 }, end: Position { index: 0, offset: 0, line: 1, column: 1 } },
+      Token { kind: TokenKind::Name(String::from("b")), start: Position { index: 3, offset: 3, line: 2, column: 1 }, end: Position { index: 3, offset: 3, line: 2, column: 1 } },
+      Token { kind: TokenKind::Name(String::from("c")), start: Position { index: 5, offset: 5, line: 3, column: 1 }, end: Position { index: 5, offset: 5, line: 3, column: 1 } },
+      Token { kind: TokenKind::Name(String::from("d")), start: Position { index: 7, offset: 7, line: 4, column: 1 }, end: Position { index: 7, offset: 7, line: 4, column: 1 } },
+      Token { kind: TokenKind::Name(String::from("e")), start: Position { index: 12, offset: 12, line: 6, column: 1 }, end: Position { index: 12, offset: 12, line: 6, column: 1 } },
+    ]);
+  }
+
+  #[test]
+  fn test_ignored_byte_order_mark() {
+    assert_eq!(Lexer::new("\u{FEFF}a \u{FEFF}b".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::Name(String::from("a")), start: Position { index: 1, offset: 3, line: 1, column: 2 }, end: Position { index: 1, offset: 3, line: 1, column: 2 } },
+      Token { kind: TokenKind::Name(String::from("b")), start: Position { index: 4, offset: 8, line: 1, column: 5 }, end: Position { index: 4, offset: 8, line: 1, column: 5 } },
     ]);
   }
 
@@ -668,7 +1186,10 @@ mod tests {
   #[test]
   fn test_string_error_newline() {
     assert_eq!(Lexer::new("\"Hello,\nworld!\"".chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
-      Error::UnterminatedString(Position { index: 6, line: 1, column: 7 }),
+      Error::UnterminatedString(Position { index: 6, offset: 6, line: 1, column: 7 }),
+    ]);
+    assert_eq!(Lexer::new("\"Hello,\rworld!\"".chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
+      Error::UnterminatedString(Position { index: 6, offset: 6, line: 1, column: 7 }),
     ]);
   }
 
@@ -722,62 +1243,132 @@ mod tests {
     ]);
   }
 
+  #[test]
+  fn test_string_empty() {
+    assert_eq!(Lexer::new("\"\"".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::String(String::new()), start: pos1(0), end: pos1(1) },
+    ]);
+    assert_eq!(Lexer::new("\"\" \"\"".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::String(String::new()), start: pos1(0), end: pos1(1) },
+      Token { kind: TokenKind::String(String::new()), start: pos1(3), end: pos1(4) },
+    ]);
+  }
+
+  #[test]
+  fn test_block_string_basic() {
+    assert_eq!(Lexer::new(r#""""Hello, world!""""#.chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::BlockString(String::from("Hello, world!")), start: pos1(0), end: pos1(18) },
+    ]);
+    assert_eq!(Lexer::new(r#""""""""#.chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::BlockString(String::new()), start: pos1(0), end: pos1(5) },
+    ]);
+  }
+
+  #[test]
+  fn test_block_string_no_escapes() {
+    assert_eq!(Lexer::new(r#""""\n\tA "quoted" ""double"" """"#.chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::BlockString(String::from(r#"\n\tA "quoted" ""double"" "#)), start: pos1(0), end: pos1(31) },
+    ]);
+  }
+
+  #[test]
+  fn test_block_string_escaped_triple_quote() {
+    assert_eq!(Lexer::new(r#""""a \""" b \"" c""""#.chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::BlockString(String::from(r#"a """ b \"" c"#)), start: pos1(0), end: pos1(19) },
+    ]);
+  }
+
+  #[test]
+  fn test_block_string_multiline() {
+    assert_eq!(Lexer::new("\"\"\"\n\n    Hello,\n      world!\n\n    Yours,\n      GraphQL.\n  \"\"\"".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token {
+        kind: TokenKind::BlockString(String::from("Hello,\n  world!\n\nYours,\n  GraphQL.")),
+        start: pos1(0),
+        end: Position { index: 60, offset: 60, line: 8, column: 5 },
+      },
+    ]);
+  }
+
+  #[test]
+  fn test_block_string_first_line_indentation() {
+    assert_eq!(Lexer::new("\"\"\"  first\n    second\n    third\"\"\"".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token {
+        kind: TokenKind::BlockString(String::from("  first\nsecond\nthird")),
+        start: pos1(0),
+        end: Position { index: 33, offset: 33, line: 3, column: 12 },
+      },
+    ]);
+  }
+
+  #[test]
+  fn test_block_string_error_ending() {
+    assert_eq!(Lexer::new(r#""""Hello,"#.chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
+      Error::UnexpectedEnding(pos1(8)),
+    ]);
+    assert_eq!(Lexer::new(r#""""Hello,"""#.chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
+      Error::UnexpectedEnding(pos1(10)),
+    ]);
+  }
+
   #[test]
   fn test_string_invalid_char() {
-    assert_eq!(Lexer::new("&".chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
-      Error::UnknownChar(pos1(0), '&'),
+    assert_eq!(Lexer::new("%".chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
+      Error::UnknownChar(pos1(0), '%'),
     ]);
-    assert_eq!(Lexer::new(" &".chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
-      Error::UnknownChar(pos1(1), '&'),
+    assert_eq!(Lexer::new(" %".chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
+      Error::UnknownChar(pos1(1), '%'),
     ]);
-    assert_eq!(Lexer::new("{&".chars()).collect::<Vec<Result<Token, Error>>>(), vec![
+    assert_eq!(Lexer::new("{%".chars()).collect::<Vec<Result<Token, Error>>>(), vec![
       Ok(Token { kind: TokenKind::LeftBrace, start: pos1(0), end: pos1(0) }),
-      Err(Error::UnknownChar(pos1(1), '&')),
+      Err(Error::UnknownChar(pos1(1), '%')),
     ]);
   }
 
   #[test]
   fn test_integer() {
     assert_eq!(Lexer::new("1".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(1), start: pos1(0), end: pos1(0) },
+      Token { kind: TokenKind::Int(String::from("1")), start: pos1(0), end: pos1(0) },
     ]);
     assert_eq!(Lexer::new("12".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(12), start: pos1(0), end: pos1(1) },
+      Token { kind: TokenKind::Int(String::from("12")), start: pos1(0), end: pos1(1) },
     ]);
     assert_eq!(Lexer::new("5000".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(5000), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Int(String::from("5000")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("98765".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(98765), start: pos1(0), end: pos1(4) },
+      Token { kind: TokenKind::Int(String::from("98765")), start: pos1(0), end: pos1(4) },
     ]);
     assert_eq!(Lexer::new("43210".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(43210), start: pos1(0), end: pos1(4) },
+      Token { kind: TokenKind::Int(String::from("43210")), start: pos1(0), end: pos1(4) },
     ]);
   }
 
   #[test]
   fn test_integer_negative() {
     assert_eq!(Lexer::new("-1".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(-1), start: pos1(0), end: pos1(1) },
+      Token { kind: TokenKind::Int(String::from("-1")), start: pos1(0), end: pos1(1) },
     ]);
     assert_eq!(Lexer::new("-12".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(-12), start: pos1(0), end: pos1(2) },
+      Token { kind: TokenKind::Int(String::from("-12")), start: pos1(0), end: pos1(2) },
     ]);
     assert_eq!(Lexer::new("-5000".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(-5000), start: pos1(0), end: pos1(4) },
+      Token { kind: TokenKind::Int(String::from("-5000")), start: pos1(0), end: pos1(4) },
     ]);
     assert_eq!(Lexer::new("-98765".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(-98765), start: pos1(0), end: pos1(5) },
+      Token { kind: TokenKind::Int(String::from("-98765")), start: pos1(0), end: pos1(5) },
     ]);
     assert_eq!(Lexer::new("-43210".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Int(-43210), start: pos1(0), end: pos1(5) },
+      Token { kind: TokenKind::Int(String::from("-43210")), start: pos1(0), end: pos1(5) },
     ]);
   }
 
   #[test]
-  fn test_integer_error_overload() {
-    assert_eq!(Lexer::new("9999999999".chars()).map(|result| result.unwrap_err()).collect::<Vec<Error>>(), vec![
-      Error::InvalidInteger(pos1(0), i32::from_str("9999999999").unwrap_err()),
+  fn test_integer_overload() {
+    assert_eq!(Lexer::new("9999999999".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::Int(String::from("9999999999")), start: pos1(0), end: pos1(9) },
+    ]);
+    assert_eq!(Lexer::new("-9007199254740993".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::Int(String::from("-9007199254740993")), start: pos1(0), end: pos1(16) },
     ]);
   }
 
@@ -804,13 +1395,22 @@ mod tests {
   #[test]
   fn test_float() {
     assert_eq!(Lexer::new("3.0".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(3.0), start: pos1(0), end: pos1(2) },
+      Token { kind: TokenKind::Float(String::from("3.0")), start: pos1(0), end: pos1(2) },
     ]);
     assert_eq!(Lexer::new("3.14".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(3.14), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("3.14")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("-3.14".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(-3.14), start: pos1(0), end: pos1(4) },
+      Token { kind: TokenKind::Float(String::from("-3.14")), start: pos1(0), end: pos1(4) },
+    ]);
+    assert_eq!(Lexer::new("0.1".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::Float(String::from("0.1")), start: pos1(0), end: pos1(2) },
+    ]);
+    assert_eq!(Lexer::new("-0.05".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::Float(String::from("-0.05")), start: pos1(0), end: pos1(4) },
+    ]);
+    assert_eq!(Lexer::new("0e10".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
+      Token { kind: TokenKind::Float(String::from("0e10")), start: pos1(0), end: pos1(3) },
     ]);
   }
 
@@ -824,51 +1424,51 @@ mod tests {
   #[test]
   fn test_float_exponent() {
     assert_eq!(Lexer::new("2e2".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(2e2), start: pos1(0), end: pos1(2) },
+      Token { kind: TokenKind::Float(String::from("2e2")), start: pos1(0), end: pos1(2) },
     ]);
     assert_eq!(Lexer::new("2E2".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(2e2), start: pos1(0), end: pos1(2) },
+      Token { kind: TokenKind::Float(String::from("2E2")), start: pos1(0), end: pos1(2) },
     ]);
     assert_eq!(Lexer::new("3e4".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(3e4), start: pos1(0), end: pos1(2) },
+      Token { kind: TokenKind::Float(String::from("3e4")), start: pos1(0), end: pos1(2) },
     ]);
     assert_eq!(Lexer::new("3E4".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(3e4), start: pos1(0), end: pos1(2) },
+      Token { kind: TokenKind::Float(String::from("3E4")), start: pos1(0), end: pos1(2) },
     ]);
   }
 
   #[test]
   fn test_float_exponent_signs() {
     assert_eq!(Lexer::new("2e-2".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(2e-2), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("2e-2")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("2E-2".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(2e-2), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("2E-2")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("3e-4".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(3e-4), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("3e-4")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("3E-4".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(3e-4), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("3E-4")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("2e+2".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(2e+2), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("2e+2")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("2E+2".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(2e+2), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("2E+2")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("3e+4".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(3e+4), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("3e+4")), start: pos1(0), end: pos1(3) },
     ]);
     assert_eq!(Lexer::new("3E+4".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(3e+4), start: pos1(0), end: pos1(3) },
+      Token { kind: TokenKind::Float(String::from("3E+4")), start: pos1(0), end: pos1(3) },
     ]);
   }
 
   #[test]
   fn test_float_overload() {
     assert_eq!(Lexer::new("99999999.0".chars()).map(|result| result.unwrap()).collect::<Vec<Token>>(), vec![
-      Token { kind: TokenKind::Float(100000000_f32), start: pos1(0), end: pos1(9) },
+      Token { kind: TokenKind::Float(String::from("99999999.0")), start: pos1(0), end: pos1(9) },
     ]);
   }
 
@@ -945,4 +1545,35 @@ mod tests {
     assert_eq!(lexer.next(), None);
     assert_eq!(lexer.next(), None);
   }
+
+  #[test]
+  fn test_borrowed() {
+    let source = "query { a(b: \"c\", d: \"e\\nf\", g: -1.5e3) # h\n  i: \"ü\" \"\"\"\n  block\n\"\"\" } %";
+    let owned = Lexer::new(source.chars()).collect::<Vec<_>>();
+    let borrowed = Lexer::borrowed(source).map(|result| result.map(Token::into_owned)).collect::<Vec<_>>();
+    assert_eq!(borrowed, owned);
+    let kinds = Lexer::borrowed("a \"ü\" \"\\n\" 1.5 # c").map(|result| result.unwrap().kind).collect::<Vec<_>>();
+    match (&kinds[0], &kinds[1], &kinds[2], &kinds[3], &kinds[4]) {
+      (
+        &TokenKind::Name(Cow::Borrowed("a")),
+        &TokenKind::String(Cow::Borrowed("ü")),
+        &TokenKind::String(Cow::Owned(ref string)),
+        &TokenKind::Float(Cow::Borrowed("1.5")),
+        &TokenKind::Comment(Cow::Borrowed(" c")),
+      ) => assert_eq!(string, "\n"),
+      _ => panic!("Unexpected kinds {:?}", kinds),
+    }
+  }
+
+  #[test]
+  fn test_byte_offsets() {
+    let source = "\"ü\" a\n\"€\"";
+    let tokens = Lexer::new(source.chars()).map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(tokens, vec![
+      Token::new(TokenKind::String(String::from("ü")), Position { index: 0, offset: 0, line: 1, column: 1 }, Position { index: 2, offset: 3, line: 1, column: 3 }),
+      Token::new(TokenKind::Name(String::from("a")), Position { index: 4, offset: 5, line: 1, column: 5 }, Position { index: 4, offset: 5, line: 1, column: 5 }),
+      Token::new(TokenKind::String(String::from("€")), Position { index: 6, offset: 7, line: 2, column: 1 }, Position { index: 8, offset: 11, line: 2, column: 3 }),
+    ]);
+    assert_eq!(&source[tokens[2].start.offset..], "\"€\"");
+  }
 }
@@ -0,0 +1,324 @@
+use super::Position;
+
+/// The unit in which the column of a `LineColumn` is counted. Editors and
+/// their protocols don’t agree on what a column is, so every `LineIndex`
+/// conversion says which one it means.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Encoding {
+  /// Columns count UTF-8 bytes.
+  Utf8,
+  /// Columns count UTF-16 code units, like the Language Server Protocol.
+  Utf16,
+  /// Columns count unicode characters, like `Position::column`.
+  Char,
+}
+
+/// A 0-indexed line and column, like the positions of the Language Server
+/// Protocol. What the column counts depends on the `Encoding` it was created
+/// with.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LineColumn {
+  /// The 0-indexed line.
+  pub line: usize,
+  /// The 0-indexed column in the line.
+  pub column: usize,
+}
+
+/// Converts between the byte offsets, character indexes and lines and columns
+/// in any `Encoding` of a source document.
+///
+/// Building the index walks the source once, after that every conversion is
+/// a binary search so it takes `O(log n)` time. Lines end with `\n`, `\r\n`
+/// or `\r` like they do for the lexer. In a `LineColumn` a line terminator
+/// is on the line it ends, but in a `Position` it is on the line it starts.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LineIndex {
+  /// Every line in the source document.
+  lines: Vec<Line>,
+  /// Every character in the source document which is more than one byte long
+  /// in order. Lines refer to their range of this list.
+  wide_chars: Vec<WideChar>,
+  /// The length of the source document in bytes.
+  len: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Line {
+  /// The byte offset at which the line starts.
+  offset: usize,
+  /// The character index at which the line starts.
+  index: usize,
+  /// The length of the line in bytes, not including the line terminator.
+  len: usize,
+  /// The first of the line’s wide characters.
+  wide_start: usize,
+  /// One past the last of the line’s wide characters.
+  wide_end: usize,
+}
+
+/// A character which takes up more than one byte. Its columns are the
+/// columns at which it starts in its line.
+#[derive(Clone, PartialEq, Debug)]
+struct WideChar {
+  utf8: usize,
+  utf16: usize,
+  char: usize,
+  len_utf8: usize,
+  len_utf16: usize,
+}
+
+impl WideChar {
+  /// The column at which this character starts, and the length of this
+  /// character, in an encoding.
+  fn get(&self, encoding: Encoding) -> (usize, usize) {
+    match encoding {
+      Encoding::Utf8 => (self.utf8, self.len_utf8),
+      Encoding::Utf16 => (self.utf16, self.len_utf16),
+      Encoding::Char => (self.char, 1),
+    }
+  }
+}
+
+impl LineIndex {
+  /// Creates a new line index for a source document.
+  pub fn new(source: &str) -> Self {
+    let mut index = LineIndex {
+      lines: Vec::new(),
+      wide_chars: Vec::new(),
+      len: source.len(),
+    };
+    let mut line = Line { offset: 0, index: 0, len: 0, wide_start: 0, wide_end: 0 };
+    let mut column = (0, 0, 0);
+    let mut chars = source.char_indices().enumerate().peekable();
+    while let Some((char_index, (offset, c))) = chars.next() {
+      if c == '\n' || c == '\r' {
+        // A `\r\n` pair is a single line terminator.
+        let mut next = (char_index + 1, offset + 1);
+        if c == '\r' {
+          if let Some(&(char_index, (offset, '\n'))) = chars.peek() {
+            chars.next();
+            next = (char_index + 1, offset + 1);
+          }
+        }
+        line.len = offset - line.offset;
+        line.wide_end = index.wide_chars.len();
+        index.lines.push(line);
+        line = Line { offset: next.1, index: next.0, len: 0, wide_start: index.wide_chars.len(), wide_end: 0 };
+        column = (0, 0, 0);
+        continue;
+      }
+      if c.len_utf8() > 1 {
+        index.wide_chars.push(WideChar {
+          utf8: column.0,
+          utf16: column.1,
+          char: column.2,
+          len_utf8: c.len_utf8(),
+          len_utf16: c.len_utf16(),
+        });
+      }
+      column = (column.0 + c.len_utf8(), column.1 + c.len_utf16(), column.2 + 1);
+    }
+    line.len = source.len() - line.offset;
+    line.wide_end = index.wide_chars.len();
+    index.lines.push(line);
+    index
+  }
+
+  /// The number of lines in the source document. An empty document has one
+  /// empty line.
+  pub fn line_count(&self) -> usize {
+    self.lines.len()
+  }
+
+  /// Gets the line and column of a byte offset in the source document.
+  /// Returns `None` if the offset is past the end of the document or inside
+  /// of a character.
+  pub fn line_column(&self, offset: usize, encoding: Encoding) -> Option<LineColumn> {
+    if offset > self.len {
+      return None;
+    }
+    let line = match self.lines.binary_search_by_key(&offset, |line| line.offset) {
+      Ok(line) => line,
+      Err(line) => line - 1,
+    };
+    // Offsets in the line terminator are at the end of the line.
+    let column = (offset - self.lines[line].offset).min(self.lines[line].len);
+    self.convert(line, column, Encoding::Utf8, encoding).map(|column| LineColumn { line: line, column: column })
+  }
+
+  /// Gets the byte offset of a line and column in the source document.
+  /// Returns `None` if the line is past the end of the document or the column
+  /// is inside of a character. Columns past the end of a line are at the end
+  /// of the line, as editors expect.
+  pub fn offset(&self, line_column: LineColumn, encoding: Encoding) -> Option<usize> {
+    self.lines.get(line_column.line).and_then(|line| {
+      let len = self.column_len(line_column.line, encoding);
+      self.convert(line_column.line, line_column.column.min(len), encoding, Encoding::Utf8).map(|column| line.offset + column)
+    })
+  }
+
+  /// Gets the byte offset of a character index in the source document.
+  /// Returns `None` if the index is past the end of the document.
+  pub fn index_offset(&self, index: usize) -> Option<usize> {
+    let line = match self.lines.binary_search_by_key(&index, |line| line.index) {
+      Ok(line) => line,
+      Err(line) => line - 1,
+    };
+    let column = index - self.lines[line].index;
+    let len = self.column_len(line, Encoding::Char);
+    if column <= len {
+      self.convert(line, column, Encoding::Char, Encoding::Utf8).map(|column| self.lines[line].offset + column)
+    } else {
+      // The index is in a line terminator, which may be two characters.
+      let offset = self.lines[line].offset + self.lines[line].len + column - len;
+      let next = self.lines.get(line + 1).map_or(self.len, |line| line.offset);
+      if offset < next { Some(offset) } else { None }
+    }
+  }
+
+  /// Gets the `Position` of a byte offset in the source document, with a
+  /// 1-indexed line and column like the positions the lexer creates. Returns
+  /// `None` if the offset is past the end of the document or inside of a
+  /// character.
+  ///
+  /// Unlike `line_column`, this agrees with the lexer about line terminators:
+  /// their characters are at column 0 of the line which they start.
+  pub fn position(&self, offset: usize) -> Option<Position> {
+    self.line_column(offset, Encoding::Char).map(|line_column| {
+      let line = &self.lines[line_column.line];
+      let end = line.offset + line.len;
+      if offset >= end && line_column.line + 1 < self.lines.len() {
+        Position {
+          index: line.index + line_column.column + offset - end,
+          offset: offset,
+          line: line_column.line + 2,
+          column: 0,
+        }
+      } else {
+        Position {
+          index: line.index + line_column.column,
+          offset: offset,
+          line: line_column.line + 1,
+          column: line_column.column + 1,
+        }
+      }
+    })
+  }
+
+  /// Converts a `Position` into a line and column in an encoding, for
+  /// example to send the locations of the parser to an editor.
+  pub fn position_line_column(&self, position: Position, encoding: Encoding) -> Option<LineColumn> {
+    self.line_column(position.offset, encoding)
+  }
+
+  /// The length of a line in an encoding, not including the line terminator.
+  fn column_len(&self, line: usize, encoding: Encoding) -> usize {
+    self.convert(line, self.lines[line].len, Encoding::Utf8, encoding).unwrap_or(0)
+  }
+
+  /// Converts a column in a line from one encoding to another. Returns `None`
+  /// if the column is inside of a character.
+  fn convert(&self, line: usize, column: usize, from: Encoding, to: Encoding) -> Option<usize> {
+    let line = &self.lines[line];
+    let wide_chars = &self.wide_chars[line.wide_start..line.wide_end];
+    // Find the last wide character which starts at or before the column.
+    // Everything after it up to the column is one unit long in every encoding.
+    let found = match wide_chars.binary_search_by_key(&column, |wide_char| wide_char.get(from).0) {
+      Ok(i) => return Some(wide_chars[i].get(to).0),
+      Err(0) => return Some(column),
+      Err(i) => &wide_chars[i - 1],
+    };
+    let (start, len) = found.get(from);
+    if column < start + len {
+      return None;
+    }
+    let (to_start, to_len) = found.get(to);
+    Some(to_start + to_len + column - start - len)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::lexer::{advance, Lexer};
+
+  fn line_column(line: usize, column: usize) -> LineColumn {
+    LineColumn { line: line, column: column }
+  }
+
+  #[test]
+  fn test_line_column() {
+    // `é` is 2 bytes and 1 UTF-16 unit, `😀` is 4 bytes and 2 UTF-16 units.
+    let index = LineIndex::new("aé😀b\r\nc😀\rd\n");
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.line_column(0, Encoding::Utf16), Some(line_column(0, 0)));
+    assert_eq!(index.line_column(7, Encoding::Utf8), Some(line_column(0, 7)));
+    assert_eq!(index.line_column(7, Encoding::Utf16), Some(line_column(0, 4)));
+    assert_eq!(index.line_column(7, Encoding::Char), Some(line_column(0, 3)));
+    assert_eq!(index.line_column(2, Encoding::Utf16), None);
+    assert_eq!(index.line_column(9, Encoding::Utf16), Some(line_column(0, 5)));
+    assert_eq!(index.line_column(10, Encoding::Utf16), Some(line_column(1, 0)));
+    assert_eq!(index.line_column(11, Encoding::Utf16), Some(line_column(1, 1)));
+    assert_eq!(index.line_column(15, Encoding::Utf16), Some(line_column(1, 3)));
+    assert_eq!(index.line_column(16, Encoding::Char), Some(line_column(2, 0)));
+    assert_eq!(index.line_column(18, Encoding::Char), Some(line_column(3, 0)));
+    assert_eq!(index.line_column(19, Encoding::Char), None);
+  }
+
+  #[test]
+  fn test_offset() {
+    let index = LineIndex::new("aé😀b\r\nc😀\rd\n");
+    assert_eq!(index.offset(line_column(0, 4), Encoding::Utf16), Some(7));
+    assert_eq!(index.offset(line_column(0, 3), Encoding::Utf16), None);
+    assert_eq!(index.offset(line_column(0, 3), Encoding::Char), Some(7));
+    assert_eq!(index.offset(line_column(0, 7), Encoding::Utf8), Some(7));
+    assert_eq!(index.offset(line_column(0, 100), Encoding::Utf16), Some(8));
+    assert_eq!(index.offset(line_column(1, 3), Encoding::Utf16), Some(15));
+    assert_eq!(index.offset(line_column(3, 0), Encoding::Utf16), Some(18));
+    assert_eq!(index.offset(line_column(4, 0), Encoding::Utf16), None);
+    for offset in 0..19 {
+      for &encoding in &[Encoding::Utf8, Encoding::Utf16, Encoding::Char] {
+        if let Some(line_column) = index.line_column(offset, encoding) {
+          // Both bytes of the `\r\n` are at the end of the first line.
+          assert_eq!(index.offset(line_column, encoding), Some(if offset == 9 { 8 } else { offset }));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_lexer_positions() {
+    let source = "{\r\n  a: b(c: \"😀\") # d😀\r  e\n}";
+    let index = LineIndex::new(source);
+    for token in Lexer::new(source.chars()) {
+      let token = token.unwrap();
+      assert_eq!(index.position(token.start.offset), Some(token.start));
+      assert_eq!(index.position(token.end.offset), Some(token.end));
+      assert_eq!(index.index_offset(token.start.index), Some(token.start.offset));
+      assert_eq!(index.index_offset(token.end.index), Some(token.end.offset));
+    }
+    assert_eq!(index.position_line_column(Lexer::new(source.chars()).nth(8).unwrap().unwrap().start, Encoding::Utf16), Some(line_column(1, 14)));
+    assert_eq!(index.index_offset(1), Some(1));
+    assert_eq!(index.index_offset(2), Some(2));
+    assert_eq!(index.index_offset(source.chars().count()), Some(source.len()));
+    assert_eq!(index.index_offset(source.chars().count() + 1), None);
+  }
+
+  #[test]
+  fn test_line_terminator_positions() {
+    // Every character, including the characters of line terminators, must be
+    // at the position the lexer gives it.
+    let source = "a\r\nb\rc\n\nd\r\n";
+    let index = LineIndex::new(source);
+    let mut pos = Position { index: 0, offset: 0, line: 1, column: 1 };
+    let chars = source.chars().collect::<Vec<_>>();
+    for (i, &c) in chars.iter().enumerate() {
+      assert_eq!(index.position(pos.offset), Some(pos));
+      if let Some(&next) = chars.get(i + 1) {
+        advance(&mut pos, c, next);
+      }
+    }
+    assert_eq!(index.position(1), Some(Position { index: 1, offset: 1, line: 2, column: 0 }));
+    assert_eq!(index.position(2), Some(Position { index: 2, offset: 2, line: 2, column: 0 }));
+  }
+}
@@ -4,15 +4,30 @@
 //! source text, parse those tokens into an AST, and visit/mutate the AST.
 
 mod position;
+mod line_index;
+mod diagnostic;
 mod lexer;
+mod reader;
+mod tokenizer;
 mod parser;
+mod cst;
+mod builder;
 mod printer;
 mod visitor;
+mod highlight;
+#[cfg(feature = "serde")]
+mod json;
 
 pub mod ast;
 
-pub use self::position::{Position, Location};
+pub use self::position::{Position, Location, Source};
+pub use self::line_index::{LineIndex, LineColumn, Encoding};
+pub use self::diagnostic::{Diagnostic, Severity};
 pub use self::lexer::{Token, TokenKind, Error as LexerError};
-pub use self::parser::{parse, parse_without_location, Error};
+pub use self::tokenizer::{tokenize, Tokenizer, Lexeme, LexemeKind};
+pub use self::parser::{parse, parse_without_location, parse_borrowed, parse_source, parse_sources, parse_reader, parse_with_recovery, RECOVERY_MAX_DEPTH, parse_with_options, parse_with_recovery_and_options, parse_value, parse_const_value, parse_type, ParseOptions, Limit, Error, SourceError, ReadError, Expected};
+pub use self::reader::ReadChars;
+pub use self::cst::{parse_cst, SyntaxTree, SyntaxToken, Trivia};
 pub use self::printer::{print};
 pub use self::visitor::{visit, Visitor, ParallelVisitor};
+pub use self::highlight::{highlight, highlight_ansi, highlight_html, Highlight};
@@ -1,14 +1,17 @@
-use super::{Position, Location};
-use super::lexer::{Lexer, Error as LexerError, Token, TokenKind};
+use std::borrow::Cow;
+use std::error;
+use std::fmt;
+use std::io;
+
+use super::{Position, Location, Source};
+use super::lexer::{Lexer, Input, Error as LexerError, Token, TokenKind};
+use super::reader::ReadChars;
 use super::ast;
 
 /// Parses an iterator of characters into a GraphQL AST which may return an
 /// error.
 pub fn parse<I>(iter: I) -> Result<ast::Document, Error> where I: IntoIterator<Item=char> {
-  let mut parser = Parser {
-    lexer: Lexer::new(iter),
-    include_location: true,
-  };
+  let mut parser = Parser::new(Lexer::new(iter), true);
   parser.parse_document()
 }
 
@@ -18,94 +21,627 @@ pub fn parse<I>(iter: I) -> Result<ast::Document, Error> where I: IntoIterator<I
 /// Will not include a location on the AST nodes which may drastically reduce
 /// the size of the AST.
 pub fn parse_without_location<I>(iter: I) -> Result<ast::Document, Error> where I: IntoIterator<Item=char> {
-  let mut parser = Parser {
-    lexer: Lexer::new(iter),
-    include_location: false,
-  };
+  let mut parser = Parser::new(Lexer::new(iter), false);
   parser.parse_document()
 }
 
+/// Parses an iterator of characters into a GraphQL AST with limits on the
+/// size of the document, which may return an error.
+///
+/// Use this to parse documents from untrusted sources. Without limits a
+/// deeply nested document can overflow the stack, and a very long document
+/// can use up all of the memory. When the document exceeds a limit parsing
+/// stops with an `Error::LimitExceeded`.
+pub fn parse_with_options<I>(iter: I, options: &ParseOptions) -> Result<ast::Document, Error> where I: IntoIterator<Item=char> {
+  let mut parser = Parser::new(Lexer::new(iter), true);
+  parser.set_options(options);
+  let document = parser.parse_document();
+  // The parser sees the end of the document where a limit was exceeded, so
+  // it may have failed with some other error or even succeeded.
+  match parser.limit_error.take() {
+    Some(error) => Err(error),
+    None => document,
+  }
+}
+
+/// Parses a string slice into a GraphQL AST which may return an error.
+///
+/// Unlike `parse`, the AST borrows names, numbers and strings from the source
+/// so that it does not need to allocate for them. Only strings with escape
+/// sequences and block strings are copied. Use `IntoOwned` to convert the AST
+/// into the owned AST that `print` and `visit` work with.
+pub fn parse_borrowed<'a>(source: &'a str) -> Result<ast::Document<Cow<'a, str>>, Error> {
+  let mut parser = Parser::new(Lexer::borrowed(source), true);
+  parser.parse_document()
+}
+
+/// Parses a named source document into a GraphQL AST which may return an
+/// error.
+///
+/// Every location in the AST refers back to the source, so the text of any
+/// node can be found with `Node::source_text`.
+pub fn parse_source(source: &Source) -> Result<ast::Document, Error> {
+  let mut parser = Parser::new(Lexer::new(source.body().chars()), true);
+  parser.source = Some(source.clone());
+  parser.parse_document()
+}
+
+/// Parses several named source documents into a single GraphQL AST which may
+/// return an error. This is useful for schemas which are split across files.
+///
+/// The definitions of every source are in the document in order, and every
+/// location in the AST refers back to the source it is in. The document
+/// itself has no location since it is in more than one source. If a source
+/// can’t be parsed, the error says which source it was.
+pub fn parse_sources(sources: &[Source]) -> Result<ast::Document, SourceError> {
+  let mut definitions = Vec::new();
+  for source in sources {
+    match parse_source(source) {
+      Ok(document) => definitions.extend(document.definitions),
+      Err(error) => return Err(SourceError {
+        source: source.clone(),
+        error: Box::new(error),
+      }),
+    }
+  }
+  Ok(ast::Document {
+    loc: None,
+    definitions: definitions,
+  })
+}
+
+/// Parses the UTF-8 text of a reader into a GraphQL AST which may return an
+/// error.
+///
+/// The text is decoded as it is read, so large documents don’t need to be
+/// read into memory before parsing. If the reader fails, or the text is not
+/// valid UTF-8, that error is returned instead of a parse error.
+pub fn parse_reader<R>(reader: R) -> Result<ast::Document, ReadError> where R: io::Read {
+  let mut chars = ReadChars::new(reader);
+  let document = parse(&mut chars);
+  // An I/O error ends the characters early, so it causes any parse error.
+  match chars.take_error() {
+    Some(error) => Err(ReadError::Io(error)),
+    None => document.map_err(ReadError::Parse),
+  }
+}
+
+/// Parses an iterator of characters into a GraphQL AST without stopping at
+/// the first error. This is useful for tools like editors where the source
+/// document is often incomplete.
+///
+/// Errors are recovered from by skipping tokens until the start of the next
+/// selection in a selection set, or the start of the next definition in the
+/// document. The returned document contains everything that could be parsed,
+/// and the returned errors are ordered by their position in the source. If
+/// there are no errors then the document is the same as the one `parse`
+/// would return.
+///
+/// So that any input can be parsed without overflowing the stack, nodes
+/// nested more than `RECOVERY_MAX_DEPTH` levels deep are skipped with an
+/// `Error::LimitExceeded`.
+pub fn parse_with_recovery<I>(iter: I) -> (ast::Document, Vec<Error>) where I: IntoIterator<Item=char> {
+  parse_with_recovery_and_options(iter, &ParseOptions::default())
+}
+
+/// Like `parse_with_recovery`, but with limits on the size of the document.
+/// Exceeding a limit is an error like any other, so parsing recovers from a
+/// node which is nested too deeply.
+///
+/// Without a `max_depth` the depth is limited to `RECOVERY_MAX_DEPTH`.
+///
+/// ```
+/// use graphql::language::{parse_with_recovery_and_options, print, ParseOptions, Limit, Error};
+///
+/// let options = ParseOptions { max_depth: Some(2), ..ParseOptions::default() };
+/// let (document, errors) = parse_with_recovery_and_options("{ a b { c { d } } }".chars(), &options);
+/// assert_eq!(print(&document), "{\n  a\n  b {\n  }\n}\n");
+/// match errors[0] {
+///   Error::LimitExceeded(_, Limit::Depth(2)) => (),
+///   ref error => panic!("Unexpected error {:?}", error),
+/// }
+/// ```
+pub fn parse_with_recovery_and_options<I>(iter: I, options: &ParseOptions) -> (ast::Document, Vec<Error>) where I: IntoIterator<Item=char> {
+  let mut parser = Parser::new(Lexer::recovering(iter), true);
+  parser.set_options(options);
+  if parser.options.max_depth.is_none() {
+    parser.options.max_depth = Some(RECOVERY_MAX_DEPTH);
+  }
+  parser.errors = Some(Vec::new());
+  let document = parser.parse_document_with_recovery();
+  let mut errors = parser.errors.take().unwrap_or_default();
+  errors.extend(parser.lexer.take_errors().into_iter().map(Error::Lexer));
+  errors.extend(parser.limit_error.take());
+  errors.sort_by_key(|error| error.position().map_or(usize::MAX, |position| position.index));
+  (document, errors)
+}
+
+/// The maximum depth of a document parsed with recovery when no other
+/// maximum is given. Parsing a deeper document could overflow the stack.
+pub const RECOVERY_MAX_DEPTH: usize = 128;
+
+/// Parses an iterator of characters into a single GraphQL value, like
+/// `{ a: [1, 2], b: $c }`. The entire source must be the value.
+pub fn parse_value<I>(iter: I) -> Result<ast::Value, Error> where I: IntoIterator<Item=char> {
+  let mut parser = Parser::new(Lexer::new(iter), true);
+  let value = try!(parser.parse_value());
+  try!(parser.expect_end());
+  Ok(value)
+}
+
+/// Parses an iterator of characters into a single constant GraphQL value. This
+/// is the same as `parse_value` except that variables are not allowed, which
+/// makes it suitable for things like default values.
+pub fn parse_const_value<I>(iter: I) -> Result<ast::Value, Error> where I: IntoIterator<Item=char> {
+  let mut parser = Parser::new(Lexer::new(iter), true);
+  let value = try!(parser.parse_const_value());
+  try!(parser.expect_end());
+  Ok(value)
+}
+
+/// Parses an iterator of characters into a single GraphQL type reference,
+/// like `[ID!]!`. The entire source must be the type.
+pub fn parse_type<I>(iter: I) -> Result<ast::Type, Error> where I: IntoIterator<Item=char> {
+  let mut parser = Parser::new(Lexer::new(iter), true);
+  let type_ = try!(parser.parse_type());
+  try!(parser.expect_end());
+  Ok(type_)
+}
+
+/// Limits on the documents accepted by `parse_with_options`. A limit of
+/// `None` means there is no limit, which is the default for every limit.
+///
+/// ```txt
+/// ParseOptions { max_depth: Some(64), ..ParseOptions::default() }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ParseOptions {
+  /// The maximum number of selection sets, list values, object values and
+  /// list types which may be nested inside of each other.
+  pub max_depth: Option<usize>,
+  /// The maximum number of tokens in the document. Comments count as tokens.
+  pub max_tokens: Option<usize>,
+  /// The maximum length of the document in bytes. Nothing past the maximum
+  /// is read, so not even a single huge token is kept in memory.
+  pub max_length: Option<usize>,
+}
+
+/// A limit from `ParseOptions` which a document exceeded, with the maximum
+/// it exceeded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Limit {
+  /// The document was nested too deeply.
+  Depth(usize),
+  /// The document had too many tokens.
+  Tokens(usize),
+  /// The document was too long.
+  Length(usize),
+}
+
 /// A parser context.
-struct Parser<I: Iterator<Item=char>> {
+struct Parser<I: Input> {
   /// The lexer that will be consumed while parsing.
   lexer: Lexer<I>,
   /// Whether or not to include the location in the AST object. Not including
   /// the location can drastically reduce the size of the AST.
   include_location: bool,
+  /// If we are recovering from errors, the errors we have recovered from so
+  /// far. Otherwise `None` and the first error is returned.
+  errors: Option<Vec<Error>>,
+  /// The number of tokens consumed so far. Used when recovering to make sure
+  /// we always make progress.
+  consumed: usize,
+  /// Everything we checked for at the next token, but did not find. Cleared
+  /// whenever a token is consumed.
+  expected: Vec<Expected>,
+  /// The position of the last character of the last token we consumed. This
+  /// is where the node we just finished parsing ends.
+  end: Position,
+  /// The source document we are parsing, if it has one. Every location we
+  /// create refers to this source.
+  source: Option<Source>,
+  /// The limits on the document we are parsing.
+  options: ParseOptions,
+  /// The number of selection sets, list values, object values and list types
+  /// we are currently inside of.
+  depth: usize,
+  /// The number of tokens taken from the lexer so far, including comments.
+  tokens: usize,
+  /// The error for the limit the document exceeded, if any. Once a limit is
+  /// exceeded the parser sees the end of the document.
+  limit_error: Option<Error>,
 }
 
 /// An error which occurred while parsing the source document into a GraphQL
 /// AST.
 #[derive(PartialEq, Debug)]
 pub enum Error {
-  /// The source ended unexpectedly.
-  UnexpectedEnding(Position),
-  /// When parsing we encountered an unexpected token.
-  UnexpectedToken(Token),
+  /// The source ended unexpectedly. Also includes everything that would have
+  /// been valid instead of the ending.
+  UnexpectedEnding(Position, Vec<Expected>),
+  /// When parsing we encountered an unexpected token. Also includes
+  /// everything that would have been valid instead of the token.
+  UnexpectedToken(Token, Vec<Expected>),
   /// An error ocurred while lexing the source document.
   Lexer(LexerError),
+  /// The source document exceeded one of the limits in `ParseOptions`.
+  LimitExceeded(Position, Limit),
   /// This error should never occur. But if it does, this cryptic error is
   /// better then panicking.
   Unreachable,
 }
 
+/// Something the parser expected to find when it failed.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Expected {
+  /// A token of this kind. Only used for punctuators like `:` or `)`.
+  Token(TokenKind),
+  /// Any name.
+  Name,
+  /// A name which is exactly this keyword, like `on` or `query`.
+  Keyword(&'static str),
+  /// Any value, like `42`, `"hello"` or `$variable`.
+  Value,
+  /// A string which describes a type system definition.
+  Description,
+  /// The name of a directive location, like `FIELD`.
+  DirectiveLocation,
+}
+
+impl fmt::Display for Expected {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Expected::Token(ref kind) => write!(f, "{}", kind),
+      Expected::Name => write!(f, "a name"),
+      Expected::Keyword(keyword) => write!(f, "`{}`", keyword),
+      Expected::Value => write!(f, "a value"),
+      Expected::Description => write!(f, "a description"),
+      Expected::DirectiveLocation => write!(f, "a directive location"),
+    }
+  }
+}
+
 impl From<LexerError> for Error {
   fn from(error: LexerError) -> Error {
     Error::Lexer(error)
   }
 }
 
-impl<I> Parser<I> where I: Iterator<Item=char> {
+impl Error {
+  /// A stable code which identifies the kind of this error. Codes never change
+  /// meaning between versions so they may be used to look up documentation or
+  /// to filter errors.
+  pub fn code(&self) -> &'static str {
+    match *self {
+      Error::UnexpectedEnding(_, _) => "E0001",
+      Error::UnexpectedToken(_, _) => "E0002",
+      Error::LimitExceeded(_, _) => "E0003",
+      Error::Lexer(ref error) => error.code(),
+      Error::Unreachable => "E0999",
+    }
+  }
+
+  /// The position in the source document at which this error occurred.
+  pub fn position(&self) -> Option<Position> {
+    match *self {
+      Error::UnexpectedEnding(position, _) => Some(position),
+      Error::UnexpectedToken(ref token, _) => Some(token.start),
+      Error::LimitExceeded(position, _) => Some(position),
+      Error::Lexer(ref error) => error.position(),
+      Error::Unreachable => None,
+    }
+  }
+
+  /// The range in the source document at which this error occurred. For an
+  /// unexpected token this covers the entire token.
+  pub fn location(&self) -> Option<Location> {
+    match *self {
+      Error::UnexpectedToken(ref token, _) => Some(Location::new(token.start, token.end)),
+      _ => self.position().map(|position| Location::new(position, position)),
+    }
+  }
+
+  /// Everything that would have been valid where this error occurred.
+  pub fn expected(&self) -> &[Expected] {
+    match *self {
+      Error::UnexpectedEnding(_, ref expected) |
+      Error::UnexpectedToken(_, ref expected) => expected,
+      _ => &[],
+    }
+  }
+
+  /// A human readable message describing this error without its position.
+  pub fn message(&self) -> String {
+    match *self {
+      Error::UnexpectedEnding(_, ref expected) => format!("Unexpected end of input{}.", expected_list(expected)),
+      Error::UnexpectedToken(ref token, ref expected) => format!("Unexpected {}{}.", token.kind, expected_list(expected)),
+      Error::Lexer(ref error) => error.message(),
+      Error::LimitExceeded(_, Limit::Depth(max_depth)) => format!("Document is nested more than {} levels deep.", max_depth),
+      Error::LimitExceeded(_, Limit::Tokens(max_tokens)) => format!("Document has more than {} tokens.", max_tokens),
+      Error::LimitExceeded(_, Limit::Length(max_length)) => format!("Document is longer than {} bytes.", max_length),
+      Error::Unreachable => String::from("An unreachable error occurred. This is a bug."),
+    }
+  }
+}
+
+/// Formats what was expected as the end of an error message, like
+/// “, expected `:` or `)`”.
+fn expected_list(expected: &[Expected]) -> String {
+  let mut list = String::new();
+  for (i, item) in expected.iter().enumerate() {
+    list.push_str(if i == 0 { ", expected " } else if i == expected.len() - 1 { " or " } else { ", " });
+    list.push_str(&item.to_string());
+  }
+  list
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.position() {
+      Some(position) => write!(f, "{} ({}:{})", self.message(), position.line, position.column),
+      None => write!(f, "{}", self.message()),
+    }
+  }
+}
+
+impl error::Error for Error {
+  fn source(&self) -> Option<&(error::Error + 'static)> {
+    match *self {
+      Error::Lexer(ref error) => Some(error),
+      _ => None,
+    }
+  }
+}
+
+/// An error which occurred while parsing one of several source documents
+/// with `parse_sources`.
+#[derive(PartialEq, Debug)]
+pub struct SourceError {
+  /// The source document which could not be parsed.
+  pub source: Source,
+  /// The error in that source document. It is boxed so that results with
+  /// this error stay small.
+  pub error: Box<Error>,
+}
+
+impl SourceError {
+  /// The range in the source document at which this error occurred. Unlike
+  /// the location of the error itself, this location refers to the source.
+  pub fn location(&self) -> Option<Location> {
+    self.error.location().map(|location| Location {
+      source: Some(self.source.clone()),
+      ..location
+    })
+  }
+}
+
+impl fmt::Display for SourceError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}: {}", self.source, self.error)
+  }
+}
+
+impl error::Error for SourceError {
+  fn source(&self) -> Option<&(error::Error + 'static)> {
+    Some(&*self.error)
+  }
+}
+
+/// An error which occurred while reading and parsing a document with
+/// `parse_reader`.
+#[derive(Debug)]
+pub enum ReadError {
+  /// The document could not be read, or it was not valid UTF-8.
+  Io(io::Error),
+  /// The document was read but could not be parsed.
+  Parse(Error),
+}
+
+impl From<io::Error> for ReadError {
+  fn from(error: io::Error) -> ReadError {
+    ReadError::Io(error)
+  }
+}
+
+impl From<Error> for ReadError {
+  fn from(error: Error) -> ReadError {
+    ReadError::Parse(error)
+  }
+}
+
+impl fmt::Display for ReadError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      ReadError::Io(ref error) => write!(f, "{}", error),
+      ReadError::Parse(ref error) => write!(f, "{}", error),
+    }
+  }
+}
+
+impl error::Error for ReadError {
+  fn source(&self) -> Option<&(error::Error + 'static)> {
+    match *self {
+      ReadError::Io(ref error) => Some(error),
+      ReadError::Parse(ref error) => Some(error),
+    }
+  }
+}
+
+impl<I> Parser<I> where I: Input {
   //////////////////////////////////////////////////////////////////////////////
   // Utilities
   //////////////////////////////////////////////////////////////////////////////
 
+  /// Creates a new parser which will stop at the first error.
+  fn new(lexer: Lexer<I>, include_location: bool) -> Self {
+    Parser {
+      lexer: lexer,
+      include_location: include_location,
+      errors: None,
+      consumed: 0,
+      expected: Vec::new(),
+      end: Position {
+        index: 0,
+        offset: 0,
+        line: 1,
+        column: 1,
+      },
+      source: None,
+      options: ParseOptions::default(),
+      depth: 0,
+      tokens: 0,
+      limit_error: None,
+    }
+  }
+
+  /// Sets the limits on the document we are parsing. The length is limited
+  /// by the lexer, which stops reading at the maximum length.
+  fn set_options(&mut self, options: &ParseOptions) {
+    self.options = options.clone();
+    self.lexer.limit_length(options.max_length);
+  }
+
   /// Gets the current position of our parser.
   fn pos(&self) -> Position {
     self.lexer.pos()
   }
 
-  /// Creates an optional location using starting and ending positions. The
-  /// location may change depending on the options object.
+  /// Gets the position at which the next node starts, which is the start of
+  /// the next token.
+  fn start(&mut self) -> Position {
+    match self.peek() {
+      Some(Ok(token)) => token.start,
+      _ => self.lexer.pos(),
+    }
+  }
+
+  /// Creates an optional location from a starting position to the end of the
+  /// last token we consumed. The location may change depending on the options
+  /// object.
   fn loc(&self, start: Position) -> Option<Location> {
     if self.include_location {
-      Some(Location::new(start, self.pos()))
+      Some(Location {
+        start: start,
+        end: self.end,
+        source: self.source.clone(),
+      })
     } else {
       None
     }
   }
 
+  /// Returns a reference to the next token without consuming it. Comments
+  /// have no meaning to the parser so they are skipped.
+  ///
+  /// If the next token exceeds a limit then the limit error is remembered and
+  /// we are at the ending.
+  fn peek(&mut self) -> Option<Result<&Token<I::Text>, &LexerError>> {
+    loop {
+      if self.limit_error.is_some() {
+        return None;
+      }
+      let (comment, limit) = match self.lexer.peek() {
+        Some(Ok(token)) => (
+          matches!(token.kind, TokenKind::Comment(_)),
+          match self.options.max_tokens {
+            Some(max_tokens) if self.tokens >= max_tokens => Some((token.start, Limit::Tokens(max_tokens))),
+            _ => None,
+          },
+        ),
+        _ => (false, None),
+      };
+      // The lexer stops reading at the maximum length, so the token it was
+      // lexing when it got there has been cut short.
+      let limit = limit.or_else(|| match (self.options.max_length, self.lexer.length_exceeded()) {
+        (Some(max_length), Some(position)) => Some((position, Limit::Length(max_length))),
+        _ => None,
+      });
+      if let Some((position, limit)) = limit {
+        self.limit_error = Some(Error::LimitExceeded(position, limit));
+        return None;
+      }
+      if !comment {
+        break;
+      }
+      self.lexer.next();
+      self.tokens += 1;
+    }
+    self.lexer.peek()
+  }
+
   /// Checks the next token kind without consuming it. Returns true if the
   /// token’s kind matches the provided kind, and returns false if the kind does
   /// not match or we are at the ending. If it encounters an error, false will
   /// be returned.
+  ///
+  /// Every failed check is remembered so that if parsing fails at this token
+  /// the error can say what was expected.
   fn check(&mut self, kind: &TokenKind) -> bool {
-    match self.lexer.peek() {
-      Some(Ok(&Token { kind: ref token_kind, .. })) => token_kind == kind,
+    let found = match self.peek() {
+      Some(Ok(&Token { kind: ref token_kind, .. })) => token_kind.matches(kind),
       _ => false,
+    };
+    if !found {
+      self.expected(Expected::Token(kind.clone()));
     }
+    found
   }
 
   /// Checks if the next token is a name token with the provided name string.
   /// With this method we won’t need to allocate an owned string and can use
   /// string references.
-  fn check_name(&mut self, name: &str) -> bool {
-    match self.lexer.peek() {
+  fn check_name(&mut self, name: &'static str) -> bool {
+    let found = match self.peek() {
       Some(result) => match result {
         Ok(ref token) => match token.kind {
-          TokenKind::Name(ref kind_name) => kind_name == name,
+          TokenKind::Name(ref kind_name) => kind_name.as_ref() == name,
           _ => false,
         },
         Err(_) => false,
       },
       None => false,
+    };
+    if !found {
+      self.expected(Expected::Keyword(name));
+    }
+    found
+  }
+
+  /// Returns true if the next token is a string which could be the
+  /// description of a type system definition.
+  #[cfg(feature = "type_system")]
+  fn check_description(&mut self) -> bool {
+    let found = matches!(
+      self.peek(),
+      Some(Ok(&Token { kind: TokenKind::String(_), .. })) |
+      Some(Ok(&Token { kind: TokenKind::BlockString(_), .. }))
+    );
+    if !found {
+      self.expected(Expected::Description);
+    }
+    found
+  }
+
+  /// Remembers that we expected something at the next token.
+  fn expected(&mut self, expected: Expected) {
+    if !self.expected.contains(&expected) {
+      self.expected.push(expected);
     }
   }
 
   /// Calls `next` on the lexer to consume the next token.
-  fn next(&mut self) -> Option<Result<Token, Error>> {
+  fn next(&mut self) -> Option<Result<Token<I::Text>, Error>> {
+    self.consumed += 1;
+    self.expected.clear();
+    if self.peek().is_none() {
+      return None;
+    }
     match self.lexer.next() {
-      Some(Ok(token)) => Some(Ok(token)),
+      Some(Ok(token)) => {
+        self.tokens += 1;
+        self.end = token.end;
+        Some(Ok(token))
+      },
       Some(Err(error)) => Some(Err(Error::Lexer(error))),
       None => None,
     }
@@ -116,7 +652,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   // Therefore `None` does not always mean the iterator has finished when
   // calling `next_if`! This method is useful with a pattern matched if
   // statement.
-  fn next_if(&mut self, kind: &TokenKind) -> Option<Result<Token, Error>> {
+  fn next_if(&mut self, kind: &TokenKind) -> Option<Result<Token<I::Text>, Error>> {
     if self.check(kind) {
       self.next()
     } else {
@@ -127,15 +663,15 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// If the next token is a name token, we consume the token and return the
   /// name string. If the next token is not a name token, then we do not consume
   /// the next token.
-  fn next_if_any_name(&mut self) -> Option<String> {
-    match self.lexer.peek() {
-      Some(Ok(ref token)) => match token.kind {
-        TokenKind::Name(_) => (),
-        _ => { return None },
+  fn next_if_any_name(&mut self) -> Option<I::Text> {
+    match self.peek() {
+      Some(Ok(&Token { kind: TokenKind::Name(_), .. })) => (),
+      _ => {
+        self.expected(Expected::Name);
+        return None;
       },
-      _ => { return None },
     };
-    match self.lexer.next() {
+    match self.next() {
       Some(Ok(token)) => match token.kind {
         TokenKind::Name(name) => Some(name),
         _ => None,
@@ -146,54 +682,73 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
 
   /// If the next token is a name token with the exact name provided then `next`
   // will be called. Otherwise `None` will be returned.
-  fn next_if_name(&mut self, name: &str) -> bool {
-    match self.lexer.peek() {
-      Some(Ok(ref token)) => match token.kind {
-        TokenKind::Name(ref name_ref) if name_ref == name => (),
-        _ => { return false },
-      },
-      _ => { return false },
-    };
-    match self.lexer.next() {
-      Some(Ok(token)) => match token.kind {
-        TokenKind::Name(_) => true,
-        _ => false,
-      },
-      _ => false,
+  fn next_if_name(&mut self, name: &'static str) -> bool {
+    if self.check_name(name) {
+      self.next();
+      true
+    } else {
+      false
     }
   }
 
   /// Generates an error that lets the user know we hit something unexpected in
-  /// the lexer by consuming the next token in the lexer.
+  /// the lexer by consuming the next token in the lexer. The error includes
+  /// everything we checked for at that token.
   fn unexpected(&mut self) -> Error {
+    let expected = self.expected.split_off(0);
     match self.next() {
-      Some(Ok(token)) => Error::UnexpectedToken(token),
+      Some(Ok(token)) => Error::UnexpectedToken(token.into_owned(), expected),
       Some(Err(error)) => error,
-      None => Error::UnexpectedEnding(self.pos()),
+      None => Error::UnexpectedEnding(self.pos(), expected),
     }
   }
 
   /// Asserts that the next token has the kind we expect. If the next token does
   /// not have that kind then we return an unexpected error.
   fn expect(&mut self, kind: &TokenKind) -> Result<(), Error> {
-    match self.next() {
-      Some(Ok(Token { kind: ref token_kind, .. })) if token_kind == kind => Ok(()),
-      Some(Ok(token)) => Err(Error::UnexpectedToken(token)),
-      Some(Err(error)) => Err(error),
-      None => Err(Error::UnexpectedEnding(self.pos())),
+    if self.check(kind) {
+      self.next();
+      Ok(())
+    } else {
+      Err(self.unexpected())
     }
   }
 
   /// Asserts that the next token is a name token with the name that we expect.
   /// If the next token does not have that name then we return an unexpected
   /// error.
-  fn expect_name(&mut self, name: &str) -> Result<(), Error> {
-    match self.next() {
-      Some(Ok(Token { kind: TokenKind::Name(ref token_name), .. })) if token_name == name => Ok(()),
-      Some(Ok(token)) => Err(Error::UnexpectedToken(token)),
-      Some(Err(error)) => Err(error),
-      None => Err(Error::UnexpectedEnding(self.pos())),
+  fn expect_name(&mut self, name: &'static str) -> Result<(), Error> {
+    if self.check_name(name) {
+      self.next();
+      Ok(())
+    } else {
+      Err(self.unexpected())
+    }
+  }
+
+  /// Asserts that there are no more tokens in the source document.
+  fn expect_end(&mut self) -> Result<(), Error> {
+    if self.peek().is_none() {
+      Ok(())
+    } else {
+      Err(self.unexpected())
+    }
+  }
+
+  /// Parses a node which is nested inside of the node we are parsing, like a
+  /// selection set or a list value. Fails instead if this would nest deeper
+  /// than the maximum depth.
+  fn nested<T, F>(&mut self, parse_fn: F) -> Result<T, Error> where F: FnOnce(&mut Parser<I>) -> Result<T, Error> {
+    if let Some(max_depth) = self.options.max_depth {
+      if self.depth >= max_depth {
+        let start = self.start();
+        return Err(Error::LimitExceeded(start, Limit::Depth(max_depth)));
+      }
     }
+    self.depth += 1;
+    let result = parse_fn(self);
+    self.depth -= 1;
+    result
   }
 
   /// Parse a list of items between two tokens. If the last argument is true
@@ -211,13 +766,14 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     try!(self.expect(start));
 
     loop {
-      if let Some(token) = self.next_if(end) {
-        // If there were no items, and we are not allowing no items then we need
-        // to return an unexpected token error.
-        if !allow_none && items.len() == 0 {
-          return Err(Error::UnexpectedToken(try!(token)));
+      // If we are not allowing no items then we parse the first item without
+      // checking for the end token. That way an empty list fails in `parse_fn`
+      // which knows what an item should look like.
+      if allow_none || !items.is_empty() {
+        if let Some(token) = self.next_if(end) {
+          try!(token);
+          break;
         }
-        break;
       }
       items.push(try!(parse_fn(self)));
     }
@@ -230,8 +786,8 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   //////////////////////////////////////////////////////////////////////////////
 
   /// Converts a name token into a name parse node.
-  fn parse_name(&mut self) -> Result<ast::Name, Error> {
-    let start = self.pos();
+  fn parse_name(&mut self) -> Result<ast::Name<I::Text>, Error> {
+    let start = self.start();
 
     match self.next_if_any_name() {
       Some(name) => Ok(ast::Name {
@@ -249,17 +805,16 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// Document : Definition+
   /// ```
-  fn parse_document(&mut self) -> Result<ast::Document, Error> {
-    let start = self.pos();
+  fn parse_document(&mut self) -> Result<ast::Document<I::Text>, Error> {
+    let start = self.start();
     let mut definitions = Vec::new();
 
-    while self.lexer.peek() != None {
+    // There must be at least one definition.
+    loop {
       definitions.push(try!(self.parse_definition()));
-    }
-
-    // Return an error if we have no definitions.
-    if definitions.len() < 1 {
-      return Err(Error::UnexpectedEnding(self.pos()));
+      if self.peek().is_none() {
+        break;
+      }
     }
 
     Ok(ast::Document {
@@ -274,7 +829,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///   - FragmentDefinition
   ///   - TypeSystemDefinition
   /// ```
-  fn parse_definition(&mut self) -> Result<ast::Definition, Error> {
+  fn parse_definition(&mut self) -> Result<ast::Definition<I::Text>, Error> {
     if {
       self.check(&TokenKind::LeftBrace) ||
       self.check_name("query") ||
@@ -286,24 +841,26 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     else if self.check_name("fragment") {
       Ok(ast::Definition::Fragment(try!(self.parse_fragment_definition())))
     }
-    else if {
-      self.check_name("schema") ||
-      self.check_name("scalar") ||
-      self.check_name("type") ||
-      self.check_name("interface") ||
-      self.check_name("union") ||
-      self.check_name("enum") ||
-      self.check_name("input") ||
-      self.check_name("extend") ||
-      self.check_name("directive")
-    } {
-      #[cfg(feature = "type_system")]
-      return Ok(ast::Definition::TypeSystem(try!(self.parse_type_system_definition())));
-
-      #[cfg(not(feature = "type_system"))]
-      return Err(self.unexpected());
-    }
     else {
+      // The type system AST extension is an experimental non-spec addition.
+      // Without it we don’t expect any of its keywords.
+      #[cfg(feature = "type_system")]
+      {
+        if {
+          self.check_description() ||
+          self.check_name("schema") ||
+          self.check_name("scalar") ||
+          self.check_name("type") ||
+          self.check_name("interface") ||
+          self.check_name("union") ||
+          self.check_name("enum") ||
+          self.check_name("input") ||
+          self.check_name("extend") ||
+          self.check_name("directive")
+        } {
+          return Ok(ast::Definition::TypeSystem(try!(self.parse_type_system_definition())));
+        }
+      }
       Err(self.unexpected())
     }
   }
@@ -313,17 +870,17 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///   - SelectionSet
   ///   - OperationType Name? VariableDefinitions? Directives? SelectionSet
   /// ```
-  fn parse_operation_definition(&mut self) -> Result<ast::OperationDefinition, Error> {
-    let start = self.pos();
+  fn parse_operation_definition(&mut self) -> Result<ast::OperationDefinition<I::Text>, Error> {
+    let start = self.start();
     let mut operation = ast::OperationType::Query;
-    let mut name: Option<ast::Name> = None;
-    let mut variable_definitions: Vec<ast::VariableDefinition> = vec![];
-    let mut directives: Vec<ast::Directive> = vec![];
+    let mut name: Option<ast::Name<I::Text>> = None;
+    let mut variable_definitions: Vec<ast::VariableDefinition<I::Text>> = vec![];
+    let mut directives: Vec<ast::Directive<I::Text>> = vec![];
 
     if !self.check(&TokenKind::LeftBrace) {
       operation = try!(self.parse_operation_type());
       {
-        let start = self.pos();
+        let start = self.start();
         if let Some(name_string) = self.next_if_any_name() {
           name = Some(ast::Name {
             loc: self.loc(start),
@@ -351,14 +908,10 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// OperationType : one of query mutation subscription
   /// ```
   fn parse_operation_type(&mut self) -> Result<ast::OperationType, Error> {
-    match self.next_if_any_name().as_ref().map(|x| &**x) {
+    match self.next_if_any_name().as_ref().map(AsRef::as_ref) {
       Some("query") => Ok(ast::OperationType::Query),
       Some("mutation") => Ok(ast::OperationType::Mutation),
-
-      // Subscriptions are an expiremental non-spec addition.
-      #[cfg(feature = "subscriptions")]
       Some("subscription") => Ok(ast::OperationType::Subscription),
-
       _ => Err(self.unexpected()),
     }
   }
@@ -366,7 +919,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// VariableDefinitions : ( VariableDefinition+ )
   /// ```
-  fn parse_variable_definitions(&mut self) -> Result<Vec<ast::VariableDefinition>, Error> {
+  fn parse_variable_definitions(&mut self) -> Result<Vec<ast::VariableDefinition<I::Text>>, Error> {
     self.many(
       &TokenKind::LeftParen,
       Parser::parse_variable_definition,
@@ -376,33 +929,35 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// VariableDefinition : Variable : Type DefaultValue?
+  /// VariableDefinition : Variable : Type DefaultValue? Directives?
   /// ```
-  fn parse_variable_definition(&mut self) -> Result<ast::VariableDefinition, Error> {
-    let start = self.pos();
+  fn parse_variable_definition(&mut self) -> Result<ast::VariableDefinition<I::Text>, Error> {
+    let start = self.start();
     let variable = try!(self.parse_variable());
     try!(self.expect(&TokenKind::Colon));
     let type_ = try!(self.parse_type());
     let default_value = {
-      if let Some(_) = self.next_if(&TokenKind::Equals) {
+      if self.next_if(&TokenKind::Equals).is_some() {
         Some(try!(self.parse_value()))
       } else {
         None
       }
     };
+    let directives = try!(self.parse_directives());
     Ok(ast::VariableDefinition {
       loc: self.loc(start),
       variable: variable,
       type_: type_,
       default_value: default_value,
+      directives: directives,
     })
   }
 
   /// ```txt
   /// Variable : $ Name
   /// ```
-  fn parse_variable(&mut self) -> Result<ast::Variable, Error> {
-    let start = self.pos();
+  fn parse_variable(&mut self) -> Result<ast::Variable<I::Text>, Error> {
+    let start = self.start();
     try!(self.expect(&TokenKind::Dollar));
     let name = try!(self.parse_name());
     Ok(ast::Variable {
@@ -414,14 +969,18 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// SelectionSet : { Selection+ }
   /// ```
-  fn parse_selection_set(&mut self) -> Result<ast::SelectionSet, Error> {
-    let start = self.pos();
-    let selections = try!(self.many(
-      &TokenKind::LeftBrace,
-      Parser::parse_selection,
-      &TokenKind::RightBrace,
-      false,
-    ));
+  fn parse_selection_set(&mut self) -> Result<ast::SelectionSet<I::Text>, Error> {
+    let start = self.start();
+    let selections = if self.errors.is_some() {
+      try!(self.nested(Parser::parse_selections_with_recovery))
+    } else {
+      try!(self.nested(|parser| parser.many(
+        &TokenKind::LeftBrace,
+        Parser::parse_selection,
+        &TokenKind::RightBrace,
+        false,
+      )))
+    };
     Ok(ast::SelectionSet {
       loc: self.loc(start),
       selections: selections,
@@ -434,7 +993,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///   - FragmentSpread
   ///   - InlineFragment
   /// ```
-  fn parse_selection(&mut self) -> Result<ast::Selection, Error> {
+  fn parse_selection(&mut self) -> Result<ast::Selection<I::Text>, Error> {
     if self.check(&TokenKind::Ellipsis) {
       self.parse_fragment()
     } else {
@@ -447,13 +1006,13 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///
   /// Alias : Name :
   /// ```
-  fn parse_field(&mut self) -> Result<ast::Field, Error> {
-    let start = self.pos();
+  fn parse_field(&mut self) -> Result<ast::Field<I::Text>, Error> {
+    let start = self.start();
 
-    let mut alias: Option<ast::Name> = None;
+    let mut alias: Option<ast::Name<I::Text>> = None;
     let mut name = try!(self.parse_name());
 
-    if let Some(_) = self.next_if(&TokenKind::Colon) {
+    if self.next_if(&TokenKind::Colon).is_some() {
       alias = Some(name);
       name = try!(self.parse_name());
     }
@@ -482,7 +1041,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// Arguments : ( Argument + )
   /// ```
-  fn parse_arguments(&mut self) -> Result<Vec<ast::Argument>, Error> {
+  fn parse_arguments(&mut self) -> Result<Vec<ast::Argument<I::Text>>, Error> {
     Ok(try!(self.many(
       &TokenKind::LeftParen,
       Parser::parse_argument,
@@ -494,8 +1053,8 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// Argument : Name : Value
   /// ```
-  fn parse_argument(&mut self) -> Result<ast::Argument, Error> {
-    let start = self.pos();
+  fn parse_argument(&mut self) -> Result<ast::Argument<I::Text>, Error> {
+    let start = self.start();
     let name = try!(self.parse_name());
     try!(self.expect(&TokenKind::Colon));
     let value = try!(self.parse_value());
@@ -513,16 +1072,19 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// Parses both `FragmentSpread` and `InlineFragment`.
   ///
   /// ```txt
-  /// FragmentSpread : ... FragmentName Directives?
+  /// FragmentSpread : ... FragmentName Arguments? Directives?
   /// ```
   ///
+  /// Fragment spread arguments are only parsed with the `fragment_variables`
+  /// feature.
+  ///
   /// ```txt
   /// InlineFragment : ... TypeCondition? Directives? SelectionSet
   /// ```
-  fn parse_fragment(&mut self) -> Result<ast::Selection, Error> {
-    let start = self.pos();
+  fn parse_fragment(&mut self) -> Result<ast::Selection<I::Text>, Error> {
+    let start = self.start();
     try!(self.expect(&TokenKind::Ellipsis));
-    let start_after_ellipsis = self.pos();
+    let start_after_ellipsis = self.start();
 
     // If there is a name to eat then this is a fragment spread.
     if !self.check_name("on") {
@@ -531,16 +1093,21 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
           loc: self.loc(start_after_ellipsis),
           value: name_value,
         };
+        // Fragment arguments are an experimental non-spec addition.
+        #[cfg(feature = "fragment_variables")]
+        let arguments = if self.check(&TokenKind::LeftParen) { try!(self.parse_arguments()) } else { vec![] };
         let directives = try!(self.parse_directives());
         return Ok(ast::Selection::FragmentSpread(ast::FragmentSpread {
           loc: self.loc(start),
           name: name,
+          #[cfg(feature = "fragment_variables")]
+          arguments: arguments,
           directives: directives,
         }));
       }
     }
 
-    let mut type_condition: Option<ast::NamedType> = None;
+    let mut type_condition: Option<ast::NamedType<I::Text>> = None;
 
     // If we see `on` then we know there will be a type condition and so we parse
     // a named type.
@@ -561,14 +1128,20 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
 
   /// ```txt
   /// FragmentDefinition :
-  ///  - fragment FragmentName on TypeCondition Directives? SelectionSet
+  ///  - fragment FragmentName VariableDefinitions? on TypeCondition Directives? SelectionSet
   ///
   /// TypeCondition : NamedType
   /// ```
-  fn parse_fragment_definition(&mut self) -> Result<ast::FragmentDefinition, Error> {
-    let start = self.pos();
+  ///
+  /// Fragment variable definitions are only parsed with the
+  /// `fragment_variables` feature.
+  fn parse_fragment_definition(&mut self) -> Result<ast::FragmentDefinition<I::Text>, Error> {
+    let start = self.start();
     try!(self.expect_name("fragment"));
     let name = try!(self.parse_fragment_name());
+    // Fragment variables are an experimental non-spec addition.
+    #[cfg(feature = "fragment_variables")]
+    let variable_definitions = if self.check(&TokenKind::LeftParen) { try!(self.parse_variable_definitions()) } else { vec![] };
     try!(self.expect_name("on"));
     let type_condition = try!(self.parse_named_type());
     let directives = try!(self.parse_directives());
@@ -576,6 +1149,8 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     Ok(ast::FragmentDefinition {
       loc: self.loc(start),
       name: name,
+      #[cfg(feature = "fragment_variables")]
+      variable_definitions: variable_definitions,
       type_condition: type_condition,
       directives: directives,
       selection_set: selection_set,
@@ -585,12 +1160,13 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// FragmentName : Name but not `on`
   /// ```
-  fn parse_fragment_name(&mut self) -> Result<ast::Name, Error> {
-    if self.check_name("ok") {
-      Err(self.unexpected())
-    } else {
-      self.parse_name()
+  fn parse_fragment_name(&mut self) -> Result<ast::Name<I::Text>, Error> {
+    match self.peek() {
+      Some(Ok(&Token { kind: TokenKind::Name(ref name), .. })) if name.as_ref() == "on" => (),
+      _ => return self.parse_name(),
     }
+    self.expected(Expected::Name);
+    Err(self.unexpected())
   }
 
   //////////////////////////////////////////////////////////////////////////////
@@ -615,14 +1191,26 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///
   /// EnumValue : Name but not `true`, `false` or `null`
   /// ```
-  fn parse_value(&mut self) -> Result<ast::Value, Error> {
-    let start = self.pos();
+  fn parse_value(&mut self) -> Result<ast::Value<I::Text>, Error> {
+    self.parse_value_literal(false)
+  }
+
+  fn parse_const_value(&mut self) -> Result<ast::Value<I::Text>, Error> {
+    self.parse_value_literal(true)
+  }
+
+  fn parse_value_literal(&mut self, const_: bool) -> Result<ast::Value<I::Text>, Error> {
+    let start = self.start();
+    // Everything expected before we started looking for a value. The checks
+    // for specific kinds of values below are summarized as `Expected::Value`.
+    let mut expected = self.expected.clone();
+    expected.push(Expected::Value);
 
     if self.check(&TokenKind::LeftBracket) {
-      Ok(ast::Value::List(try!(self.parse_list_value())))
+      Ok(ast::Value::List(try!(self.nested(|parser| parser.parse_list_value(const_)))))
     }
     else if self.check(&TokenKind::LeftBrace) {
-      Ok(ast::Value::Object(try!(self.parse_object_value())))
+      Ok(ast::Value::Object(try!(self.nested(|parser| parser.parse_object_value(const_)))))
     }
     else if self.check_name("null") {
       self.next();
@@ -644,7 +1232,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
         value: false,
       }))
     }
-    else if self.check(&TokenKind::Dollar) {
+    else if !const_ && self.check(&TokenKind::Dollar) {
       Ok(ast::Value::Variable(try!(self.parse_variable())))
     }
     else {
@@ -661,15 +1249,21 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
           TokenKind::String(string) => Ok(ast::Value::String(ast::StringValue {
             loc: self.loc(start),
             value: string,
+            block: false,
+          })),
+          TokenKind::BlockString(string) => Ok(ast::Value::String(ast::StringValue {
+            loc: self.loc(start),
+            value: string,
+            block: true,
           })),
           TokenKind::Name(name) => Ok(ast::Value::Enum(ast::EnumValue {
             loc: self.loc(start),
             value: name,
           })),
-          _ => Err(Error::UnexpectedToken(token)),
+          _ => Err(Error::UnexpectedToken(token.into_owned(), expected)),
         },
         Some(Err(error)) => Err(error),
-        None => Err(Error::UnexpectedEnding(self.pos())),
+        None => Err(Error::UnexpectedEnding(self.pos(), expected)),
       }
     }
   }
@@ -679,11 +1273,11 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///  - [ ]
   ///  - [ Value[?Const]+ ]
   /// ```
-  fn parse_list_value(&mut self) -> Result<ast::ListValue, Error> {
-    let start = self.pos();
+  fn parse_list_value(&mut self, const_: bool) -> Result<ast::ListValue<I::Text>, Error> {
+    let start = self.start();
     let values = try!(self.many(
       &TokenKind::LeftBracket,
-      Parser::parse_value,
+      if const_ { Parser::parse_const_value } else { Parser::parse_value },
       &TokenKind::RightBracket,
       true,
     ));
@@ -698,11 +1292,11 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///  - { }
   ///  - { ObjectField[?Const]+ }
   /// ```
-  fn parse_object_value(&mut self) -> Result<ast::ObjectValue, Error> {
-    let start = self.pos();
+  fn parse_object_value(&mut self, const_: bool) -> Result<ast::ObjectValue<I::Text>, Error> {
+    let start = self.start();
     let fields = try!(self.many(
       &TokenKind::LeftBrace,
-      Parser::parse_object_field,
+      if const_ { Parser::parse_const_object_field } else { Parser::parse_object_field },
       &TokenKind::RightBrace,
       true,
     ));
@@ -715,11 +1309,19 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// ObjectField[Const] : Name : Value[?Const]
   /// ```
-  fn parse_object_field(&mut self) -> Result<ast::ObjectField, Error> {
-    let start = self.pos();
+  fn parse_object_field(&mut self) -> Result<ast::ObjectField<I::Text>, Error> {
+    self.parse_object_field_literal(false)
+  }
+
+  fn parse_const_object_field(&mut self) -> Result<ast::ObjectField<I::Text>, Error> {
+    self.parse_object_field_literal(true)
+  }
+
+  fn parse_object_field_literal(&mut self, const_: bool) -> Result<ast::ObjectField<I::Text>, Error> {
+    let start = self.start();
     let name = try!(self.parse_name());
     try!(self.expect(&TokenKind::Colon));
-    let value = try!(self.parse_value());
+    let value = try!(self.parse_value_literal(const_));
     Ok(ast::ObjectField {
       loc: self.loc(start),
       name: name,
@@ -738,8 +1340,8 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// Directives : Directive*
   /// ```
-  fn parse_directives(&mut self) -> Result<Vec<ast::Directive>, Error> {
-    let mut directives: Vec<ast::Directive> = vec![];
+  fn parse_directives(&mut self) -> Result<Vec<ast::Directive<I::Text>>, Error> {
+    let mut directives: Vec<ast::Directive<I::Text>> = vec![];
     loop {
       if !self.check(&TokenKind::At) {
         break;
@@ -752,8 +1354,8 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// Directive : @ Name Arguments?
   /// ```
-  fn parse_directive(&mut self) -> Result<ast::Directive, Error> {
-    let start = self.pos();
+  fn parse_directive(&mut self) -> Result<ast::Directive<I::Text>, Error> {
+    let start = self.start();
     try!(self.expect(&TokenKind::At));
     let name = try!(self.parse_name());
     let arguments = if self.check(&TokenKind::LeftParen) { try!(self.parse_arguments()) } else { vec![] };
@@ -774,14 +1376,18 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///   - ListType
   ///   - NonNullType
   /// ```
-  fn parse_type(&mut self) -> Result<ast::Type, Error> {
-    let start = self.pos();
+  fn parse_type(&mut self) -> Result<ast::Type<I::Text>, Error> {
+    let start = self.start();
 
     let nullable_type = {
       // If we hit a left bracket, this is likely an array type.
-      if let Some(_) = self.next_if(&TokenKind::LeftBracket) {
-        let type_ = try!(self.parse_type());
-        try!(self.expect(&TokenKind::RightBracket));
+      if self.check(&TokenKind::LeftBracket) {
+        let type_ = try!(self.nested(|parser| {
+          parser.next();
+          let type_ = try!(parser.parse_type());
+          try!(parser.expect(&TokenKind::RightBracket));
+          Ok(type_)
+        }));
         ast::NullableType::List(ast::ListType {
           loc: self.loc(start),
           type_: Box::new(type_),
@@ -794,7 +1400,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     };
 
     // If we have a bang at the end, this is a non-null type.
-    if let Some(_) = self.next_if(&TokenKind::Bang) {
+    if self.next_if(&TokenKind::Bang).is_some() {
       Ok(ast::Type::NonNull(ast::NonNullType {
         loc: self.loc(start),
         type_: Box::new(nullable_type),
@@ -809,65 +1415,213 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// NamedType : Name
   /// ```
-  fn parse_named_type(&mut self) -> Result<ast::NamedType, Error> {
-    let start = self.pos();
+  fn parse_named_type(&mut self) -> Result<ast::NamedType<I::Text>, Error> {
+    let start = self.start();
     let name = try!(self.parse_name());
     Ok(ast::NamedType {
       loc: self.loc(start),
       name: name,
     })
   }
-}
 
-////////////////////////////////////////////////////////////////////////////////
-// Type System Definition
-////////////////////////////////////////////////////////////////////////////////
-//
-// The type system AST extension is an experimental non-spec addition.
-#[cfg(feature = "type_system")]
-impl<I> Parser<I> where I: Iterator<Item=char> {
-  /// ```txt
-  /// TypeSystemDefinition :
-  ///   - SchemaDefinition
-  ///   - TypeDefinition
-  ///   - TypeExtensionDefinition
-  ///   - DirectiveDefinition
-  ///
-  /// TypeDefinition :
-  ///   - ScalarTypeDefinition
-  ///   - ObjectTypeDefinition
-  ///   - InterfaceTypeDefinition
-  ///   - UnionTypeDefinition
-  ///   - EnumTypeDefinition
-  ///   - InputObjectTypeDefinition
-  /// ```
-  fn parse_type_system_definition(&mut self) -> Result<ast::TypeSystemDefinition, Error> {
-    if self.check_name("schema") {
-      Ok(ast::TypeSystemDefinition::Schema(try!(self.parse_schema_definition())))
-    }
-    else if self.check_name("scalar") {
-      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Scalar(try!(self.parse_scalar_type_definition()))))
-    }
-    else if self.check_name("type") {
-      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Object(try!(self.parse_object_type_definition()))))
-    }
-    else if self.check_name("interface") {
-      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Interface(try!(self.parse_interface_type_definition()))))
-    }
-    else if self.check_name("union") {
-      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Union(try!(self.parse_union_type_definition()))))
+  //////////////////////////////////////////////////////////////////////////////
+  // Recovery
+  //////////////////////////////////////////////////////////////////////////////
+
+  /// Remembers an error we are recovering from.
+  fn recover(&mut self, error: Error) {
+    if let Some(ref mut errors) = self.errors {
+      errors.push(error);
     }
-    else if self.check_name("enum") {
-      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Enum(try!(self.parse_enum_type_definition()))))
+  }
+
+  /// Like `parse_document`, but will recover from errors in a definition by
+  /// skipping to the next definition. Always returns a document even if it
+  /// has no definitions.
+  fn parse_document_with_recovery(&mut self) -> ast::Document<I::Text> {
+    let start = self.start();
+    let mut definitions = Vec::new();
+
+    while self.peek().is_some() {
+      let consumed = self.consumed;
+      match self.parse_definition() {
+        Ok(definition) => definitions.push(definition),
+        Err(error) => {
+          self.recover(error);
+          // Always skip at least one token so that we make progress.
+          if self.consumed == consumed {
+            self.next();
+          }
+          self.skip_to_definition();
+        },
+      }
     }
-    else if self.check_name("input") {
-      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::InputObject(try!(self.parse_input_object_type_definition()))))
+
+    // Report an error if we have no definitions, but only if we do not
+    // already have a better error.
+    if definitions.is_empty() && self.errors.as_ref().map_or(true, Vec::is_empty) {
+      if let Err(error) = self.parse_definition() {
+        self.recover(error);
+      }
     }
-    else if self.check_name("extend") {
-      Ok(ast::TypeSystemDefinition::TypeExtension(try!(self.parse_type_extension_definition())))
+
+    ast::Document {
+      loc: self.loc(start),
+      definitions: definitions,
+    }
+  }
+
+  /// Like `many` for the selections in a selection set, but will recover from
+  /// errors in a selection by skipping to the next selection. Only fails if
+  /// the selection set does not start with a brace.
+  fn parse_selections_with_recovery(&mut self) -> Result<Vec<ast::Selection<I::Text>>, Error> {
+    let mut selections = Vec::new();
+    // Whether we recovered from an error in one of the selections. If we did
+    // then the selection set is only empty because of that error.
+    let mut recovered = false;
+
+    try!(self.expect(&TokenKind::LeftBrace));
+
+    loop {
+      if self.peek().is_none() {
+        self.expected(Expected::Token(TokenKind::RightBrace));
+        let error = self.unexpected();
+        self.recover(error);
+        break;
+      }
+      if let Some(token) = self.next_if(&TokenKind::RightBrace) {
+        if selections.is_empty() && !recovered {
+          // What `parse_selection` would have expected at this token.
+          let expected = vec![Expected::Token(TokenKind::Ellipsis), Expected::Name];
+          let error = Error::UnexpectedToken(try!(token).into_owned(), expected);
+          self.recover(error);
+        }
+        break;
+      }
+      let consumed = self.consumed;
+      match self.parse_selection() {
+        Ok(selection) => selections.push(selection),
+        Err(error) => {
+          self.recover(error);
+          recovered = true;
+          // Always skip at least one token so that we make progress.
+          if self.consumed == consumed {
+            self.next();
+          }
+          self.skip_to_selection();
+        },
+      }
+    }
+
+    Ok(selections)
+  }
+
+  /// Skips tokens until the next token could start a selection, or is the
+  /// brace which ends the current selection set.
+  fn skip_to_selection(&mut self) {
+    let mut depth: usize = 0;
+    loop {
+      match self.peek() {
+        None => return,
+        Some(Ok(&Token { kind: TokenKind::Name(_), .. })) |
+        Some(Ok(&Token { kind: TokenKind::Ellipsis, .. })) |
+        Some(Ok(&Token { kind: TokenKind::RightBrace, .. })) if depth == 0 => return,
+        _ => (),
+      }
+      match self.next() {
+        Some(Ok(Token { kind: TokenKind::LeftBrace, .. })) => depth += 1,
+        Some(Ok(Token { kind: TokenKind::RightBrace, .. })) => depth -= 1,
+        _ => (),
+      }
+    }
+  }
+
+  /// Skips tokens until the next token could start a definition.
+  fn skip_to_definition(&mut self) {
+    loop {
+      match self.peek() {
+        None |
+        Some(Ok(&Token { kind: TokenKind::LeftBrace, .. })) => return,
+        Some(Ok(&Token { kind: TokenKind::Name(ref name), .. })) => match name.as_ref() {
+          "query" |
+          "mutation" |
+          "subscription" |
+          "fragment" => return,
+          #[cfg(feature = "type_system")]
+          "schema" |
+          "scalar" |
+          "type" |
+          "interface" |
+          "union" |
+          "enum" |
+          "input" |
+          "extend" |
+          "directive" => return,
+          _ => (),
+        },
+        _ => (),
+      }
+      self.next();
+    }
+  }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Type System Definition
+////////////////////////////////////////////////////////////////////////////////
+//
+// The type system AST extension is an experimental non-spec addition.
+#[cfg(feature = "type_system")]
+impl<I> Parser<I> where I: Input {
+  /// Because the description comes before the keyword that tells us what
+  /// kind of definition we are parsing, the description is parsed here and
+  /// passed along to the function for the specific definition kind.
+  ///
+  /// ```txt
+  /// TypeSystemDefinition :
+  ///   - SchemaDefinition
+  ///   - TypeDefinition
+  ///   - TypeSystemExtension
+  ///   - DirectiveDefinition
+  ///
+  /// TypeDefinition :
+  ///   - ScalarTypeDefinition
+  ///   - ObjectTypeDefinition
+  ///   - InterfaceTypeDefinition
+  ///   - UnionTypeDefinition
+  ///   - EnumTypeDefinition
+  ///   - InputObjectTypeDefinition
+  /// ```
+  fn parse_type_system_definition(&mut self) -> Result<ast::TypeSystemDefinition<I::Text>, Error> {
+    let start = self.start();
+    let description = try!(self.parse_description());
+
+    if self.check_name("schema") {
+      Ok(ast::TypeSystemDefinition::Schema(try!(self.parse_schema_definition(start, description))))
+    }
+    else if self.check_name("scalar") {
+      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Scalar(try!(self.parse_scalar_type_definition(start, description)))))
+    }
+    else if self.check_name("type") {
+      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Object(try!(self.parse_object_type_definition(start, description)))))
+    }
+    else if self.check_name("interface") {
+      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Interface(try!(self.parse_interface_type_definition(start, description)))))
+    }
+    else if self.check_name("union") {
+      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Union(try!(self.parse_union_type_definition(start, description)))))
+    }
+    else if self.check_name("enum") {
+      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Enum(try!(self.parse_enum_type_definition(start, description)))))
+    }
+    else if self.check_name("input") {
+      Ok(ast::TypeSystemDefinition::Type(ast::TypeDefinition::InputObject(try!(self.parse_input_object_type_definition(start, description)))))
+    }
+    else if description.is_none() && self.check_name("extend") {
+      self.parse_type_system_extension()
     }
     else if self.check_name("directive") {
-      Ok(ast::TypeSystemDefinition::Directive(try!(self.parse_directive_definition())))
+      Ok(ast::TypeSystemDefinition::Directive(try!(self.parse_directive_definition(start, description))))
     }
     else {
       Err(self.unexpected())
@@ -875,10 +1629,36 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// SchemaDefinition : schema Directives? { OperationTypeDefinition+ }
+  /// Description : StringValue
+  /// ```
+  fn parse_description(&mut self) -> Result<Option<ast::StringValue<I::Text>>, Error> {
+    if !self.check_description() {
+      return Ok(None);
+    }
+    let start = self.start();
+    match self.next() {
+      Some(Ok(token)) => match token.kind {
+        TokenKind::String(string) => Ok(Some(ast::StringValue {
+          loc: self.loc(start),
+          value: string,
+          block: false,
+        })),
+        TokenKind::BlockString(string) => Ok(Some(ast::StringValue {
+          loc: self.loc(start),
+          value: string,
+          block: true,
+        })),
+        _ => Err(Error::Unreachable),
+      },
+      Some(Err(error)) => Err(error),
+      None => Err(Error::Unreachable),
+    }
+  }
+
+  /// ```txt
+  /// SchemaDefinition : Description? schema Directives? { OperationTypeDefinition+ }
   /// ```
-  fn parse_schema_definition(&mut self) -> Result<ast::SchemaDefinition, Error> {
-    let start = self.pos();
+  fn parse_schema_definition(&mut self, start: Position, description: Option<ast::StringValue<I::Text>>) -> Result<ast::SchemaDefinition<I::Text>, Error> {
     try!(self.expect_name("schema"));
     let directives = try!(self.parse_directives());
     let operation_types = try!(self.many(
@@ -889,6 +1669,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     ));
     Ok(ast::SchemaDefinition {
       loc: self.loc(start),
+      description: description,
       directives: directives,
       operation_types: operation_types,
     })
@@ -897,8 +1678,8 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// OperationTypeDefinition : OperationType : NamedType
   /// ```
-  fn parse_operation_type_definition(&mut self) -> Result<ast::OperationTypeDefinition, Error> {
-    let start = self.pos();
+  fn parse_operation_type_definition(&mut self) -> Result<ast::OperationTypeDefinition<I::Text>, Error> {
+    let start = self.start();
     let operation = try!(self.parse_operation_type());
     try!(self.expect(&TokenKind::Colon));
     let typ = try!(self.parse_named_type());
@@ -910,15 +1691,15 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// ScalarTypeDefinition : scalar Name Directives?
+  /// ScalarTypeDefinition : Description? scalar Name Directives?
   /// ```
-  fn parse_scalar_type_definition(&mut self) -> Result<ast::ScalarTypeDefinition, Error> {
-    let start = self.pos();
+  fn parse_scalar_type_definition(&mut self, start: Position, description: Option<ast::StringValue<I::Text>>) -> Result<ast::ScalarTypeDefinition<I::Text>, Error> {
     try!(self.expect_name("scalar"));
     let name = try!(self.parse_name());
     let directives = try!(self.parse_directives());
     Ok(ast::ScalarTypeDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       directives: directives,
     })
@@ -926,13 +1707,12 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
 
   /// ```txt
   /// ObjectTypeDefinition :
-  ///   - type Name ImplementsInterfaces? Directives? { FieldDefinition+ }
+  ///   - Description? type Name ImplementsInterfaces? Directives? { FieldDefinition+ }
   /// ```
-  fn parse_object_type_definition(&mut self) -> Result<ast::ObjectTypeDefinition, Error> {
-    let start = self.pos();
+  fn parse_object_type_definition(&mut self, start: Position, description: Option<ast::StringValue<I::Text>>) -> Result<ast::ObjectTypeDefinition<I::Text>, Error> {
     try!(self.expect_name("type"));
     let name = try!(self.parse_name());
-    let interfaces = if self.check_name("implements") { try!(self.parse_implements_interfaces()) } else { vec![] };
+    let interfaces = if self.check_name("implements") { try!(self.parse_implements_interfaces(true)) } else { vec![] };
     let directives = try!(self.parse_directives());
     let fields = try!(self.many(
       &TokenKind::LeftBrace,
@@ -942,6 +1722,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     ));
     Ok(ast::ObjectTypeDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       interfaces: interfaces,
       directives: directives,
@@ -950,33 +1731,40 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// ImplementsInterfaces : implements NamedType+
+  /// ImplementsInterfaces :
+  ///   - ImplementsInterfaces & NamedType
+  ///   - implements &? NamedType
   /// ```
-  fn parse_implements_interfaces(&mut self) -> Result<Vec<ast::NamedType>, Error> {
+  ///
+  /// For compatibility with older schemas we also accept interfaces which
+  /// are only separated by whitespace or commas, as in `implements A, B`,
+  /// when `allow_legacy` is true. Extensions may end right after the list of
+  /// interfaces so for them the legacy form would be ambiguous.
+  fn parse_implements_interfaces(&mut self, allow_legacy: bool) -> Result<Vec<ast::NamedType<I::Text>>, Error> {
     try!(self.expect_name("implements"));
-    let mut types: Vec<ast::NamedType> = vec![];
+    // The leading ampersand is optional.
+    self.next_if(&TokenKind::Amp);
+    let mut types: Vec<ast::NamedType<I::Text>> = vec![];
     loop {
-      let start = self.pos();
-      if let Some(name) = self.next_if_any_name() {
-        types.push(ast::NamedType {
-          loc: self.loc(start),
-          name: ast::Name {
-            loc: self.loc(start),
-            value: name,
-          },
-        });
-      } else {
-        break;
+      types.push(try!(self.parse_named_type()));
+      // If the next token is neither an ampersand nor a legacy separated name
+      // then break out of the loop.
+      if self.next_if(&TokenKind::Amp).is_none() {
+        match self.peek() {
+          Some(Ok(&Token { kind: TokenKind::Name(_), .. })) if allow_legacy => (),
+          _ => break,
+        }
       }
     }
     Ok(types)
   }
 
   /// ```txt
-  /// FieldDefinition : Name ArgumentsDefinition? : Type Directives?
+  /// FieldDefinition : Description? Name ArgumentsDefinition? : Type Directives?
   /// ```
-  fn parse_field_definition(&mut self) -> Result<ast::FieldDefinition, Error> {
-    let start = self.pos();
+  fn parse_field_definition(&mut self) -> Result<ast::FieldDefinition<I::Text>, Error> {
+    let start = self.start();
+    let description = try!(self.parse_description());
     let name = try!(self.parse_name());
     let arguments = if self.check(&TokenKind::LeftParen) { try!(self.parse_argument_definitions()) } else { vec![] };
     try!(self.expect(&TokenKind::Colon));
@@ -984,6 +1772,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     let directives = try!(self.parse_directives());
     Ok(ast::FieldDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       arguments: arguments,
       typ: typ,
@@ -994,7 +1783,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   /// ```txt
   /// ArgumentsDefinition : ( InputValueDefinition+ )
   /// ```
-  fn parse_argument_definitions(&mut self) -> Result<Vec<ast::InputValueDefinition>, Error> {
+  fn parse_argument_definitions(&mut self) -> Result<Vec<ast::InputValueDefinition<I::Text>>, Error> {
     self.many(
       &TokenKind::LeftParen,
       Parser::parse_input_value_definition,
@@ -1004,15 +1793,16 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// InputValueDefinition : Name : Type DefaultValue? Directives?
+  /// InputValueDefinition : Description? Name : Type DefaultValue? Directives?
   /// ```
-  fn parse_input_value_definition(&mut self) -> Result<ast::InputValueDefinition, Error> {
-    let start = self.pos();
+  fn parse_input_value_definition(&mut self) -> Result<ast::InputValueDefinition<I::Text>, Error> {
+    let start = self.start();
+    let description = try!(self.parse_description());
     let name = try!(self.parse_name());
     try!(self.expect(&TokenKind::Colon));
     let typ = try!(self.parse_type());
     let default_value = {
-      if let Some(_) = self.next_if(&TokenKind::Equals) {
+      if self.next_if(&TokenKind::Equals).is_some() {
         Some(try!(self.parse_value()))
       } else {
         None
@@ -1021,6 +1811,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     let directives = try!(self.parse_directives());
     Ok(ast::InputValueDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       typ: typ,
       default_value: default_value,
@@ -1029,12 +1820,12 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// InterfaceTypeDefinition : interface Name Directives? { FieldDefinition+ }
+  /// InterfaceTypeDefinition : Description? interface Name ImplementsInterfaces? Directives? { FieldDefinition+ }
   /// ```
-  fn parse_interface_type_definition(&mut self) -> Result<ast::InterfaceTypeDefinition, Error> {
-    let start = self.pos();
+  fn parse_interface_type_definition(&mut self, start: Position, description: Option<ast::StringValue<I::Text>>) -> Result<ast::InterfaceTypeDefinition<I::Text>, Error> {
     try!(self.expect_name("interface"));
     let name = try!(self.parse_name());
+    let interfaces = if self.check_name("implements") { try!(self.parse_implements_interfaces(true)) } else { vec![] };
     let directives = try!(self.parse_directives());
     let fields = try!(self.many(
       &TokenKind::LeftBrace,
@@ -1044,17 +1835,18 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     ));
     Ok(ast::InterfaceTypeDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
+      interfaces: interfaces,
       directives: directives,
       fields: fields,
     })
   }
 
   /// ```txt
-  /// UnionTypeDefinition : union Name Directives? = UnionMembers
+  /// UnionTypeDefinition : Description? union Name Directives? = UnionMembers
   /// ```
-  fn parse_union_type_definition(&mut self) -> Result<ast::UnionTypeDefinition, Error> {
-    let start = self.pos();
+  fn parse_union_type_definition(&mut self, start: Position, description: Option<ast::StringValue<I::Text>>) -> Result<ast::UnionTypeDefinition<I::Text>, Error> {
     try!(self.expect_name("union"));
     let name = try!(self.parse_name());
     let directives = try!(self.parse_directives());
@@ -1062,6 +1854,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     let types = try!(self.parse_union_members());
     Ok(ast::UnionTypeDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       directives: directives,
       types: types,
@@ -1073,12 +1866,12 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   ///   - NamedType
   ///   - UnionMembers | NamedType
   /// ```
-  fn parse_union_members(&mut self) -> Result<Vec<ast::NamedType>, Error> {
-    let mut members: Vec<ast::NamedType> = vec![];
+  fn parse_union_members(&mut self) -> Result<Vec<ast::NamedType<I::Text>>, Error> {
+    let mut members: Vec<ast::NamedType<I::Text>> = vec![];
     loop {
       members.push(try!(self.parse_named_type()));
       // If the next character is not a pipe then break out of the loop.
-      if let None = self.next_if(&TokenKind::Pipe) {
+      if self.next_if(&TokenKind::Pipe).is_none() {
         break;
       }
     }
@@ -1086,10 +1879,9 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// EnumTypeDefinition : enum Name Directives? { EnumValueDefinition+ }
+  /// EnumTypeDefinition : Description? enum Name Directives? { EnumValueDefinition+ }
   /// ```
-  fn parse_enum_type_definition(&mut self) -> Result<ast::EnumTypeDefinition, Error> {
-    let start = self.pos();
+  fn parse_enum_type_definition(&mut self, start: Position, description: Option<ast::StringValue<I::Text>>) -> Result<ast::EnumTypeDefinition<I::Text>, Error> {
     try!(self.expect_name("enum"));
     let name = try!(self.parse_name());
     let directives = try!(self.parse_directives());
@@ -1101,6 +1893,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     ));
     Ok(ast::EnumTypeDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       directives: directives,
       values: values,
@@ -1108,26 +1901,27 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// EnumValueDefinition : EnumValue Directives?
+  /// EnumValueDefinition : Description? EnumValue Directives?
   ///
   /// EnumValue : Name
   /// ```
-  fn parse_enum_value_definition(&mut self) -> Result<ast::EnumValueDefinition, Error> {
-    let start = self.pos();
+  fn parse_enum_value_definition(&mut self) -> Result<ast::EnumValueDefinition<I::Text>, Error> {
+    let start = self.start();
+    let description = try!(self.parse_description());
     let name = try!(self.parse_name());
     let directives = try!(self.parse_directives());
     Ok(ast::EnumValueDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       directives: directives,
     })
   }
 
   /// ```txt
-  /// InputObjectTypeDefinition : input Name Directives? { InputValueDefinition+ }
+  /// InputObjectTypeDefinition : Description? input Name Directives? { InputValueDefinition+ }
   /// ```
-  fn parse_input_object_type_definition(&mut self) -> Result<ast::InputObjectTypeDefinition, Error> {
-    let start = self.pos();
+  fn parse_input_object_type_definition(&mut self, start: Position, description: Option<ast::StringValue<I::Text>>) -> Result<ast::InputObjectTypeDefinition<I::Text>, Error> {
     try!(self.expect_name("input"));
     let name = try!(self.parse_name());
     let directives = try!(self.parse_directives());
@@ -1139,6 +1933,7 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
     ));
     Ok(ast::InputObjectTypeDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       directives: directives,
       fields: fields,
@@ -1146,59 +1941,305 @@ impl<I> Parser<I> where I: Iterator<Item=char> {
   }
 
   /// ```txt
-  /// TypeExtensionDefinition : extend ObjectTypeDefinition
+  /// TypeSystemExtension :
+  ///   - SchemaExtension
+  ///   - TypeExtension
+  ///
+  /// TypeExtension :
+  ///   - ScalarTypeExtension
+  ///   - ObjectTypeExtension
+  ///   - InterfaceTypeExtension
+  ///   - UnionTypeExtension
+  ///   - EnumTypeExtension
+  ///   - InputObjectTypeExtension
   /// ```
-  fn parse_type_extension_definition(&mut self) -> Result<ast::TypeExtensionDefinition, Error> {
-    let start = self.pos();
+  fn parse_type_system_extension(&mut self) -> Result<ast::TypeSystemDefinition<I::Text>, Error> {
+    let start = self.start();
     try!(self.expect_name("extend"));
-    let definition = try!(self.parse_object_type_definition());
-    Ok(ast::TypeExtensionDefinition {
+
+    if self.check_name("schema") {
+      Ok(ast::TypeSystemDefinition::SchemaExtension(try!(self.parse_schema_extension(start))))
+    }
+    else if self.check_name("scalar") {
+      Ok(ast::TypeSystemDefinition::TypeExtension(ast::TypeExtension::Scalar(try!(self.parse_scalar_type_extension(start)))))
+    }
+    else if self.check_name("type") {
+      Ok(ast::TypeSystemDefinition::TypeExtension(ast::TypeExtension::Object(try!(self.parse_object_type_extension(start)))))
+    }
+    else if self.check_name("interface") {
+      Ok(ast::TypeSystemDefinition::TypeExtension(ast::TypeExtension::Interface(try!(self.parse_interface_type_extension(start)))))
+    }
+    else if self.check_name("union") {
+      Ok(ast::TypeSystemDefinition::TypeExtension(ast::TypeExtension::Union(try!(self.parse_union_type_extension(start)))))
+    }
+    else if self.check_name("enum") {
+      Ok(ast::TypeSystemDefinition::TypeExtension(ast::TypeExtension::Enum(try!(self.parse_enum_type_extension(start)))))
+    }
+    else if self.check_name("input") {
+      Ok(ast::TypeSystemDefinition::TypeExtension(ast::TypeExtension::InputObject(try!(self.parse_input_object_type_extension(start)))))
+    }
+    else {
+      Err(self.unexpected())
+    }
+  }
+
+  /// ```txt
+  /// SchemaExtension :
+  ///   - extend schema Directives? { OperationTypeDefinition+ }
+  ///   - extend schema Directives
+  /// ```
+  fn parse_schema_extension(&mut self, start: Position) -> Result<ast::SchemaExtension<I::Text>, Error> {
+    try!(self.expect_name("schema"));
+    let directives = try!(self.parse_directives());
+    let operation_types = if self.check(&TokenKind::LeftBrace) {
+      try!(self.many(
+        &TokenKind::LeftBrace,
+        Parser::parse_operation_type_definition,
+        &TokenKind::RightBrace,
+        false,
+      ))
+    } else {
+      vec![]
+    };
+    // An extension must extend something.
+    if directives.is_empty() && operation_types.is_empty() {
+      return Err(self.unexpected());
+    }
+    Ok(ast::SchemaExtension {
+      loc: self.loc(start),
+      directives: directives,
+      operation_types: operation_types,
+    })
+  }
+
+  /// ```txt
+  /// ScalarTypeExtension : extend scalar Name Directives
+  /// ```
+  fn parse_scalar_type_extension(&mut self, start: Position) -> Result<ast::ScalarTypeExtension<I::Text>, Error> {
+    try!(self.expect_name("scalar"));
+    let name = try!(self.parse_name());
+    let directives = try!(self.parse_directives());
+    // An extension must extend something.
+    if directives.is_empty() {
+      return Err(self.unexpected());
+    }
+    Ok(ast::ScalarTypeExtension {
+      loc: self.loc(start),
+      name: name,
+      directives: directives,
+    })
+  }
+
+  /// ```txt
+  /// ObjectTypeExtension :
+  ///   - extend type Name ImplementsInterfaces? Directives? { FieldDefinition+ }
+  ///   - extend type Name ImplementsInterfaces? Directives
+  ///   - extend type Name ImplementsInterfaces
+  /// ```
+  fn parse_object_type_extension(&mut self, start: Position) -> Result<ast::ObjectTypeExtension<I::Text>, Error> {
+    try!(self.expect_name("type"));
+    let name = try!(self.parse_name());
+    let interfaces = if self.check_name("implements") { try!(self.parse_implements_interfaces(false)) } else { vec![] };
+    let directives = try!(self.parse_directives());
+    let has_fields = self.check(&TokenKind::LeftBrace);
+    let fields = if has_fields {
+      try!(self.many(
+        &TokenKind::LeftBrace,
+        Parser::parse_field_definition,
+        &TokenKind::RightBrace,
+        false,
+      ))
+    } else {
+      vec![]
+    };
+    // An extension must extend something.
+    if interfaces.is_empty() && directives.is_empty() && !has_fields {
+      return Err(self.unexpected());
+    }
+    Ok(ast::ObjectTypeExtension {
+      loc: self.loc(start),
+      name: name,
+      interfaces: interfaces,
+      directives: directives,
+      fields: fields,
+    })
+  }
+
+  /// ```txt
+  /// InterfaceTypeExtension :
+  ///   - extend interface Name ImplementsInterfaces? Directives? { FieldDefinition+ }
+  ///   - extend interface Name ImplementsInterfaces? Directives
+  ///   - extend interface Name ImplementsInterfaces
+  /// ```
+  fn parse_interface_type_extension(&mut self, start: Position) -> Result<ast::InterfaceTypeExtension<I::Text>, Error> {
+    try!(self.expect_name("interface"));
+    let name = try!(self.parse_name());
+    let interfaces = if self.check_name("implements") { try!(self.parse_implements_interfaces(false)) } else { vec![] };
+    let directives = try!(self.parse_directives());
+    let fields = if (interfaces.is_empty() && directives.is_empty()) || self.check(&TokenKind::LeftBrace) {
+      try!(self.many(
+        &TokenKind::LeftBrace,
+        Parser::parse_field_definition,
+        &TokenKind::RightBrace,
+        false,
+      ))
+    } else {
+      vec![]
+    };
+    Ok(ast::InterfaceTypeExtension {
+      loc: self.loc(start),
+      name: name,
+      interfaces: interfaces,
+      directives: directives,
+      fields: fields,
+    })
+  }
+
+  /// ```txt
+  /// UnionTypeExtension :
+  ///   - extend union Name Directives? = UnionMembers
+  ///   - extend union Name Directives
+  /// ```
+  fn parse_union_type_extension(&mut self, start: Position) -> Result<ast::UnionTypeExtension<I::Text>, Error> {
+    try!(self.expect_name("union"));
+    let name = try!(self.parse_name());
+    let directives = try!(self.parse_directives());
+    let types = if directives.is_empty() || self.check(&TokenKind::Equals) {
+      try!(self.expect(&TokenKind::Equals));
+      try!(self.parse_union_members())
+    } else {
+      vec![]
+    };
+    Ok(ast::UnionTypeExtension {
+      loc: self.loc(start),
+      name: name,
+      directives: directives,
+      types: types,
+    })
+  }
+
+  /// ```txt
+  /// EnumTypeExtension :
+  ///   - extend enum Name Directives? { EnumValueDefinition+ }
+  ///   - extend enum Name Directives
+  /// ```
+  fn parse_enum_type_extension(&mut self, start: Position) -> Result<ast::EnumTypeExtension<I::Text>, Error> {
+    try!(self.expect_name("enum"));
+    let name = try!(self.parse_name());
+    let directives = try!(self.parse_directives());
+    let values = if directives.is_empty() || self.check(&TokenKind::LeftBrace) {
+      try!(self.many(
+        &TokenKind::LeftBrace,
+        Parser::parse_enum_value_definition,
+        &TokenKind::RightBrace,
+        false,
+      ))
+    } else {
+      vec![]
+    };
+    Ok(ast::EnumTypeExtension {
       loc: self.loc(start),
-      definition: definition,
+      name: name,
+      directives: directives,
+      values: values,
+    })
+  }
+
+  /// ```txt
+  /// InputObjectTypeExtension :
+  ///   - extend input Name Directives? { InputValueDefinition+ }
+  ///   - extend input Name Directives
+  /// ```
+  fn parse_input_object_type_extension(&mut self, start: Position) -> Result<ast::InputObjectTypeExtension<I::Text>, Error> {
+    try!(self.expect_name("input"));
+    let name = try!(self.parse_name());
+    let directives = try!(self.parse_directives());
+    let fields = if directives.is_empty() || self.check(&TokenKind::LeftBrace) {
+      try!(self.many(
+        &TokenKind::LeftBrace,
+        Parser::parse_input_value_definition,
+        &TokenKind::RightBrace,
+        false,
+      ))
+    } else {
+      vec![]
+    };
+    Ok(ast::InputObjectTypeExtension {
+      loc: self.loc(start),
+      name: name,
+      directives: directives,
+      fields: fields,
     })
   }
 
   /// ```txt
   /// DirectiveDefinition :
-  ///   - directive @ Name ArgumentsDefinition? on DirectiveLocations
+  ///   - Description? directive @ Name ArgumentsDefinition? repeatable? on DirectiveLocations
   /// ```
-  fn parse_directive_definition(&mut self) -> Result<ast::DirectiveDefinition, Error> {
-    let start = self.pos();
+  fn parse_directive_definition(&mut self, start: Position, description: Option<ast::StringValue<I::Text>>) -> Result<ast::DirectiveDefinition<I::Text>, Error> {
     try!(self.expect_name("directive"));
     try!(self.expect(&TokenKind::At));
     let name = try!(self.parse_name());
     let arguments = if self.check(&TokenKind::LeftParen) { try!(self.parse_argument_definitions()) } else { vec![] };
+    let repeatable = self.next_if_name("repeatable");
     try!(self.expect_name("on"));
     let locations = try!(self.parse_directive_locations());
     Ok(ast::DirectiveDefinition {
       loc: self.loc(start),
+      description: description,
       name: name,
       arguments: arguments,
+      repeatable: repeatable,
       locations: locations,
     })
   }
 
   /// ```txt
   /// DirectiveLocations :
-  ///   - Name
-  ///   - DirectiveLocations | Name
+  ///   - DirectiveLocation
+  ///   - DirectiveLocations | DirectiveLocation
   /// ```
-  fn parse_directive_locations(&mut self) -> Result<Vec<ast::Name>, Error> {
-    let mut locations: Vec<ast::Name> = vec![];
+  fn parse_directive_locations(&mut self) -> Result<Vec<ast::DirectiveLocation>, Error> {
+    let mut locations: Vec<ast::DirectiveLocation> = vec![];
     loop {
-      locations.push(try!(self.parse_name()));
+      locations.push(try!(self.parse_directive_location()));
       // If the next character is not a pipe then break out of the loop.
-      if let None = self.next_if(&TokenKind::Pipe) {
+      if self.next_if(&TokenKind::Pipe).is_none() {
         break;
       }
     }
     Ok(locations)
   }
+
+  /// ```txt
+  /// DirectiveLocation :
+  ///   - ExecutableDirectiveLocation
+  ///   - TypeSystemDirectiveLocation
+  /// ```
+  fn parse_directive_location(&mut self) -> Result<ast::DirectiveLocation, Error> {
+    let location = match self.peek() {
+      Some(Ok(&Token { kind: TokenKind::Name(ref name), .. })) => ast::DirectiveLocation::from_name(name.as_ref()),
+      _ => None,
+    };
+    match location {
+      Some(location) => {
+        self.next();
+        Ok(location)
+      },
+      None => {
+        self.expected(Expected::DirectiveLocation);
+        Err(self.unexpected())
+      },
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
+
   use super::*;
+  use super::super::print;
+  use super::super::ast::{IntoOwned, Node};
 
   macro_rules! assert_parse {
     ($source:expr, $result:expr) => ({
@@ -1209,83 +2250,642 @@ mod tests {
   fn pos1(index: usize) -> Position {
     Position {
       index: index,
+      offset: index,
       line: 1,
       column: index + 1,
     }
   }
 
+  fn expected_definition() -> Vec<Expected> {
+    vec![
+      Expected::Token(TokenKind::LeftBrace),
+      Expected::Keyword("query"),
+      Expected::Keyword("mutation"),
+      Expected::Keyword("subscription"),
+      Expected::Keyword("fragment"),
+      #[cfg(feature = "type_system")] Expected::Description,
+      #[cfg(feature = "type_system")] Expected::Keyword("schema"),
+      #[cfg(feature = "type_system")] Expected::Keyword("scalar"),
+      #[cfg(feature = "type_system")] Expected::Keyword("type"),
+      #[cfg(feature = "type_system")] Expected::Keyword("interface"),
+      #[cfg(feature = "type_system")] Expected::Keyword("union"),
+      #[cfg(feature = "type_system")] Expected::Keyword("enum"),
+      #[cfg(feature = "type_system")] Expected::Keyword("input"),
+      #[cfg(feature = "type_system")] Expected::Keyword("extend"),
+      #[cfg(feature = "type_system")] Expected::Keyword("directive"),
+    ]
+  }
+
   #[test]
   fn test_document_empty() {
-    assert_parse!("", Err(Error::UnexpectedEnding(pos1(0))));
-    assert_parse!(" ", Err(Error::UnexpectedEnding(pos1(0))));
-    assert_parse!("  ", Err(Error::UnexpectedEnding(pos1(1))));
-    assert_parse!("   ", Err(Error::UnexpectedEnding(pos1(2))));
+    assert_parse!("", Err(Error::UnexpectedEnding(pos1(0), expected_definition())));
+    assert_parse!(" ", Err(Error::UnexpectedEnding(pos1(0), expected_definition())));
+    assert_parse!("  ", Err(Error::UnexpectedEnding(pos1(1), expected_definition())));
+    assert_parse!("   ", Err(Error::UnexpectedEnding(pos1(2), expected_definition())));
   }
 
   #[test]
   fn test_selection_set_empty() {
-    assert_parse!("{}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(1), pos1(1)))));
-    assert_parse!("{ }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(2), pos1(2)))));
-    assert_parse!("{  }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(3), pos1(3)))));
-    assert_parse!("{   }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(4), pos1(4)))));
-    assert_parse!("{", Err(Error::UnexpectedEnding(pos1(0))));
-    assert_parse!("{  ", Err(Error::UnexpectedEnding(pos1(2))));
-    assert_parse!("query {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(7), pos1(7)))));
-    assert_parse!("mutation {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(10), pos1(10)))));
-    assert_parse!("{ foo {} }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(7), pos1(7)))));
+    assert_parse!("{}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(1), pos1(1)), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("{ }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(2), pos1(2)), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("{  }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(3), pos1(3)), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("{   }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(4), pos1(4)), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("{", Err(Error::UnexpectedEnding(pos1(0), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("{  ", Err(Error::UnexpectedEnding(pos1(2), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("query {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(7), pos1(7)), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("mutation {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(10), pos1(10)), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("{ foo {} }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(7), pos1(7)), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name])));
   }
 
   #[test]
   fn parse_definition_bad_name() {
-    assert_parse!("hello", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("hello")), pos1(0), pos1(4)))));
-    assert_parse!("hello {}", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("hello")), pos1(0), pos1(4)))));
-    assert_parse!("  world", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("world")), pos1(2), pos1(6)))));
-    assert_parse!("foo  ", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("foo")), pos1(0), pos1(2)))));
-    assert_parse!("  bar  ", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("bar")), pos1(2), pos1(4)))));
+    assert_parse!("hello", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("hello")), pos1(0), pos1(4)), expected_definition())));
+    assert_parse!("hello {}", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("hello")), pos1(0), pos1(4)), expected_definition())));
+    assert_parse!("  world", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("world")), pos1(2), pos1(6)), expected_definition())));
+    assert_parse!("foo  ", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("foo")), pos1(0), pos1(2)), expected_definition())));
+    assert_parse!("  bar  ", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("bar")), pos1(2), pos1(4)), expected_definition())));
+  }
+
+  #[test]
+  #[cfg(not(feature = "type_system"))]
+  fn test_type_system_definition_disabled() {
+    assert_parse!("type Foo { a: A }", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("type")), pos1(0), pos1(3)), expected_definition())));
+    assert_parse!("\"a\" { b }", Err(Error::UnexpectedToken(Token::new(TokenKind::String(String::from("a")), pos1(0), pos1(2)), expected_definition())));
+    assert!(!expected_definition().contains(&Expected::Description));
   }
 
   #[test]
   fn test_variable_definitions_empty() {
-    assert_parse!("query ()", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(7), pos1(7)))));
-    assert_parse!("mutation ()", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(10), pos1(10)))));
-    assert_parse!("query (  )", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(9), pos1(9)))));
+    assert_parse!("query ()", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(7), pos1(7)), vec![Expected::Token(TokenKind::Dollar)])));
+    assert_parse!("mutation ()", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(10), pos1(10)), vec![Expected::Token(TokenKind::Dollar)])));
+    assert_parse!("query (  )", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(9), pos1(9)), vec![Expected::Token(TokenKind::Dollar)])));
   }
 
   #[test]
   fn test_variable_definitions_no_dollar() {
-    assert_parse!("query (foo)", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("foo")), pos1(7), pos1(9)))));
-    assert_parse!("query ($foo: Foo, bar)", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("bar")), pos1(18), pos1(20)))));
+    assert_parse!("query (foo)", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("foo")), pos1(7), pos1(9)), vec![Expected::Token(TokenKind::Dollar)])));
+    assert_parse!("query ($foo: Foo, bar)", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("bar")), pos1(18), pos1(20)), vec![Expected::Token(TokenKind::Bang), Expected::Token(TokenKind::Equals), Expected::Token(TokenKind::At), Expected::Token(TokenKind::RightParen), Expected::Token(TokenKind::Dollar)])));
   }
 
   #[test]
   fn test_variable_definitions_no_colon() {
-    assert_parse!("query ($foo Foo)", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("Foo")), pos1(12), pos1(14)))));
-    assert_parse!("query ($foo: Foo, $bar Bar)", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("Bar")), pos1(23), pos1(25)))));
+    assert_parse!("query ($foo Foo)", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("Foo")), pos1(12), pos1(14)), vec![Expected::Token(TokenKind::Colon)])));
+    assert_parse!("query ($foo: Foo, $bar Bar)", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("Bar")), pos1(23), pos1(25)), vec![Expected::Token(TokenKind::Colon)])));
   }
 
   #[test]
   fn test_variable_definitions_no_type_reference() {
-    assert_parse!("query ($foo:, $bar)", Err(Error::UnexpectedToken(Token::new(TokenKind::Dollar, pos1(14), pos1(14)))));
+    assert_parse!("query ($foo:, $bar)", Err(Error::UnexpectedToken(Token::new(TokenKind::Dollar, pos1(14), pos1(14)), vec![Expected::Token(TokenKind::LeftBracket), Expected::Name])));
   }
 
   #[test]
   fn test_variable_definitions_no_value_for_default_value() {
-    assert_parse!("query ($foo: Foo =)", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(18), pos1(18)))));
+    assert_parse!("query ($foo: Foo =)", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(18), pos1(18)), vec![Expected::Value])));
   }
 
   #[test]
   fn test_arguments_empty() {
-    assert_parse!("{ foo() }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(6), pos1(6)))));
-    assert_parse!("{ foo( ) }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(7), pos1(7)))));
-    assert_parse!("{ foo(  ) }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(8), pos1(8)))));
-    assert_parse!("{ foo(   ) }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(9), pos1(9)))));
-    assert_parse!("{ foo { bar() } }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(12), pos1(12)))));
+    assert_parse!("{ foo() }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(6), pos1(6)), vec![Expected::Name])));
+    assert_parse!("{ foo( ) }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(7), pos1(7)), vec![Expected::Name])));
+    assert_parse!("{ foo(  ) }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(8), pos1(8)), vec![Expected::Name])));
+    assert_parse!("{ foo(   ) }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(9), pos1(9)), vec![Expected::Name])));
+    assert_parse!("{ foo { bar() } }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(12), pos1(12)), vec![Expected::Name])));
   }
 
   #[test]
   fn test_arguments_no_colon() {
-    assert_parse!("{ foo(arg1 12) }", Err(Error::UnexpectedToken(Token::new(TokenKind::Int(12), pos1(11), pos1(12)))));
-    assert_parse!("{ foo(arg1 arg2) }", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("arg2")), pos1(11), pos1(14)))));
-    assert_parse!("{ foo(arg1: 12, arg2 34) }", Err(Error::UnexpectedToken(Token::new(TokenKind::Int(34), pos1(21), pos1(22)))));
+    assert_parse!("{ foo(arg1 12) }", Err(Error::UnexpectedToken(Token::new(TokenKind::Int(String::from("12")), pos1(11), pos1(12)), vec![Expected::Token(TokenKind::Colon)])));
+    assert_parse!("{ foo(arg1 arg2) }", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("arg2")), pos1(11), pos1(14)), vec![Expected::Token(TokenKind::Colon)])));
+    assert_parse!("{ foo(arg1: 12, arg2 34) }", Err(Error::UnexpectedToken(Token::new(TokenKind::Int(String::from("34")), pos1(21), pos1(22)), vec![Expected::Token(TokenKind::Colon)])));
+  }
+
+  #[test]
+  fn test_error_display() {
+    assert_eq!(parse("{ foo(a 1) }".chars()).unwrap_err().to_string(), "Unexpected integer `1`, expected `:`. (1:9)");
+    assert_eq!(parse("{\n  foo".chars()).unwrap_err().to_string(), "Unexpected end of input, expected `:`, `(`, `@`, `{`, `}`, `...` or a name. (2:5)");
+    assert_eq!(parse("{ \"foo".chars()).unwrap_err().to_string(), "Unexpected end of input. (1:6)");
+    assert_eq!(parse("{ \"\\q\" }".chars()).unwrap_err().to_string(), "Invalid escape sequence `\\q` in string. (1:5)");
+  }
+
+  #[test]
+  fn test_recovery_no_errors() {
+    let source = "query Foo($a: Int = 1) { a(b: $a) { c } ...D ... on E { f } }\n\nfragment D on E { g }";
+    let (document, errors) = parse_with_recovery(source.chars());
+    assert_eq!(errors, vec![]);
+    assert_eq!(Ok(document), parse(source.chars()));
+  }
+
+  #[test]
+  fn test_recovery_empty() {
+    let (document, errors) = parse_with_recovery("".chars());
+    assert_eq!(document.definitions, vec![]);
+    assert_eq!(errors, vec![Error::UnexpectedEnding(pos1(0), expected_definition())]);
+  }
+
+  #[test]
+  fn test_recovery_selection() {
+    let (document, errors) = parse_with_recovery("{ a b(x: ) c }".chars());
+    assert_eq!(print(&document), "{\n  a\n  c\n}\n");
+    assert_eq!(errors, vec![Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(9), pos1(9)), vec![Expected::Value])]);
+    let (document, errors) = parse_with_recovery("{ a { } b { c 12 d } }".chars());
+    assert_eq!(print(&document), "{\n  a {\n  }\n  b {\n    c\n    d\n  }\n}\n");
+    assert_eq!(errors, vec![
+      Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(6), pos1(6)), vec![Expected::Token(TokenKind::Ellipsis), Expected::Name]),
+      Error::UnexpectedToken(Token::new(TokenKind::Int(String::from("12")), pos1(14), pos1(15)), vec![Expected::Token(TokenKind::Colon), Expected::Token(TokenKind::LeftParen), Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace), Expected::Token(TokenKind::RightBrace), Expected::Token(TokenKind::Ellipsis), Expected::Name]),
+    ]);
+  }
+
+  #[test]
+  fn test_recovery_unterminated_selection_set() {
+    let (document, errors) = parse_with_recovery("{ a { b } c".chars());
+    assert_eq!(print(&document), "{\n  a {\n    b\n  }\n  c\n}\n");
+    assert_eq!(errors, vec![Error::UnexpectedEnding(pos1(10), vec![Expected::Token(TokenKind::Colon), Expected::Token(TokenKind::LeftParen), Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace), Expected::Token(TokenKind::RightBrace)])]);
+  }
+
+  #[test]
+  fn test_recovery_definition() {
+    let (document, errors) = parse_with_recovery("{ a } garbage ) fragment F on { x } query { y }".chars());
+    assert_eq!(print(&document), "{\n  a\n}\n\n{\n  y\n}\n");
+    assert_eq!(errors, vec![
+      Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("garbage")), pos1(6), pos1(12)), expected_definition()),
+      Error::UnexpectedToken(Token::new(TokenKind::LeftBrace, pos1(30), pos1(30)), vec![Expected::Name]),
+    ]);
+  }
+
+  #[test]
+  fn test_recovery_lexer_errors() {
+    let (document, errors) = parse_with_recovery("{ a % b \"c }".chars());
+    assert_eq!(print(&document), "{\n  a\n  b\n}\n");
+    assert_eq!(errors, vec![
+      Error::Lexer(LexerError::UnknownChar(pos1(4), '%')),
+      Error::UnexpectedEnding(pos1(11), vec![Expected::Token(TokenKind::Colon), Expected::Token(TokenKind::LeftParen), Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace), Expected::Token(TokenKind::RightBrace)]),
+      Error::Lexer(LexerError::UnexpectedEnding(pos1(11))),
+    ]);
+  }
+
+  #[test]
+  fn test_recovery_any_prefix() {
+    let source = "query Q($a: [Int!] = [1, 2]) @d(x: {y: \"z\"}) { a: b(c: $a) { ...F ... on T @e { f } } }\nfragment F on T { g }";
+    let chars = source.chars().collect::<Vec<char>>();
+    for end in 0..chars.len() {
+      parse_with_recovery(chars[..end].iter().cloned());
+      parse_with_recovery(chars[end..].iter().cloned());
+    }
+  }
+
+  #[test]
+  fn test_recovery_deep_nesting() {
+    let too_deep = Error::LimitExceeded(pos1(RECOVERY_MAX_DEPTH * 4), Limit::Depth(RECOVERY_MAX_DEPTH));
+    let (document, errors) = parse_with_recovery("{ a ".repeat(100_000).chars());
+    assert_eq!(document.definitions.len(), 1);
+    assert_eq!(errors[0], too_deep);
+    let (_, errors) = parse_with_recovery(format!("{}{}", "{ a ".repeat(100_000), "}".repeat(100_000)).chars());
+    assert_eq!(errors[0], too_deep);
+    let (_, errors) = parse_with_recovery(format!("{{ a(b: {}) }}", "[".repeat(100_000)).chars());
+    assert_eq!(errors[0], Error::LimitExceeded(pos1(RECOVERY_MAX_DEPTH + 6), Limit::Depth(RECOVERY_MAX_DEPTH)));
+    let (_, errors) = parse_with_recovery(format!("query ($a: {}) {{ a }}", "[".repeat(100_000)).chars());
+    assert_eq!(errors[0], Error::LimitExceeded(pos1(RECOVERY_MAX_DEPTH + 11), Limit::Depth(RECOVERY_MAX_DEPTH)));
+    let options = ParseOptions { max_depth: Some(2), ..ParseOptions::default() };
+    let (document, errors) = parse_with_recovery_and_options("{ a { b { c } } d }".chars(), &options);
+    // The selection which is too deep is skipped up to its matching brace.
+    assert_eq!(print(&document), "{\n  a {\n  }\n  d\n}\n");
+    assert_eq!(errors, vec![Error::LimitExceeded(pos1(8), Limit::Depth(2))]);
+  }
+
+  #[test]
+  fn test_number_values_lossless() {
+    let document = parse_without_location("{ foo(a: 9007199254740993, b: 2147483648, c: -7, d: 0.1, e: 1e400) }".chars()).unwrap();
+    let values = match document.definitions[0] {
+      ast::Definition::Operation(ref operation) => match operation.selection_set.selections[0] {
+        ast::Selection::Field(ref field) => field.arguments.iter().map(|argument| argument.value.clone()).collect::<Vec<_>>(),
+        _ => unreachable!(),
+      },
+      _ => unreachable!(),
+    };
+    match (&values[0], &values[1], &values[2], &values[3], &values[4]) {
+      (&ast::Value::Int(ref a), &ast::Value::Int(ref b), &ast::Value::Int(ref c), &ast::Value::Float(ref d), &ast::Value::Float(ref e)) => {
+        assert_eq!(a.as_str(), "9007199254740993");
+        assert_eq!(a.as_i32(), None);
+        assert_eq!(a.as_i64(), Some(9007199254740993));
+        assert_eq!(b.as_i32(), None);
+        assert_eq!(b.as_i64(), Some(2147483648));
+        assert_eq!(c.as_i32(), Some(-7));
+        assert_eq!(c.as_f64(), Some(-7.0));
+        assert_eq!(d.as_str(), "0.1");
+        assert_eq!(d.as_f64(), Some(0.1));
+        assert_eq!(e.as_str(), "1e400");
+        assert_eq!(e.as_f64(), None);
+      },
+      _ => panic!("Expected number values."),
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "type_system")]
+  fn test_implements_interfaces_separators() {
+    let expected = parse_without_location("type Foo implements A & B & C { a: A }".chars());
+    assert!(expected.is_ok());
+    assert_eq!(parse_without_location("type Foo implements & A & B & C { a: A }".chars()), expected);
+    assert_eq!(parse_without_location("type Foo implements A, B, C { a: A }".chars()), expected);
+    assert_eq!(parse_without_location("type Foo implements A B C { a: A }".chars()), expected);
+    // The legacy separators are always printed as ampersands.
+    assert_eq!(print(&parse_without_location("type Foo implements A, B C { a: A }".chars()).unwrap()), "type Foo implements A & B & C {\n  a: A\n}\n");
+    assert_parse!("type Foo implements & { a: A }", Err(Error::UnexpectedToken(Token::new(TokenKind::LeftBrace, pos1(22), pos1(22)), vec![Expected::Name])));
+    assert_parse!("type Foo implements A & { a: A }", Err(Error::UnexpectedToken(Token::new(TokenKind::LeftBrace, pos1(24), pos1(24)), vec![Expected::Name])));
+  }
+
+  #[test]
+  fn test_subscription() {
+    match parse_without_location("subscription S { a }".chars()).unwrap().definitions[0] {
+      ast::Definition::Operation(ref operation) => assert_eq!(operation.operation, ast::OperationType::Subscription),
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    }
+    assert_parse!("subscription", Err(Error::UnexpectedEnding(pos1(11), vec![Expected::Name, Expected::Token(TokenKind::LeftParen), Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace)])));
+  }
+
+  #[test]
+  #[cfg(feature = "type_system")]
+  fn test_schema_subscription() {
+    match parse_without_location("schema { query: Q subscription: S }".chars()).unwrap().definitions[0] {
+      ast::Definition::TypeSystem(ast::TypeSystemDefinition::Schema(ref schema)) => {
+        let operations = schema.operation_types.iter().map(|operation_type| operation_type.operation.clone()).collect::<Vec<_>>();
+        assert_eq!(operations, vec![ast::OperationType::Query, ast::OperationType::Subscription]);
+      },
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "type_system")]
+  fn test_type_system_extension_empty() {
+    let expected_extension = || vec![
+      Expected::Keyword("schema"),
+      Expected::Keyword("scalar"),
+      Expected::Keyword("type"),
+      Expected::Keyword("interface"),
+      Expected::Keyword("union"),
+      Expected::Keyword("enum"),
+      Expected::Keyword("input"),
+    ];
+    assert_parse!("extend", Err(Error::UnexpectedEnding(pos1(5), expected_extension())));
+    assert_parse!("extend foo", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("foo")), pos1(7), pos1(9)), expected_extension())));
+    assert_parse!("extend schema", Err(Error::UnexpectedEnding(pos1(12), vec![Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace)])));
+    assert_parse!("extend scalar Foo", Err(Error::UnexpectedEnding(pos1(16), vec![Expected::Token(TokenKind::At)])));
+    assert_parse!("extend type Foo", Err(Error::UnexpectedEnding(pos1(14), vec![Expected::Keyword("implements"), Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace)])));
+    assert_parse!("extend interface Foo", Err(Error::UnexpectedEnding(pos1(19), vec![Expected::Keyword("implements"), Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace)])));
+    assert_parse!("extend union Foo", Err(Error::UnexpectedEnding(pos1(15), vec![Expected::Token(TokenKind::At), Expected::Token(TokenKind::Equals)])));
+    assert_parse!("extend enum Foo", Err(Error::UnexpectedEnding(pos1(14), vec![Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace)])));
+    assert_parse!("extend input Foo", Err(Error::UnexpectedEnding(pos1(15), vec![Expected::Token(TokenKind::At), Expected::Token(TokenKind::LeftBrace)])));
+    assert_parse!("extend enum Foo {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(17), pos1(17)), vec![Expected::Description, Expected::Name])));
+    assert_parse!("extend type Foo {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(17), pos1(17)), vec![Expected::Description, Expected::Name])));
+    assert_parse!("extend type Foo @a {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(20), pos1(20)), vec![Expected::Description, Expected::Name])));
+    assert_parse!("extend interface Foo {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(22), pos1(22)), vec![Expected::Description, Expected::Name])));
+    assert_parse!("extend input Foo {}", Err(Error::UnexpectedToken(Token::new(TokenKind::RightBrace, pos1(18), pos1(18)), vec![Expected::Description, Expected::Name])));
+  }
+
+  #[test]
+  #[cfg(feature = "type_system")]
+  fn test_type_system_extension_print() {
+    // Every extension the parser accepts must print as an extension the
+    // parser accepts again.
+    let source = "extend type Foo @a\n\nextend type Foo implements Bar\n\nextend type Foo {\n  b: Int\n}\n";
+    let document = parse_without_location(source.chars()).unwrap();
+    assert_eq!(print(&document), source);
+    assert_eq!(parse_without_location(print(&document).chars()).unwrap(), document);
+  }
+
+  #[test]
+  fn test_string_value_print_control_characters() {
+    // Control characters must be escaped so that the printed string parses
+    // back to the same value.
+    let document = parse_without_location("{ a(b: \"c\\rd\\te\\bf\\fg\\u0001h\\u001Fi\\nj\") }".chars()).unwrap();
+    let printed = print(&document);
+    assert_eq!(printed, "{\n  a(b: \"c\\rd\\te\\bf\\fg\\u0001h\\u001Fi\\nj\")\n}\n");
+    assert_eq!(parse_without_location(printed.chars()).unwrap(), document);
+  }
+
+  #[test]
+  fn test_variable_directives() {
+    let source = "query Q($a: Int = 1 @b(c: 2) @d, $e: ID @f) {\n  g\n}\n";
+    let document = parse_without_location(source.chars()).unwrap();
+    match document.definitions[0] {
+      ast::Definition::Operation(ref operation) => {
+        let directives = operation.variable_definitions.iter().map(|variable_definition| variable_definition.directives.iter().map(|directive| directive.name.value.as_str()).collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert_eq!(directives, vec![vec!["b", "d"], vec!["f"]]);
+      },
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    }
+    assert_eq!(print(&document), source);
+  }
+
+  #[test]
+  #[cfg(feature = "fragment_variables")]
+  fn test_fragment_variables() {
+    let source = "{\n  ...F(size: 20) @a\n  ...G\n}\n\nfragment F($size: Int = 10) on User {\n  pic(size: $size)\n}\n\nfragment G on User {\n  id\n}\n";
+    let document = parse_without_location(source.chars()).unwrap();
+    match (&document.definitions[0], &document.definitions[1], &document.definitions[2]) {
+      (&ast::Definition::Operation(ref operation), &ast::Definition::Fragment(ref f), &ast::Definition::Fragment(ref g)) => {
+        let arguments = operation.selection_set.selections.iter().map(|selection| match *selection {
+          ast::Selection::FragmentSpread(ref spread) => spread.arguments.iter().map(|argument| argument.name.value.as_str()).collect::<Vec<_>>(),
+          ref selection => panic!("Unexpected selection {:?}", selection),
+        }).collect::<Vec<_>>();
+        assert_eq!(arguments, vec![vec!["size"], vec![]]);
+        assert_eq!(f.variable_definitions.iter().map(|variable_definition| variable_definition.variable.name.value.as_str()).collect::<Vec<_>>(), vec!["size"]);
+        assert_eq!(g.variable_definitions, vec![]);
+      },
+      definitions => panic!("Unexpected definitions {:?}", definitions),
+    }
+    assert_eq!(print(&document), source);
+    assert_parse!("fragment F() on T { a }", Err(Error::UnexpectedToken(Token::new(TokenKind::RightParen, pos1(11), pos1(11)), vec![Expected::Token(TokenKind::Dollar)])));
+  }
+
+  #[test]
+  #[cfg(not(feature = "fragment_variables"))]
+  fn test_fragment_variables_disabled() {
+    assert_parse!("{ ...F(size: 20) }", Err(Error::UnexpectedToken(Token::new(TokenKind::LeftParen, pos1(6), pos1(6)), vec![Expected::Token(TokenKind::At), Expected::Token(TokenKind::RightBrace), Expected::Token(TokenKind::Ellipsis), Expected::Name])));
+    assert_parse!("fragment F($size: Int) on T { a }", Err(Error::UnexpectedToken(Token::new(TokenKind::LeftParen, pos1(10), pos1(10)), vec![Expected::Keyword("on")])));
+  }
+
+  #[test]
+  #[cfg(feature = "type_system")]
+  fn test_directive_definition_repeatable() {
+    let source = "directive @a(b: Int) repeatable on FIELD | OBJECT\n\ndirective @repeatable on FIELD\n";
+    let document = parse_without_location(source.chars()).unwrap();
+    let repeatable = document.definitions.iter().map(|definition| match *definition {
+      ast::Definition::TypeSystem(ast::TypeSystemDefinition::Directive(ref directive)) => (directive.name.value.as_str(), directive.repeatable),
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    }).collect::<Vec<_>>();
+    assert_eq!(repeatable, vec![("a", true), ("repeatable", false)]);
+    assert_eq!(print(&document), source);
+    assert_parse!("directive @a repeatable", Err(Error::UnexpectedEnding(pos1(22), vec![Expected::Keyword("on")])));
+  }
+
+  #[test]
+  #[cfg(feature = "type_system")]
+  fn test_directive_locations() {
+    let document = parse_without_location("directive @a on QUERY | FIELD | INPUT_FIELD_DEFINITION".chars()).unwrap();
+    match document.definitions[0] {
+      ast::Definition::TypeSystem(ast::TypeSystemDefinition::Directive(ref directive)) => {
+        assert_eq!(directive.locations, vec![ast::DirectiveLocation::Query, ast::DirectiveLocation::Field, ast::DirectiveLocation::InputFieldDefinition]);
+      },
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    }
+    assert_parse!("directive @a on FEILD", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("FEILD")), pos1(16), pos1(20)), vec![Expected::DirectiveLocation])));
+    assert_parse!("directive @a on FIELD | field", Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("field")), pos1(24), pos1(28)), vec![Expected::DirectiveLocation])));
+  }
+
+  #[test]
+  fn test_directive_location_of_nodes() {
+    use super::super::ast::{DirectiveLocation, HasDirectives};
+    let document = parse("query Q($a: Int @b) @c { d @e ... @f { g } ...H @i }\nfragment H on T @j { k }\nmutation { l }".chars()).unwrap();
+    let (operation, fragment, mutation) = match (&document.definitions[0], &document.definitions[1], &document.definitions[2]) {
+      (&ast::Definition::Operation(ref operation), &ast::Definition::Fragment(ref fragment), &ast::Definition::Operation(ref mutation)) => (operation, fragment, mutation),
+      definitions => panic!("Unexpected definitions {:?}", definitions),
+    };
+    let mut locations = vec![
+      (operation.directive_location(), operation.directives()[0].name.value.as_str()),
+      (operation.variable_definitions[0].directive_location(), operation.variable_definitions[0].directives()[0].name.value.as_str()),
+      (fragment.directive_location(), fragment.directives()[0].name.value.as_str()),
+    ];
+    for selection in &operation.selection_set.selections {
+      locations.push(match *selection {
+        ast::Selection::Field(ref node) => (node.directive_location(), node.directives()[0].name.value.as_str()),
+        ast::Selection::InlineFragment(ref node) => (node.directive_location(), node.directives()[0].name.value.as_str()),
+        ast::Selection::FragmentSpread(ref node) => (node.directive_location(), node.directives()[0].name.value.as_str()),
+      });
+    }
+    assert_eq!(locations, vec![
+      (DirectiveLocation::Query, "c"),
+      (DirectiveLocation::VariableDefinition, "b"),
+      (DirectiveLocation::FragmentDefinition, "j"),
+      (DirectiveLocation::Field, "e"),
+      (DirectiveLocation::InlineFragment, "f"),
+      (DirectiveLocation::FragmentSpread, "i"),
+    ]);
+    assert_eq!(mutation.directive_location(), DirectiveLocation::Mutation);
+    assert!(DirectiveLocation::VariableDefinition.is_executable());
+    assert!(!DirectiveLocation::InputFieldDefinition.is_executable());
+    assert_eq!(DirectiveLocation::from_name("ENUM_VALUE"), Some(DirectiveLocation::EnumValue));
+    assert_eq!(DirectiveLocation::from_name("enum_value"), None);
+    assert_eq!(DirectiveLocation::ArgumentDefinition.to_string(), "ARGUMENT_DEFINITION");
+  }
+
+  #[test]
+  #[cfg(feature = "type_system")]
+  fn test_directive_location_of_input_values() {
+    use super::super::ast::{DirectiveLocation, InputValueParent};
+    let document = parse("type A { b(c: Int @d): Int }\ninput E { f: Int @g }\nextend input E { h: Int @i }\ndirective @j(k: Int @l) on FIELD".chars()).unwrap();
+    let parents = document.definitions.iter().map(|definition| match *definition {
+      ast::Definition::TypeSystem(ast::TypeSystemDefinition::Type(ast::TypeDefinition::Object(ref node))) => InputValueParent::FieldDefinition(&node.fields[0]),
+      ast::Definition::TypeSystem(ast::TypeSystemDefinition::Type(ast::TypeDefinition::InputObject(ref node))) => InputValueParent::InputObjectTypeDefinition(node),
+      ast::Definition::TypeSystem(ast::TypeSystemDefinition::TypeExtension(ast::TypeExtension::InputObject(ref node))) => InputValueParent::InputObjectTypeExtension(node),
+      ast::Definition::TypeSystem(ast::TypeSystemDefinition::Directive(ref node)) => InputValueParent::DirectiveDefinition(node),
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    });
+    let mut locations = vec![];
+    for parent in parents {
+      locations.extend(parent.input_values().iter().map(|input_value| (parent.input_value_directive_location(), input_value.directives[0].name.value.as_str())));
+    }
+    assert_eq!(locations, vec![
+      (DirectiveLocation::ArgumentDefinition, "d"),
+      (DirectiveLocation::InputFieldDefinition, "g"),
+      (DirectiveLocation::InputFieldDefinition, "i"),
+      (DirectiveLocation::ArgumentDefinition, "l"),
+    ]);
+  }
+
+  #[test]
+  fn test_limit_depth() {
+    let options = ParseOptions { max_depth: Some(3), ..ParseOptions::default() };
+    assert!(parse_with_options("{ a { b { c } } }".chars(), &options).is_ok());
+    assert_eq!(parse_with_options("{ a { b { c { d } } } }".chars(), &options), Err(Error::LimitExceeded(pos1(12), Limit::Depth(3))));
+    assert_eq!(parse_with_options("{ a(b: [{c: [1]}]) }".chars(), &options), Err(Error::LimitExceeded(pos1(12), Limit::Depth(3))));
+    assert_eq!(parse_with_options("query ($a: [[[[Int]]]]) { a }".chars(), &options), Err(Error::LimitExceeded(pos1(14), Limit::Depth(3))));
+    assert!(parse_with_options("query ($a: [[[[Int]]]]) { a }".chars(), &ParseOptions::default()).is_ok());
+
+    // Hostile documents which would otherwise overflow the stack.
+    let options = ParseOptions { max_depth: Some(64), ..ParseOptions::default() };
+    for source in &["{ a ".repeat(100000), "{ a(b: ".to_owned() + &"[".repeat(100000), "query ($a: ".to_owned() + &"[".repeat(100000)] {
+      match parse_with_options(source.chars(), &options) {
+        Err(Error::LimitExceeded(_, Limit::Depth(64))) => (),
+        result => panic!("Unexpected result {:?}", result),
+      }
+    }
+  }
+
+  #[test]
+  fn test_limit_tokens() {
+    let options = ParseOptions { max_tokens: Some(4), ..ParseOptions::default() };
+    assert!(parse_with_options("{ a b }".chars(), &options).is_ok());
+    assert_eq!(parse_with_options("{ a b c }".chars(), &options), Err(Error::LimitExceeded(pos1(8), Limit::Tokens(4))));
+    assert_eq!(parse_with_options("{ a b } # c".chars(), &options), Err(Error::LimitExceeded(pos1(8), Limit::Tokens(4))));
+    assert_eq!(parse_with_options("{ a b } { c }".chars(), &options), Err(Error::LimitExceeded(pos1(8), Limit::Tokens(4))));
+  }
+
+  #[test]
+  fn test_limit_length() {
+    let options = ParseOptions { max_length: Some(7), ..ParseOptions::default() };
+    assert!(parse_with_options("{ a b }".chars(), &options).is_ok());
+    assert_eq!(parse_with_options("{ a bc }".chars(), &options), Err(Error::LimitExceeded(pos1(7), Limit::Length(7))));
+    assert_eq!(parse_with_options("{ a b } ".chars(), &options), Err(Error::LimitExceeded(pos1(7), Limit::Length(7))));
+    assert_eq!(parse_with_options("{ a(b: \"cdef\") }".chars(), &options), Err(Error::LimitExceeded(pos1(7), Limit::Length(7))));
+    let error = parse_with_options("{ a bc }".chars(), &options).unwrap_err();
+    assert_eq!(error.code(), "E0003");
+    assert_eq!(error.to_string(), "Document is longer than 7 bytes. (1:8)");
+  }
+
+  #[test]
+  fn test_limit_length_single_token() {
+    use std::iter::repeat;
+    // These sources never end, so the lexer must stop reading them at the
+    // maximum length instead of after the token.
+    let options = ParseOptions { max_length: Some(16), ..ParseOptions::default() };
+    let too_long = Err(Error::LimitExceeded(pos1(16), Limit::Length(16)));
+    assert_eq!(parse_with_options("{ a(b: \"".chars().chain(repeat('c')), &options), too_long);
+    assert_eq!(parse_with_options("{ a(b: \"\"\"".chars().chain(repeat('c')), &options), too_long);
+    assert_eq!(parse_with_options("{ a } #".chars().chain(repeat('c')), &options), too_long);
+    assert_eq!(parse_with_options("{ a".chars().chain(repeat('c')), &options), too_long);
+    let (document, errors) = parse_with_recovery_and_options("{ a b(c: \"".chars().chain(repeat('d')), &options);
+    assert_eq!(print(&document), "{\n  a\n}\n");
+    assert!(errors.contains(&Error::LimitExceeded(pos1(16), Limit::Length(16))));
+  }
+
+  #[test]
+  fn test_parse_value() {
+    match parse_value(" [1, $a, {b: \"c\"}] ".chars()).unwrap() {
+      ast::Value::List(ref list) => match (&list.values[0], &list.values[1], &list.values[2]) {
+        (&ast::Value::Int(ref a), &ast::Value::Variable(ref b), &ast::Value::Object(ref c)) => {
+          assert_eq!(a.as_str(), "1");
+          assert_eq!(b.name.value, "a");
+          assert_eq!(c.fields[0].name.value, "b");
+        },
+        values => panic!("Unexpected values {:?}", values),
+      },
+      value => panic!("Unexpected value {:?}", value),
+    }
+    assert_eq!(parse_value("".chars()), Err(Error::UnexpectedEnding(pos1(0), vec![Expected::Value])));
+    assert_eq!(parse_value("1 2".chars()), Err(Error::UnexpectedToken(Token::new(TokenKind::Int(String::from("2")), pos1(2), pos1(2)), vec![])));
+  }
+
+  #[test]
+  fn test_parse_const_value() {
+    match parse_const_value("{a: [true, null, B]}".chars()).unwrap() {
+      ast::Value::Object(ref object) => match object.fields[0].value {
+        ast::Value::List(ref list) => assert_eq!(list.values.len(), 3),
+        ref value => panic!("Unexpected value {:?}", value),
+      },
+      value => panic!("Unexpected value {:?}", value),
+    }
+    assert_eq!(parse_const_value("$a".chars()), Err(Error::UnexpectedToken(Token::new(TokenKind::Dollar, pos1(0), pos1(0)), vec![Expected::Value])));
+    assert_eq!(parse_const_value("[1, $a]".chars()), Err(Error::UnexpectedToken(Token::new(TokenKind::Dollar, pos1(4), pos1(4)), vec![Expected::Token(TokenKind::RightBracket), Expected::Value])));
+    assert_eq!(parse_const_value("{a: $a}".chars()), Err(Error::UnexpectedToken(Token::new(TokenKind::Dollar, pos1(4), pos1(4)), vec![Expected::Value])));
+  }
+
+  #[test]
+  fn test_parse_type() {
+    match parse_type("[ID!]!".chars()).unwrap() {
+      ast::Type::NonNull(ref non_null) => match *non_null.type_ {
+        ast::NullableType::List(ref list) => match *list.type_ {
+          ast::Type::NonNull(ref non_null) => match *non_null.type_ {
+            ast::NullableType::Named(ref named) => assert_eq!(named.name.value, "ID"),
+            ref type_ => panic!("Unexpected type {:?}", type_),
+          },
+          ref type_ => panic!("Unexpected type {:?}", type_),
+        },
+        ref type_ => panic!("Unexpected type {:?}", type_),
+      },
+      type_ => panic!("Unexpected type {:?}", type_),
+    }
+    assert_eq!(parse_type("[ID".chars()), Err(Error::UnexpectedEnding(pos1(2), vec![Expected::Token(TokenKind::Bang), Expected::Token(TokenKind::RightBracket)])));
+    assert_eq!(parse_type("ID ID".chars()), Err(Error::UnexpectedToken(Token::new(TokenKind::Name(String::from("ID")), pos1(3), pos1(4)), vec![Expected::Token(TokenKind::Bang)])));
+  }
+
+  #[test]
+  fn test_comments_and_locations() {
+    let document = parse("# a\n{ b # c\n  d }  # e".chars()).unwrap();
+    assert_eq!(document.loc, Some(Location::new(Position { index: 4, offset: 4, line: 2, column: 1 }, Position { index: 16, offset: 16, line: 3, column: 5 })));
+    match document.definitions[0] {
+      ast::Definition::Operation(ref operation) => match operation.selection_set.selections[1] {
+        ast::Selection::Field(ref field) => assert_eq!(field.loc, Some(Location::new(Position { index: 14, offset: 14, line: 3, column: 3 }, Position { index: 14, offset: 14, line: 3, column: 3 }))),
+        ref selection => panic!("Unexpected selection {:?}", selection),
+      },
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    }
+  }
+
+  #[test]
+  fn test_parse_source() {
+    let source = Source::new("schema.graphql", "query Q { a(b: \"ü\") { c } }\n");
+    let document = parse_source(&source).unwrap();
+    assert_eq!(document.loc.as_ref().and_then(|loc| loc.source.as_ref()), Some(&source));
+    assert_eq!(document.source_text(), Some("query Q { a(b: \"ü\") { c } }"));
+    match document.definitions[0] {
+      ast::Definition::Operation(ref operation) => match operation.selection_set.selections[0] {
+        ast::Selection::Field(ref field) => {
+          assert_eq!(field.source_text(), Some("a(b: \"ü\") { c }"));
+          assert_eq!(field.arguments[0].value.source_text(), Some("\"ü\""));
+          assert_eq!(field.selection_set.as_ref().and_then(|selection_set| selection_set.text(source.body())), Some("{ c }"));
+        },
+        ref selection => panic!("Unexpected selection {:?}", selection),
+      },
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    }
+    let document = parse(source.body().chars()).unwrap();
+    assert_eq!(document.source_text(), None);
+    assert_eq!(document.text(source.body()), Some("query Q { a(b: \"ü\") { c } }"));
+  }
+
+  #[test]
+  fn test_parse_sources() {
+    let a = Source::new("a.graphql", "query A { a }\n");
+    let b = Source::new("b.graphql", "fragment B on T { b }\n{ c }\n");
+    let document = parse_sources(&[a.clone(), b.clone()]).unwrap();
+    assert_eq!(document.loc, None);
+    let sources = document.definitions.iter().map(|definition| definition.loc().and_then(|loc| loc.source.clone())).collect::<Vec<_>>();
+    assert_eq!(sources, vec![Some(a.clone()), Some(b.clone()), Some(b.clone())]);
+    let texts = document.definitions.iter().map(|definition| definition.source_text()).collect::<Vec<_>>();
+    assert_eq!(texts, vec![Some("query A { a }"), Some("fragment B on T { b }"), Some("{ c }")]);
+
+    let c = Source::new("c.graphql", "{ d(e: ) }");
+    let error = parse_sources(&[a.clone(), c.clone(), b.clone()]).unwrap_err();
+    assert_eq!(error.source, c);
+    assert_eq!(error.location().and_then(|location| location.source), Some(c.clone()));
+    assert_eq!(error.to_string(), "c.graphql: Unexpected `)`, expected a value. (1:8)");
+  }
+
+  #[test]
+  fn test_parse_reader() {
+    let source = "query Q { a(b: \"ü😀\") { c } }";
+    assert_eq!(parse_reader(source.as_bytes()).unwrap(), parse(source.chars()).unwrap());
+    match parse_reader("{ a(b: ) }".as_bytes()) {
+      Err(ReadError::Parse(error)) => assert_eq!(error, parse("{ a(b: ) }".chars()).unwrap_err()),
+      result => panic!("Unexpected result {:?}", result),
+    }
+    // Invalid UTF-8 in the middle of a name, which would otherwise parse.
+    match parse_reader(&b"{ a\xFFb }"[..]) {
+      Err(ReadError::Io(error)) => assert_eq!(error.kind(), ::std::io::ErrorKind::InvalidData),
+      result => panic!("Unexpected result {:?}", result),
+    }
+  }
+
+  #[test]
+  fn test_parse_borrowed() {
+    let source = "query Q($a: Int = 1) { a(b: \"c\", d: \"\\u00e9\") { ...F } }\nfragment F on T { e }";
+    let document = parse_borrowed(source).unwrap();
+    match document.definitions[0] {
+      ast::Definition::Operation(ref operation) => match operation.selection_set.selections[0] {
+        ast::Selection::Field(ref field) => match (&field.name.value, &field.arguments[0].value, &field.arguments[1].value) {
+          (&Cow::Borrowed("a"), &ast::Value::String(ref b), &ast::Value::String(ref d)) => {
+            assert_eq!(b.value, Cow::Borrowed("c"));
+            assert_eq!(d.value, Cow::Owned::<str>(String::from("é")));
+          },
+          values => panic!("Unexpected values {:?}", values),
+        },
+        ref selection => panic!("Unexpected selection {:?}", selection),
+      },
+      ref definition => panic!("Unexpected definition {:?}", definition),
+    }
+    assert_eq!(Ok(document.into_owned()), parse(source.chars()));
+    assert_eq!(parse_borrowed("{ a(b 1) }"), Err(Error::UnexpectedToken(Token::new(TokenKind::Int(String::from("1")), pos1(6), pos1(6)), vec![Expected::Token(TokenKind::Colon)])));
   }
 }
@@ -1,9 +1,16 @@
+use std::fmt;
+use std::sync::Arc;
+
 /// The position of a single character in a source document.
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Position {
   /// The 0-indexed position of the character. If the source document was an
   /// array of characters, this would be the index position of that character.
   pub index: usize,
+  /// The 0-indexed UTF-8 byte offset of the character. If the source document
+  /// was a string slice, this would be the index of that character’s first
+  /// byte.
+  pub offset: usize,
   /// The 1-indexed line on which this character lies. This number is helpful
   /// when it comes to text editors. Used in conjunction with `column`.
   pub line: usize,
@@ -16,10 +23,15 @@ pub struct Position {
 /// identify the region of the source from which the AST derived.
 #[derive(Clone, PartialEq, Debug)]
 pub struct Location {
-  /// The character position at which this Node begins.
+  /// The character position at which this Node begins. This is the position
+  /// of the first character of the Node’s first token.
   pub start: Position,
-  /// The character position at which this Node ends.
+  /// The character position at which this Node ends. This is the position of
+  /// the last character of the Node’s last token.
   pub end: Position,
+  /// The source document this location is in, if the document was parsed
+  /// with `parse_source`.
+  pub source: Option<Source>,
 }
 
 impl Location {
@@ -28,6 +40,81 @@ impl Location {
     Location {
       start: start,
       end: end,
+      source: None,
+    }
+  }
+
+  /// Gets the text of this location from the source document it refers to.
+  /// The source must be the same document the location was created from.
+  /// Returns `None` if the source is too short for this location.
+  pub fn text<'a>(&self, source: &'a str) -> Option<&'a str> {
+    // The end position is the last character in the location, so we need to
+    // skip over that character to find the end of the text.
+    let end = match source.get(self.end.offset..).and_then(|rest| rest.chars().next()) {
+      Some(c) => self.end.offset + c.len_utf8(),
+      None => return None,
+    };
+    source.get(self.start.offset..end)
+  }
+
+  /// Gets the text of this location from the `Source` it refers to. Returns
+  /// `None` if this location does not have a source.
+  pub fn source_text(&self) -> Option<&str> {
+    match self.source {
+      Some(ref source) => self.text(source.body()),
+      None => None,
+    }
+  }
+}
+
+/// A named GraphQL source document.
+///
+/// Locations in a document parsed with `parse_source` refer back to their
+/// `Source`, so that errors and tools can report which document a node came
+/// from. Cloning a `Source` is cheap as the name and body are shared.
+#[derive(Clone, Debug)]
+pub struct Source {
+  inner: Arc<SourceInner>,
+}
+
+#[derive(Debug)]
+struct SourceInner {
+  name: String,
+  body: String,
+}
+
+impl Source {
+  /// Creates a new source document with a name, like a file path or URI,
+  /// and the GraphQL text of the document.
+  pub fn new<N, B>(name: N, body: B) -> Self where N: Into<String>, B: Into<String> {
+    Source {
+      inner: Arc::new(SourceInner {
+        name: name.into(),
+        body: body.into(),
+      }),
     }
   }
+
+  /// The name of this source document.
+  pub fn name(&self) -> &str {
+    &self.inner.name
+  }
+
+  /// The GraphQL text of this source document.
+  pub fn body(&self) -> &str {
+    &self.inner.body
+  }
+}
+
+impl PartialEq for Source {
+  fn eq(&self, other: &Source) -> bool {
+    Arc::ptr_eq(&self.inner, &other.inner) || (self.name() == other.name() && self.body() == other.body())
+  }
+}
+
+impl fmt::Display for Source {
+  /// Writes the name of this source document.
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
 }
@@ -159,9 +159,6 @@ impl Printer {
     self.push(match operation {
       &ast::OperationType::Query => "query",
       &ast::OperationType::Mutation => "mutation",
-
-      // Subscriptions are an expiremental non-spec addition.
-      #[cfg(feature = "subscriptions")]
       &ast::OperationType::Subscription => "subscription",
     });
   }
@@ -174,6 +171,7 @@ impl Printer {
       self.push(" = ");
       self.print_value(default_value);
     }
+    self.print_directives(&node.directives);
   }
 
   fn print_variable(&mut self, node: &ast::Variable) {
@@ -236,6 +234,19 @@ impl Printer {
   fn print_fragment_spread(&mut self, node: &ast::FragmentSpread) {
     self.push("...");
     self.print_name(&node.name);
+    // Fragment arguments are an experimental non-spec addition.
+    #[cfg(feature = "fragment_variables")]
+    {
+      if !node.arguments.is_empty() {
+        self.push("(");
+        self.many(
+          &node.arguments,
+          Printer::print_argument,
+          ", ",
+        );
+        self.push(")");
+      }
+    }
     self.print_directives(&node.directives);
   }
 
@@ -253,6 +264,19 @@ impl Printer {
   fn print_fragment_definition(&mut self, node: &ast::FragmentDefinition) {
     self.push("fragment ");
     self.print_name(&node.name);
+    // Fragment variables are an experimental non-spec addition.
+    #[cfg(feature = "fragment_variables")]
+    {
+      if !node.variable_definitions.is_empty() {
+        self.push("(");
+        self.many(
+          &node.variable_definitions,
+          Printer::print_variable_definition,
+          ", ",
+        );
+        self.push(")");
+      }
+    }
     self.push(" on ");
     self.print_named_type(&node.type_condition);
     self.print_directives(&node.directives);
@@ -267,8 +291,8 @@ impl Printer {
   fn print_value(&mut self, node: &ast::Value) {
     match node {
       &ast::Value::Variable(ref node) => self.print_variable(node),
-      &ast::Value::Int(ref node) => self.push(&node.value.to_string()),
-      &ast::Value::Float(ref node) => self.push(&node.value.to_string()),
+      &ast::Value::Int(ref node) => self.push(&node.value),
+      &ast::Value::Float(ref node) => self.push(&node.value),
       &ast::Value::String(ref node) => self.print_string_value(node),
       &ast::Value::Boolean(ref node) => self.print_boolean_value(node),
       &ast::Value::Null(_) => self.push("null"),
@@ -279,18 +303,65 @@ impl Printer {
   }
 
   fn print_string_value(&mut self, node: &ast::StringValue) {
+    // Block strings stay block strings, and multi-line strings become block
+    // strings, but only if the value would survive being parsed again.
+    if (node.block || node.value.contains('\n')) && is_printable_as_block_string(&node.value) {
+      self.print_block_string(&node.value, true);
+      return;
+    }
     self.push("\"");
     for character in node.value.chars() {
       match character {
         '"' => self.push("\\\""),
         '\\' => self.push("\\\\"),
         '\n' => self.push("\\n"),
+        '\r' => self.push("\\r"),
+        '\t' => self.push("\\t"),
+        '\u{0008}' => self.push("\\b"),
+        '\u{000C}' => self.push("\\f"),
+        // Any other control character would end the string or be rejected by
+        // the lexer, so it must be escaped as a unicode sequence.
+        character @ '\u{0000}'..='\u{001F}' => self.push(&format!("\\u{:04X}", character as u32)),
         character => self.push_char(character),
       }
     }
     self.push("\"");
   }
 
+  /// Prints a value as a block string. If `indent` is true then the lines of
+  /// the block string will be indented one level deeper than the quotes.
+  fn print_block_string(&mut self, value: &str, indent: bool) {
+    let value = value.replace("\"\"\"", "\\\"\"\"");
+    self.push("\"\"\"");
+    // A single line with leading whitespace must stay on the first line,
+    // otherwise the whitespace would be removed as indentation.
+    if !value.contains('\n') && value.starts_with(|c| c == ' ' || c == '\t') {
+      self.push(&value);
+      // Make sure a trailing quote or backslash does not merge with the
+      // closing quotes.
+      if value.ends_with('"') || value.ends_with('\\') {
+        self.line();
+      }
+    } else if !value.is_empty() {
+      if indent {
+        self.indent();
+      }
+      for line in value.split('\n') {
+        if line.is_empty() {
+          self.push("\n");
+        } else {
+          self.line();
+          self.push(line);
+        }
+      }
+      if indent {
+        self.deindent();
+      }
+      self.line();
+    }
+    self.push("\"\"\"");
+  }
+
   fn print_boolean_value(&mut self, node: &ast::BooleanValue) {
     if node.value {
       self.push("true");
@@ -385,6 +456,53 @@ impl Printer {
   }
 }
 
+/// Checks if a string value can be printed as a block string and then parsed
+/// back into exactly the same value. Block strings normalize line endings and
+/// strip indentation along with leading and trailing blank lines, so some
+/// values can only be printed as regular strings.
+fn is_printable_as_block_string(value: &str) -> bool {
+  if value.is_empty() {
+    return true;
+  }
+
+  let mut is_empty_line = true;
+  let mut has_indent = false;
+  let mut has_common_indent = true;
+  let mut seen_non_empty_line = false;
+
+  for character in value.chars() {
+    match character {
+      '\n' => {
+        // Leading blank lines would be removed.
+        if is_empty_line && !seen_non_empty_line {
+          return false;
+        }
+        seen_non_empty_line = true;
+        is_empty_line = true;
+        has_indent = false;
+      },
+      ' ' | '\t' => {
+        has_indent = has_indent || is_empty_line;
+      },
+      // Carriage returns would be normalized into newlines, and other control
+      // characters can not be represented in a block string.
+      '\u{0000}' ... '\u{001F}' => return false,
+      _ => {
+        has_common_indent = has_common_indent && has_indent;
+        is_empty_line = false;
+      },
+    }
+  }
+
+  // Trailing blank lines would be removed.
+  if is_empty_line {
+    return false;
+  }
+
+  // Indentation common to every line would be removed.
+  !(has_common_indent && seen_non_empty_line)
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Type System Definition
 ////////////////////////////////////////////////////////////////////////////////
@@ -403,15 +521,56 @@ impl Printer {
       &Type(Union(ref node)) => self.print_union_type_definition(node),
       &Type(Enum(ref node)) => self.print_enum_type_definition(node),
       &Type(InputObject(ref node)) => self.print_input_object_type_definition(node),
-      &TypeExtension(ref node) => self.print_type_extension_definition(node),
+      &SchemaExtension(ref node) => self.print_schema_extension(node),
+      &TypeExtension(ref node) => self.print_type_extension(node),
       &Directive(ref node) => self.print_directive_definition(node),
     }
   }
 
+  /// Prints the description of a type system definition followed by a new
+  /// line. If there is no description then nothing is printed.
+  fn print_description(&mut self, description: &Option<ast::StringValue>) {
+    if let Some(ref description) = *description {
+      if (description.block || description.value.contains('\n')) && is_printable_as_block_string(&description.value) {
+        self.print_block_string(&description.value, false);
+      } else {
+        self.print_string_value(description);
+      }
+      self.line();
+    }
+  }
+
+  /// Prints the argument definitions for a field or directive. If any of the
+  /// arguments has a description then every argument is printed on its own
+  /// line.
+  fn print_argument_definitions(&mut self, nodes: &Vec<ast::InputValueDefinition>) {
+    if nodes.is_empty() {
+      return;
+    }
+    self.push("(");
+    if nodes.iter().any(|node| node.description.is_some()) {
+      self.indent();
+      for node in nodes {
+        self.line();
+        self.print_input_value_definition(node);
+      }
+      self.deindent();
+      self.line();
+    } else {
+      self.many(
+        nodes,
+        Printer::print_input_value_definition,
+        ", ",
+      );
+    }
+    self.push(")");
+  }
+
   fn print_schema_definition(&mut self, node: &ast::SchemaDefinition) {
-    self.push("schema ");
+    self.print_description(&node.description);
+    self.push("schema");
     self.print_directives(&node.directives);
-    self.push("{");
+    self.push(" {");
     self.indent();
     for operation_type in &node.operation_types {
       self.line();
@@ -429,22 +588,17 @@ impl Printer {
   }
 
   fn print_scalar_type_definition(&mut self, node: &ast::ScalarTypeDefinition) {
+    self.print_description(&node.description);
     self.push("scalar ");
     self.print_name(&node.name);
     self.print_directives(&node.directives);
   }
 
   fn print_object_type_definition(&mut self, node: &ast::ObjectTypeDefinition) {
+    self.print_description(&node.description);
     self.push("type ");
     self.print_name(&node.name);
-    if !node.interfaces.is_empty() {
-      self.push(" implements ");
-      self.many(
-        &node.interfaces,
-        Printer::print_named_type,
-        ", ",
-      );
-    }
+    self.print_implements_interfaces(&node.interfaces);
     self.print_directives(&node.directives);
     self.push(" {");
     if !node.fields.is_empty() {
@@ -459,23 +613,28 @@ impl Printer {
     self.push("}");
   }
 
-  fn print_field_definition(&mut self, node: &ast::FieldDefinition) {
-    self.print_name(&node.name);
-    if !node.arguments.is_empty() {
-      self.push("(");
+  fn print_implements_interfaces(&mut self, interfaces: &Vec<ast::NamedType>) {
+    if !interfaces.is_empty() {
+      self.push(" implements ");
       self.many(
-        &node.arguments,
-        Printer::print_input_value_definition,
-        ", ",
+        interfaces,
+        Printer::print_named_type,
+        " & ",
       );
-      self.push(")");
     }
+  }
+
+  fn print_field_definition(&mut self, node: &ast::FieldDefinition) {
+    self.print_description(&node.description);
+    self.print_name(&node.name);
+    self.print_argument_definitions(&node.arguments);
     self.push(": ");
     self.print_type(&node.typ);
     self.print_directives(&node.directives);
   }
 
   fn print_input_value_definition(&mut self, node: &ast::InputValueDefinition) {
+    self.print_description(&node.description);
     self.print_name(&node.name);
     self.push(": ");
     self.print_type(&node.typ);
@@ -487,8 +646,10 @@ impl Printer {
   }
 
   fn print_interface_type_definition(&mut self, node: &ast::InterfaceTypeDefinition) {
+    self.print_description(&node.description);
     self.push("interface ");
     self.print_name(&node.name);
+    self.print_implements_interfaces(&node.interfaces);
     self.print_directives(&node.directives);
     self.push(" {");
     self.indent();
@@ -502,6 +663,7 @@ impl Printer {
   }
 
   fn print_union_type_definition(&mut self, node: &ast::UnionTypeDefinition) {
+    self.print_description(&node.description);
     self.push("union ");
     self.print_name(&node.name);
     self.print_directives(&node.directives);
@@ -514,6 +676,7 @@ impl Printer {
   }
 
   fn print_enum_type_definition(&mut self, node: &ast::EnumTypeDefinition) {
+    self.print_description(&node.description);
     self.push("enum ");
     self.print_name(&node.name);
     self.print_directives(&node.directives);
@@ -529,11 +692,13 @@ impl Printer {
   }
 
   fn print_enum_value_definition(&mut self, node: &ast::EnumValueDefinition) {
+    self.print_description(&node.description);
     self.print_name(&node.name);
     self.print_directives(&node.directives);
   }
 
   fn print_input_object_type_definition(&mut self, node: &ast::InputObjectTypeDefinition) {
+    self.print_description(&node.description);
     self.push("input ");
     self.print_name(&node.name);
     self.print_directives(&node.directives);
@@ -548,28 +713,140 @@ impl Printer {
     self.push("}");
   }
 
-  fn print_type_extension_definition(&mut self, node: &ast::TypeExtensionDefinition) {
-    self.push("extend ");
-    self.print_object_type_definition(&node.definition);
+  fn print_schema_extension(&mut self, node: &ast::SchemaExtension) {
+    self.push("extend schema");
+    self.print_directives(&node.directives);
+    if !node.operation_types.is_empty() {
+      self.push(" {");
+      self.indent();
+      for operation_type in &node.operation_types {
+        self.line();
+        self.print_operation_type_definition(&operation_type);
+      }
+      self.deindent();
+      self.line();
+      self.push("}");
+    }
   }
 
-  fn print_directive_definition(&mut self, node: &ast::DirectiveDefinition) {
-    self.push("directive @");
+  fn print_type_extension(&mut self, node: &ast::TypeExtension) {
+    match node {
+      &ast::TypeExtension::Scalar(ref node) => self.print_scalar_type_extension(node),
+      &ast::TypeExtension::Object(ref node) => self.print_object_type_extension(node),
+      &ast::TypeExtension::Interface(ref node) => self.print_interface_type_extension(node),
+      &ast::TypeExtension::Union(ref node) => self.print_union_type_extension(node),
+      &ast::TypeExtension::Enum(ref node) => self.print_enum_type_extension(node),
+      &ast::TypeExtension::InputObject(ref node) => self.print_input_object_type_extension(node),
+    }
+  }
+
+  fn print_scalar_type_extension(&mut self, node: &ast::ScalarTypeExtension) {
+    self.push("extend scalar ");
     self.print_name(&node.name);
-    if !node.arguments.is_empty() {
-      self.push("(");
+    self.print_directives(&node.directives);
+  }
+
+  fn print_object_type_extension(&mut self, node: &ast::ObjectTypeExtension) {
+    self.push("extend type ");
+    self.print_name(&node.name);
+    self.print_implements_interfaces(&node.interfaces);
+    self.print_directives(&node.directives);
+    if !node.fields.is_empty() {
+      self.push(" {");
+      self.indent();
+      for field in &node.fields {
+        self.line();
+        self.print_field_definition(&field);
+      }
+      self.deindent();
+      self.line();
+      self.push("}");
+    }
+  }
+
+  fn print_interface_type_extension(&mut self, node: &ast::InterfaceTypeExtension) {
+    self.push("extend interface ");
+    self.print_name(&node.name);
+    self.print_implements_interfaces(&node.interfaces);
+    self.print_directives(&node.directives);
+    if !node.fields.is_empty() {
+      self.push(" {");
+      self.indent();
+      for field in &node.fields {
+        self.line();
+        self.print_field_definition(&field);
+      }
+      self.deindent();
+      self.line();
+      self.push("}");
+    }
+  }
+
+  fn print_union_type_extension(&mut self, node: &ast::UnionTypeExtension) {
+    self.push("extend union ");
+    self.print_name(&node.name);
+    self.print_directives(&node.directives);
+    if !node.types.is_empty() {
+      self.push(" = ");
       self.many(
-        &node.arguments,
-        Printer::print_input_value_definition,
-        ", ",
+        &node.types,
+        Printer::print_named_type,
+        " | ",
       );
-      self.push(")");
+    }
+  }
+
+  fn print_enum_type_extension(&mut self, node: &ast::EnumTypeExtension) {
+    self.push("extend enum ");
+    self.print_name(&node.name);
+    self.print_directives(&node.directives);
+    if !node.values.is_empty() {
+      self.push(" {");
+      self.indent();
+      for value in &node.values {
+        self.line();
+        self.print_enum_value_definition(&value);
+      }
+      self.deindent();
+      self.line();
+      self.push("}");
+    }
+  }
+
+  fn print_input_object_type_extension(&mut self, node: &ast::InputObjectTypeExtension) {
+    self.push("extend input ");
+    self.print_name(&node.name);
+    self.print_directives(&node.directives);
+    if !node.fields.is_empty() {
+      self.push(" {");
+      self.indent();
+      for field in &node.fields {
+        self.line();
+        self.print_input_value_definition(&field);
+      }
+      self.deindent();
+      self.line();
+      self.push("}");
+    }
+  }
+
+  fn print_directive_definition(&mut self, node: &ast::DirectiveDefinition) {
+    self.print_description(&node.description);
+    self.push("directive @");
+    self.print_name(&node.name);
+    self.print_argument_definitions(&node.arguments);
+    if node.repeatable {
+      self.push(" repeatable");
     }
     self.push(" on ");
     self.many(
       &node.locations,
-      Printer::print_name,
+      Printer::print_directive_location,
       " | ",
     );
   }
+
+  fn print_directive_location(&mut self, location: &ast::DirectiveLocation) {
+    self.push(location.name());
+  }
 }
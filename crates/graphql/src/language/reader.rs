@@ -0,0 +1,186 @@
+use std::io::{self, Read};
+use std::str;
+
+/// The number of bytes we try to read from a reader at a time.
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// An iterator over the characters of UTF-8 text from a reader. The bytes are
+/// read and decoded a buffer at a time, so the text never needs to be in
+/// memory all at once.
+///
+/// If reading fails, or the text is not valid UTF-8, the iterator ends early.
+/// Use `take_error` afterwards to find out if that happened.
+pub struct ReadChars<R> {
+  reader: R,
+  buffer: Vec<u8>,
+  /// The index of the first byte in the buffer which has not been decoded.
+  start: usize,
+  /// The index just past the last byte read into the buffer.
+  end: usize,
+  /// The byte offset in the text of the first byte in the buffer.
+  offset: usize,
+  /// The error which ended the iterator early, if any.
+  error: Option<io::Error>,
+}
+
+impl<R> ReadChars<R> where R: Read {
+  /// Creates an iterator over the characters of a reader.
+  pub fn new(reader: R) -> Self {
+    ReadChars {
+      reader: reader,
+      buffer: vec![0; BUFFER_SIZE],
+      start: 0,
+      end: 0,
+      offset: 0,
+      error: None,
+    }
+  }
+
+  /// Takes the error which ended the iterator early. Returns `None` if the
+  /// iterator has not ended or ended at the end of the text. Invalid UTF-8 is
+  /// an error of the kind `InvalidData`, just like with `read_to_string`.
+  pub fn take_error(&mut self) -> Option<io::Error> {
+    self.error.take()
+  }
+
+  /// Reads more bytes into the buffer after the bytes which have not been
+  /// decoded yet. Returns false at the end of the text.
+  fn fill(&mut self) -> io::Result<bool> {
+    if self.start > 0 {
+      for i in self.start..self.end {
+        self.buffer[i - self.start] = self.buffer[i];
+      }
+      self.offset += self.start;
+      self.end -= self.start;
+      self.start = 0;
+    }
+    loop {
+      match self.reader.read(&mut self.buffer[self.end..]) {
+        Ok(0) => return Ok(false),
+        Ok(read) => {
+          self.end += read;
+          return Ok(true);
+        },
+        Err(ref error) if error.kind() == io::ErrorKind::Interrupted => (),
+        Err(error) => return Err(error),
+      }
+    }
+  }
+
+  /// Decodes the next character, reading more bytes if we need them.
+  fn decode(&mut self) -> io::Result<Option<char>> {
+    if self.start == self.end && !try!(self.fill()) {
+      return Ok(None);
+    }
+    let width = utf8_width(self.buffer[self.start]);
+    while self.end - self.start < width {
+      if !try!(self.fill()) {
+        break;
+      }
+    }
+    let bytes = &self.buffer[self.start..self.end.min(self.start + width)];
+    match str::from_utf8(bytes).ok().and_then(|text| text.chars().next()) {
+      Some(c) if width > 0 => {
+        self.start += width;
+        Ok(Some(c))
+      },
+      _ => Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("stream did not contain valid UTF-8 at byte {}", self.offset + self.start),
+      )),
+    }
+  }
+}
+
+impl<R> Iterator for ReadChars<R> where R: Read {
+  type Item = char;
+
+  fn next(&mut self) -> Option<char> {
+    if self.error.is_some() {
+      return None;
+    }
+    match self.decode() {
+      Ok(c) => c,
+      Err(error) => {
+        self.error = Some(error);
+        None
+      },
+    }
+  }
+}
+
+/// The number of bytes in a UTF-8 character with this first byte. Returns 0
+/// if no character starts with this byte.
+fn utf8_width(byte: u8) -> usize {
+  match byte {
+    0x00...0x7F => 1,
+    0xC2...0xDF => 2,
+    0xE0...0xEF => 3,
+    0xF0...0xF4 => 4,
+    _ => 0,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::{self, Read};
+  use super::ReadChars;
+
+  /// A reader which reads one byte at a time, and then fails if it has an
+  /// error.
+  struct Trickle<'a> {
+    bytes: &'a [u8],
+    error: Option<io::ErrorKind>,
+  }
+
+  impl<'a> Read for Trickle<'a> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+      match self.bytes.split_first() {
+        Some((&byte, rest)) => {
+          buffer[0] = byte;
+          self.bytes = rest;
+          Ok(1)
+        },
+        None => match self.error {
+          Some(kind) => Err(io::Error::new(kind, "trickle")),
+          None => Ok(0),
+        },
+      }
+    }
+  }
+
+  #[test]
+  fn test_read_chars() {
+    let text = "{ a(b: \"ü€😀\") }";
+    assert_eq!(ReadChars::new(text.as_bytes()).collect::<String>(), text);
+    let mut chars = ReadChars::new(Trickle { bytes: text.as_bytes(), error: None });
+    assert_eq!(chars.by_ref().collect::<String>(), text);
+    assert!(chars.take_error().is_none());
+  }
+
+  #[test]
+  fn test_read_chars_long() {
+    let text = "é".repeat(10000);
+    assert_eq!(ReadChars::new(text.as_bytes()).collect::<String>(), text);
+  }
+
+  #[test]
+  fn test_read_chars_invalid() {
+    let mut chars = ReadChars::new(&b"ab\xC3(c"[..]);
+    assert_eq!(chars.by_ref().collect::<String>(), "ab");
+    let error = chars.take_error().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "stream did not contain valid UTF-8 at byte 2");
+    let mut chars = ReadChars::new(Trickle { bytes: b"a\xE2\x82", error: None });
+    assert_eq!(chars.by_ref().collect::<String>(), "a");
+    assert_eq!(chars.take_error().map(|error| error.kind()), Some(io::ErrorKind::InvalidData));
+  }
+
+  #[test]
+  fn test_read_chars_error() {
+    let mut chars = ReadChars::new(Trickle { bytes: b"ab", error: Some(io::ErrorKind::BrokenPipe) });
+    assert_eq!(chars.by_ref().collect::<String>(), "ab");
+    assert_eq!(chars.take_error().map(|error| error.kind()), Some(io::ErrorKind::BrokenPipe));
+    assert_eq!(chars.next(), None);
+  }
+}
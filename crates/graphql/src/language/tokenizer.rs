@@ -0,0 +1,215 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use super::Position;
+use super::lexer::{self, Lexer, StrInput, TokenKind, Error};
+
+/// Splits a source text into every piece of source text it contains, including
+/// the whitespace, commas and comments that the parser ignores.
+///
+/// Every character of the source is in exactly one lexeme, so joining the text
+/// of all lexemes gives back the source. Lexical errors don’t stop the
+/// tokenizer, instead the offending characters become an error lexeme and
+/// tokenizing continues after them. This makes the tokenizer a good fit for
+/// syntax highlighters and minifiers, which must handle any text.
+pub fn tokenize<'a>(source: &'a str) -> Tokenizer<'a> {
+  Tokenizer {
+    source: source,
+    lexer: Lexer::borrowed(source),
+    pos: Position {
+      index: 0,
+      offset: 0,
+      line: 1,
+      column: 1,
+    },
+    pending: VecDeque::new(),
+    done: false,
+  }
+}
+
+/// A piece of source text from the tokenizer.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Lexeme<'a> {
+  /// What kind of source text this is.
+  pub kind: LexemeKind<'a>,
+  /// The exact source text of this lexeme.
+  pub text: &'a str,
+  /// The position of the first character of this lexeme.
+  pub start: Position,
+  /// The position of the last character of this lexeme.
+  pub end: Position,
+}
+
+/// The kind of a `Lexeme`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum LexemeKind<'a> {
+  /// A token, exactly as the lexer used by the parser produces it. This
+  /// includes comments.
+  Token(TokenKind<Cow<'a, str>>),
+  /// A run of spaces, tabs, line terminators and byte order marks.
+  Whitespace,
+  /// A comma, which GraphQL ignores just like whitespace.
+  Comma,
+  /// Characters which could not be lexed, along with the error they caused.
+  Error(Error),
+}
+
+/// An iterator over the lexemes of a source text. Created with `tokenize`.
+pub struct Tokenizer<'a> {
+  source: &'a str,
+  lexer: Lexer<StrInput<'a>>,
+  /// The position of the first character which has not been put in a lexeme
+  /// yet.
+  pos: Position,
+  /// Lexemes which have been found but not yet returned.
+  pending: VecDeque<Lexeme<'a>>,
+  done: bool,
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+  type Item = Lexeme<'a>;
+
+  fn next(&mut self) -> Option<Lexeme<'a>> {
+    if let Some(lexeme) = self.pending.pop_front() {
+      return Some(lexeme);
+    }
+    if self.done {
+      return None;
+    }
+    match self.lexer.next() {
+      Some(Ok(token)) => {
+        let end = self.end_offset(token.end);
+        self.push_ignored(token.start.offset);
+        self.push(LexemeKind::Token(token.kind), end);
+      },
+      // The lexer skipped any ignored characters before the error, and then
+      // consumed the offending characters.
+      Some(Err(error)) => {
+        self.lexer.resume();
+        let end = self.end_offset(self.lexer.pos());
+        let start = self.source[self.pos.offset..end].find(|c| !is_ignored(c)).map_or(end, |start| self.pos.offset + start);
+        self.push_ignored(start);
+        self.push(LexemeKind::Error(error), end);
+      },
+      None => {
+        let end = self.source.len();
+        self.push_ignored(end);
+        self.done = true;
+      },
+    }
+    self.pending.pop_front()
+  }
+}
+
+impl<'a> Tokenizer<'a> {
+  /// The byte offset just past the character at a position.
+  fn end_offset(&self, pos: Position) -> usize {
+    let end = self.source[pos.offset..].chars().next().map_or(0, char::len_utf8);
+    (pos.offset + end).max(self.pos.offset)
+  }
+
+  /// Pushes a lexeme of the source text from our position up to the end
+  /// offset, and moves our position past it.
+  fn push(&mut self, kind: LexemeKind<'a>, end: usize) {
+    let text = &self.source[self.pos.offset..end];
+    let start = self.pos;
+    let mut last = self.pos;
+    for c in text.chars() {
+      last = self.pos;
+      // Move our position to the next character, unless this was the last
+      // character in the source.
+      match self.source[self.pos.offset + c.len_utf8()..].chars().next() {
+        Some(next) => lexer::advance(&mut self.pos, c, next),
+        None => self.pos.offset += c.len_utf8(),
+      }
+    }
+    self.pending.push_back(Lexeme {
+      kind: kind,
+      text: text,
+      start: start,
+      end: last,
+    });
+  }
+
+  /// Pushes whitespace and comma lexemes for the ignored source text from our
+  /// position up to the end offset.
+  fn push_ignored(&mut self, end: usize) {
+    while self.pos.offset < end {
+      let rest = &self.source[self.pos.offset..end];
+      if rest.starts_with(',') {
+        let comma = self.pos.offset + 1;
+        self.push(LexemeKind::Comma, comma);
+      } else {
+        let whitespace = rest.find(',').map_or(end, |comma| self.pos.offset + comma);
+        self.push(LexemeKind::Whitespace, whitespace);
+      }
+    }
+  }
+}
+
+/// Whether a character is ignored by the lexer between tokens.
+fn is_ignored(c: char) -> bool {
+  matches!(c, '\u{FEFF}' | '\t' | ' ' | ',' | '\n' | '\r')
+}
+
+#[cfg(test)]
+mod tests {
+  use std::borrow::Cow;
+  use super::super::Position;
+  use super::super::lexer::{TokenKind, Error};
+  use super::{tokenize, LexemeKind};
+
+  fn pos1(index: usize) -> Position {
+    Position {
+      index: index,
+      offset: index,
+      line: 1,
+      column: index + 1,
+    }
+  }
+
+  #[test]
+  fn test_lexemes() {
+    let lexemes = tokenize("{ a,, # b\r\n}").map(|lexeme| (lexeme.kind, lexeme.text, lexeme.start, lexeme.end)).collect::<Vec<_>>();
+    assert_eq!(lexemes, vec![
+      (LexemeKind::Token(TokenKind::LeftBrace), "{", pos1(0), pos1(0)),
+      (LexemeKind::Whitespace, " ", pos1(1), pos1(1)),
+      (LexemeKind::Token(TokenKind::Name(Cow::Borrowed("a"))), "a", pos1(2), pos1(2)),
+      (LexemeKind::Comma, ",", pos1(3), pos1(3)),
+      (LexemeKind::Comma, ",", pos1(4), pos1(4)),
+      (LexemeKind::Whitespace, " ", pos1(5), pos1(5)),
+      (LexemeKind::Token(TokenKind::Comment(Cow::Borrowed(" b"))), "# b", pos1(6), pos1(8)),
+      (LexemeKind::Whitespace, "\r\n", Position { index: 9, offset: 9, line: 2, column: 0 }, Position { index: 10, offset: 10, line: 2, column: 0 }),
+      (LexemeKind::Token(TokenKind::RightBrace), "}", Position { index: 11, offset: 11, line: 2, column: 1 }, Position { index: 11, offset: 11, line: 2, column: 1 }),
+    ]);
+  }
+
+  #[test]
+  fn test_errors() {
+    let lexemes = tokenize("a %é b \"c").map(|lexeme| (lexeme.kind, lexeme.text)).collect::<Vec<_>>();
+    assert_eq!(lexemes, vec![
+      (LexemeKind::Token(TokenKind::Name(Cow::Borrowed("a"))), "a"),
+      (LexemeKind::Whitespace, " "),
+      (LexemeKind::Error(Error::UnknownChar(pos1(2), '%')), "%"),
+      (LexemeKind::Error(Error::UnknownChar(Position { index: 3, offset: 3, line: 1, column: 4 }, 'é')), "é"),
+      (LexemeKind::Whitespace, " "),
+      (LexemeKind::Token(TokenKind::Name(Cow::Borrowed("b"))), "b"),
+      (LexemeKind::Whitespace, " "),
+      (LexemeKind::Error(Error::UnexpectedEnding(Position { index: 8, offset: 9, line: 1, column: 9 })), "\"c"),
+    ]);
+  }
+
+  #[test]
+  fn test_lossless() {
+    let sources = [
+      "",
+      "   ",
+      "\u{FEFF}query Q($a: [Int!]! = [1, 2]) @d { a: b(c: \"é\\n\") ...F # 😀\r\n}",
+      "{ a(b: \"\"\"\n  block \\\"\"\"\n\"\"\") }",
+      "{ 01 1.x ..a \"\\q\" \"unterminated\n ! }",
+    ];
+    for source in &sources {
+      assert_eq!(tokenize(source).map(|lexeme| lexeme.text).collect::<String>(), *source);
+    }
+  }
+}
@@ -49,6 +49,30 @@ pub trait Visitor {
   fn_visits!(NamedType, enter_named_type, leave_named_type);
   fn_visits!(ListType, enter_list_type, leave_list_type);
   fn_visits!(NonNullType, enter_non_null_type, leave_non_null_type);
+
+  // The type system AST extension is an experimental non-spec addition.
+  #[cfg(feature = "type_system")] fn_visits!(TypeSystemDefinition, enter_type_system_definition, leave_type_system_definition);
+  #[cfg(feature = "type_system")] fn_visits!(SchemaDefinition, enter_schema_definition, leave_schema_definition);
+  #[cfg(feature = "type_system")] fn_visits!(OperationTypeDefinition, enter_operation_type_definition, leave_operation_type_definition);
+  #[cfg(feature = "type_system")] fn_visits!(TypeDefinition, enter_type_definition, leave_type_definition);
+  #[cfg(feature = "type_system")] fn_visits!(ScalarTypeDefinition, enter_scalar_type_definition, leave_scalar_type_definition);
+  #[cfg(feature = "type_system")] fn_visits!(ObjectTypeDefinition, enter_object_type_definition, leave_object_type_definition);
+  #[cfg(feature = "type_system")] fn_visits!(FieldDefinition, enter_field_definition, leave_field_definition);
+  #[cfg(feature = "type_system")] fn_visits!(InputValueDefinition, enter_input_value_definition, leave_input_value_definition);
+  #[cfg(feature = "type_system")] fn_visits!(InterfaceTypeDefinition, enter_interface_type_definition, leave_interface_type_definition);
+  #[cfg(feature = "type_system")] fn_visits!(UnionTypeDefinition, enter_union_type_definition, leave_union_type_definition);
+  #[cfg(feature = "type_system")] fn_visits!(EnumTypeDefinition, enter_enum_type_definition, leave_enum_type_definition);
+  #[cfg(feature = "type_system")] fn_visits!(EnumValueDefinition, enter_enum_value_definition, leave_enum_value_definition);
+  #[cfg(feature = "type_system")] fn_visits!(InputObjectTypeDefinition, enter_input_object_type_definition, leave_input_object_type_definition);
+  #[cfg(feature = "type_system")] fn_visits!(SchemaExtension, enter_schema_extension, leave_schema_extension);
+  #[cfg(feature = "type_system")] fn_visits!(TypeExtension, enter_type_extension, leave_type_extension);
+  #[cfg(feature = "type_system")] fn_visits!(ScalarTypeExtension, enter_scalar_type_extension, leave_scalar_type_extension);
+  #[cfg(feature = "type_system")] fn_visits!(ObjectTypeExtension, enter_object_type_extension, leave_object_type_extension);
+  #[cfg(feature = "type_system")] fn_visits!(InterfaceTypeExtension, enter_interface_type_extension, leave_interface_type_extension);
+  #[cfg(feature = "type_system")] fn_visits!(UnionTypeExtension, enter_union_type_extension, leave_union_type_extension);
+  #[cfg(feature = "type_system")] fn_visits!(EnumTypeExtension, enter_enum_type_extension, leave_enum_type_extension);
+  #[cfg(feature = "type_system")] fn_visits!(InputObjectTypeExtension, enter_input_object_type_extension, leave_input_object_type_extension);
+  #[cfg(feature = "type_system")] fn_visits!(DirectiveDefinition, enter_directive_definition, leave_directive_definition);
 }
 
 /// A visitor that will take any number of other visitors and execute their
@@ -119,6 +143,30 @@ impl<'a> Visitor for ParallelVisitor<'a> {
   fn_visits_parallel!(NamedType, enter_named_type, leave_named_type);
   fn_visits_parallel!(ListType, enter_list_type, leave_list_type);
   fn_visits_parallel!(NonNullType, enter_non_null_type, leave_non_null_type);
+
+  // The type system AST extension is an experimental non-spec addition.
+  #[cfg(feature = "type_system")] fn_visits_parallel!(TypeSystemDefinition, enter_type_system_definition, leave_type_system_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(SchemaDefinition, enter_schema_definition, leave_schema_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(OperationTypeDefinition, enter_operation_type_definition, leave_operation_type_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(TypeDefinition, enter_type_definition, leave_type_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(ScalarTypeDefinition, enter_scalar_type_definition, leave_scalar_type_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(ObjectTypeDefinition, enter_object_type_definition, leave_object_type_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(FieldDefinition, enter_field_definition, leave_field_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(InputValueDefinition, enter_input_value_definition, leave_input_value_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(InterfaceTypeDefinition, enter_interface_type_definition, leave_interface_type_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(UnionTypeDefinition, enter_union_type_definition, leave_union_type_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(EnumTypeDefinition, enter_enum_type_definition, leave_enum_type_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(EnumValueDefinition, enter_enum_value_definition, leave_enum_value_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(InputObjectTypeDefinition, enter_input_object_type_definition, leave_input_object_type_definition);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(SchemaExtension, enter_schema_extension, leave_schema_extension);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(TypeExtension, enter_type_extension, leave_type_extension);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(ScalarTypeExtension, enter_scalar_type_extension, leave_scalar_type_extension);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(ObjectTypeExtension, enter_object_type_extension, leave_object_type_extension);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(InterfaceTypeExtension, enter_interface_type_extension, leave_interface_type_extension);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(UnionTypeExtension, enter_union_type_extension, leave_union_type_extension);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(EnumTypeExtension, enter_enum_type_extension, leave_enum_type_extension);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(InputObjectTypeExtension, enter_input_object_type_extension, leave_input_object_type_extension);
+  #[cfg(feature = "type_system")] fn_visits_parallel!(DirectiveDefinition, enter_directive_definition, leave_directive_definition);
 }
 
 // Below this comment is the actual visiting implementation code.
@@ -162,6 +210,10 @@ fn visit_definition<V: Visitor>(v: &mut V, _node: Definition) -> Definition {
   node = match node {
     Definition::Operation(operation) => Definition::Operation(visit_operation_definition(v, operation)),
     Definition::Fragment(fragment) => Definition::Fragment(visit_fragment_definition(v, fragment)),
+
+    // The type system AST extension is an experimental non-spec addition.
+    #[cfg(feature = "type_system")]
+    Definition::TypeSystem(type_system) => Definition::TypeSystem(visit_type_system_definition(v, type_system)),
   };
   v.leave_definition(node)
 }
@@ -182,6 +234,7 @@ fn visit_variable_definition<V: Visitor>(v: &mut V, _node: VariableDefinition) -
   node.variable = visit_variable(v, node.variable);
   node.type_ = visit_type(v, node.type_);
   node.default_value = node.default_value.map(|default_value| visit_value(v, default_value));
+  visit_many!(visit_directive, v, node.directives);
   v.leave_variable_definition(node)
 }
 
@@ -237,6 +290,8 @@ fn visit_fragment_spread<V: Visitor>(v: &mut V, _node: FragmentSpread) -> Fragme
   let mut node = _node;
   node = v.enter_fragment_spread(node);
   node.name = visit_name(v, node.name);
+  #[cfg(feature = "fragment_variables")]
+  visit_many!(visit_argument, v, node.arguments);
   visit_many!(visit_directive, v, node.directives);
   v.leave_fragment_spread(node)
 }
@@ -254,6 +309,8 @@ fn visit_fragment_definition<V: Visitor>(v: &mut V, _node: FragmentDefinition) -
   let mut node = _node;
   node = v.enter_fragment_definition(node);
   node.name = visit_name(v, node.name);
+  #[cfg(feature = "fragment_variables")]
+  visit_many!(visit_variable_definition, v, node.variable_definitions);
   node.type_condition = visit_named_type(v, node.type_condition);
   visit_many!(visit_directive, v, node.directives);
   node.selection_set = visit_selection_set(v, node.selection_set);
@@ -396,3 +453,252 @@ fn visit_non_null_type<V: Visitor>(v: &mut V, _node: NonNullType) -> NonNullType
   node.type_ = Box::new(visit_nullable_type(v, *node.type_));
   v.leave_non_null_type(node)
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// Type System Definition
+////////////////////////////////////////////////////////////////////////////////
+//
+// The type system AST extension is an experimental non-spec addition.
+
+#[cfg(feature = "type_system")]
+fn visit_type_system_definition<V: Visitor>(v: &mut V, _node: TypeSystemDefinition) -> TypeSystemDefinition {
+  let mut node = _node;
+  node = v.enter_type_system_definition(node);
+  node = match node {
+    TypeSystemDefinition::Schema(node) => TypeSystemDefinition::Schema(visit_schema_definition(v, node)),
+    TypeSystemDefinition::Type(node) => TypeSystemDefinition::Type(visit_type_definition(v, node)),
+    TypeSystemDefinition::SchemaExtension(node) => TypeSystemDefinition::SchemaExtension(visit_schema_extension(v, node)),
+    TypeSystemDefinition::TypeExtension(node) => TypeSystemDefinition::TypeExtension(visit_type_extension(v, node)),
+    TypeSystemDefinition::Directive(node) => TypeSystemDefinition::Directive(visit_directive_definition(v, node)),
+  };
+  v.leave_type_system_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_schema_definition<V: Visitor>(v: &mut V, _node: SchemaDefinition) -> SchemaDefinition {
+  let mut node = _node;
+  node = v.enter_schema_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_operation_type_definition, v, node.operation_types);
+  v.leave_schema_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_operation_type_definition<V: Visitor>(v: &mut V, _node: OperationTypeDefinition) -> OperationTypeDefinition {
+  let mut node = _node;
+  node = v.enter_operation_type_definition(node);
+  node.typ = visit_named_type(v, node.typ);
+  v.leave_operation_type_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_type_definition<V: Visitor>(v: &mut V, _node: TypeDefinition) -> TypeDefinition {
+  let mut node = _node;
+  node = v.enter_type_definition(node);
+  node = match node {
+    TypeDefinition::Scalar(node) => TypeDefinition::Scalar(visit_scalar_type_definition(v, node)),
+    TypeDefinition::Object(node) => TypeDefinition::Object(visit_object_type_definition(v, node)),
+    TypeDefinition::Interface(node) => TypeDefinition::Interface(visit_interface_type_definition(v, node)),
+    TypeDefinition::Union(node) => TypeDefinition::Union(visit_union_type_definition(v, node)),
+    TypeDefinition::Enum(node) => TypeDefinition::Enum(visit_enum_type_definition(v, node)),
+    TypeDefinition::InputObject(node) => TypeDefinition::InputObject(visit_input_object_type_definition(v, node)),
+  };
+  v.leave_type_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_scalar_type_definition<V: Visitor>(v: &mut V, _node: ScalarTypeDefinition) -> ScalarTypeDefinition {
+  let mut node = _node;
+  node = v.enter_scalar_type_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  v.leave_scalar_type_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_object_type_definition<V: Visitor>(v: &mut V, _node: ObjectTypeDefinition) -> ObjectTypeDefinition {
+  let mut node = _node;
+  node = v.enter_object_type_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_named_type, v, node.interfaces);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_field_definition, v, node.fields);
+  v.leave_object_type_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_field_definition<V: Visitor>(v: &mut V, _node: FieldDefinition) -> FieldDefinition {
+  let mut node = _node;
+  node = v.enter_field_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_input_value_definition, v, node.arguments);
+  node.typ = visit_type(v, node.typ);
+  visit_many!(visit_directive, v, node.directives);
+  v.leave_field_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_input_value_definition<V: Visitor>(v: &mut V, _node: InputValueDefinition) -> InputValueDefinition {
+  let mut node = _node;
+  node = v.enter_input_value_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  node.typ = visit_type(v, node.typ);
+  node.default_value = node.default_value.map(|default_value| visit_value(v, default_value));
+  visit_many!(visit_directive, v, node.directives);
+  v.leave_input_value_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_interface_type_definition<V: Visitor>(v: &mut V, _node: InterfaceTypeDefinition) -> InterfaceTypeDefinition {
+  let mut node = _node;
+  node = v.enter_interface_type_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_named_type, v, node.interfaces);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_field_definition, v, node.fields);
+  v.leave_interface_type_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_union_type_definition<V: Visitor>(v: &mut V, _node: UnionTypeDefinition) -> UnionTypeDefinition {
+  let mut node = _node;
+  node = v.enter_union_type_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_named_type, v, node.types);
+  v.leave_union_type_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_enum_type_definition<V: Visitor>(v: &mut V, _node: EnumTypeDefinition) -> EnumTypeDefinition {
+  let mut node = _node;
+  node = v.enter_enum_type_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_enum_value_definition, v, node.values);
+  v.leave_enum_type_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_enum_value_definition<V: Visitor>(v: &mut V, _node: EnumValueDefinition) -> EnumValueDefinition {
+  let mut node = _node;
+  node = v.enter_enum_value_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  v.leave_enum_value_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_input_object_type_definition<V: Visitor>(v: &mut V, _node: InputObjectTypeDefinition) -> InputObjectTypeDefinition {
+  let mut node = _node;
+  node = v.enter_input_object_type_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_input_value_definition, v, node.fields);
+  v.leave_input_object_type_definition(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_schema_extension<V: Visitor>(v: &mut V, _node: SchemaExtension) -> SchemaExtension {
+  let mut node = _node;
+  node = v.enter_schema_extension(node);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_operation_type_definition, v, node.operation_types);
+  v.leave_schema_extension(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_type_extension<V: Visitor>(v: &mut V, _node: TypeExtension) -> TypeExtension {
+  let mut node = _node;
+  node = v.enter_type_extension(node);
+  node = match node {
+    TypeExtension::Scalar(node) => TypeExtension::Scalar(visit_scalar_type_extension(v, node)),
+    TypeExtension::Object(node) => TypeExtension::Object(visit_object_type_extension(v, node)),
+    TypeExtension::Interface(node) => TypeExtension::Interface(visit_interface_type_extension(v, node)),
+    TypeExtension::Union(node) => TypeExtension::Union(visit_union_type_extension(v, node)),
+    TypeExtension::Enum(node) => TypeExtension::Enum(visit_enum_type_extension(v, node)),
+    TypeExtension::InputObject(node) => TypeExtension::InputObject(visit_input_object_type_extension(v, node)),
+  };
+  v.leave_type_extension(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_scalar_type_extension<V: Visitor>(v: &mut V, _node: ScalarTypeExtension) -> ScalarTypeExtension {
+  let mut node = _node;
+  node = v.enter_scalar_type_extension(node);
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  v.leave_scalar_type_extension(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_object_type_extension<V: Visitor>(v: &mut V, _node: ObjectTypeExtension) -> ObjectTypeExtension {
+  let mut node = _node;
+  node = v.enter_object_type_extension(node);
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_named_type, v, node.interfaces);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_field_definition, v, node.fields);
+  v.leave_object_type_extension(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_interface_type_extension<V: Visitor>(v: &mut V, _node: InterfaceTypeExtension) -> InterfaceTypeExtension {
+  let mut node = _node;
+  node = v.enter_interface_type_extension(node);
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_named_type, v, node.interfaces);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_field_definition, v, node.fields);
+  v.leave_interface_type_extension(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_union_type_extension<V: Visitor>(v: &mut V, _node: UnionTypeExtension) -> UnionTypeExtension {
+  let mut node = _node;
+  node = v.enter_union_type_extension(node);
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_named_type, v, node.types);
+  v.leave_union_type_extension(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_enum_type_extension<V: Visitor>(v: &mut V, _node: EnumTypeExtension) -> EnumTypeExtension {
+  let mut node = _node;
+  node = v.enter_enum_type_extension(node);
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_enum_value_definition, v, node.values);
+  v.leave_enum_type_extension(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_input_object_type_extension<V: Visitor>(v: &mut V, _node: InputObjectTypeExtension) -> InputObjectTypeExtension {
+  let mut node = _node;
+  node = v.enter_input_object_type_extension(node);
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_directive, v, node.directives);
+  visit_many!(visit_input_value_definition, v, node.fields);
+  v.leave_input_object_type_extension(node)
+}
+
+#[cfg(feature = "type_system")]
+fn visit_directive_definition<V: Visitor>(v: &mut V, _node: DirectiveDefinition) -> DirectiveDefinition {
+  let mut node = _node;
+  node = v.enter_directive_definition(node);
+  node.description = node.description.map(|description| visit_string_value(v, description));
+  node.name = visit_name(v, node.name);
+  visit_many!(visit_input_value_definition, v, node.arguments);
+  v.leave_directive_definition(node)
+}
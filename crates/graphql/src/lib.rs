@@ -1,2 +1,8 @@
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+#[macro_use]
+extern crate serde_json;
+
 pub mod language;
 pub mod types;
@@ -2,18 +2,33 @@
 
 extern crate graphql;
 
-use graphql::language::{parse_without_location, print};
+use graphql::language::{parse, parse_without_location, parse_borrowed, print, tokenize, LexemeKind};
 use graphql::language::ast::*;
 
 const SOURCE: &'static str =
-r#"schema {
+r#""This is a description of the schema as a whole."
+schema {
   query: QueryType
   mutation: MutationType
+  subscription: SubscriptionType
 }
 
-type Foo implements Bar {
+"""
+This is a description
+of the `Foo` type.
+"""
+type Foo implements Bar & Baz {
+  "Description of the `one` field."
   one: Type
-  two(argument: InputType!): Type
+  """
+  This is a description of the `two` field.
+  """
+  two(
+    """
+    This is a description of the `argument` argument.
+    """
+    argument: InputType!
+  ): Type
   three(argument: InputType, other: String): Int
   four(argument: String = "string"): String
   five(argument: [String] = ["string", "string"]): String
@@ -30,6 +45,10 @@ interface Bar {
   four(argument: String = "string"): String
 }
 
+interface Baz implements Bar & Node {
+  one: Type
+}
+
 interface AnnotatedInterface @onInterface {
   annotatedField(arg: Type @onArg): Type @onField
 }
@@ -43,6 +62,7 @@ scalar CustomScalar
 scalar AnnotatedScalar @onScalar
 
 enum Site {
+  "The desktop site."
   DESKTOP
   MOBILE
 }
@@ -65,10 +85,43 @@ extend type Foo {
   seven(argument: [String]): Type
 }
 
-extend type Foo @onType {}
+extend type Foo @onType
+
+extend interface Bar {
+  five: Type
+}
+
+extend interface Bar @onInterface
+
+extend interface Bar implements Node
+
+extend union Feed = Photo | Video
+
+extend union Feed @onUnion
+
+extend scalar CustomScalar @onScalar
+
+extend enum Site {
+  VR
+}
+
+extend enum Site @onEnum
+
+extend input InputType {
+  other: String
+}
+
+extend input InputType @onInputObjectType
+
+extend schema @onSchema
+
+extend schema {
+  mutation: OtherMutationType
+}
 
 type NoFields {}
 
+"Directs the executor to skip this field or fragment when the `if` argument is true."
 directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
 
 directive @include(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
@@ -85,6 +138,31 @@ fn kitchen_sink_schema_parse_and_print_2x() {
   assert_eq!(print1, print2);
 }
 
+#[test]
+fn kitchen_sink_schema_parse_borrowed() {
+  let borrowed = parse_borrowed(SOURCE).unwrap();
+  assert_eq!(Ok(borrowed.into_owned()), parse(SOURCE.chars()));
+}
+
+#[test]
+fn kitchen_sink_schema_line_terminators() {
+  let expected = parse(SOURCE.chars()).unwrap();
+  let lines = |document: &Document| document.definitions.iter().map(|definition| definition.loc().map(|loc| (loc.start.line, loc.end.line))).collect::<Vec<_>>();
+  for source in &[SOURCE.replace('\n', "\r\n"), SOURCE.replace('\n', "\r"), format!("\u{FEFF}{}", SOURCE.replace('\n', "\r\n"))] {
+    let document = parse(source.chars()).unwrap();
+    assert_eq!(lines(&document), lines(&expected));
+    assert_eq!(parse_without_location(source.chars()).unwrap(), parse_without_location(SOURCE.chars()).unwrap());
+    assert_eq!(parse_borrowed(source).unwrap().into_owned(), document);
+  }
+}
+
+#[test]
+fn kitchen_sink_schema_tokenize() {
+  let lexemes = tokenize(SOURCE).collect::<Vec<_>>();
+  assert_eq!(lexemes.iter().map(|lexeme| lexeme.text).collect::<String>(), SOURCE);
+  assert!(lexemes.iter().all(|lexeme| !matches!(lexeme.kind, LexemeKind::Error(_))));
+}
+
 #[test]
 fn kitchen_sink_schema_ast() {
   let document = Document {
@@ -92,6 +170,11 @@ fn kitchen_sink_schema_ast() {
     definitions: vec![
       Definition::TypeSystem(TypeSystemDefinition::Schema(SchemaDefinition {
         loc: None,
+        description: Some(StringValue {
+          loc: None,
+          value: String::from("This is a description of the schema as a whole."),
+          block: false,
+        }),
         directives: vec![],
         operation_types: vec![
           OperationTypeDefinition {
@@ -116,10 +199,26 @@ fn kitchen_sink_schema_ast() {
               },
             },
           },
+          OperationTypeDefinition {
+            loc: None,
+            operation: OperationType::Subscription,
+            typ: NamedType {
+              loc: None,
+              name: Name {
+                loc: None,
+                value: String::from("SubscriptionType"),
+              },
+            },
+          },
         ],
       })),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Object(ObjectTypeDefinition {
         loc: None,
+        description: Some(StringValue {
+          loc: None,
+          value: String::from("This is a description\nof the `Foo` type."),
+          block: true,
+        }),
         name: Name {
           loc: None,
           value: String::from("Foo"),
@@ -132,11 +231,23 @@ fn kitchen_sink_schema_ast() {
               value: String::from("Bar"),
             },
           },
+          NamedType {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("Baz"),
+            },
+          },
         ],
         directives: vec![],
         fields: vec![
           FieldDefinition {
             loc: None,
+            description: Some(StringValue {
+              loc: None,
+              value: String::from("Description of the `one` field."),
+              block: false,
+            }),
             name: Name {
               loc: None,
               value: String::from("one"),
@@ -153,6 +264,11 @@ fn kitchen_sink_schema_ast() {
           },
           FieldDefinition {
             loc: None,
+            description: Some(StringValue {
+              loc: None,
+              value: String::from("This is a description of the `two` field."),
+              block: true,
+            }),
             name: Name {
               loc: None,
               value: String::from("two"),
@@ -160,13 +276,18 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: Some(StringValue {
+                  loc: None,
+                  value: String::from("This is a description of the `argument` argument."),
+                  block: true,
+                }),
                 name: Name {
                   loc: None,
                   value: String::from("argument"),
                 },
                 typ: Type::NonNull(NonNullType {
                   loc: None,
-                  typ: Box::new(NullableType::Named(NamedType {
+                  type_: Box::new(NullableType::Named(NamedType {
                     loc: None,
                     name: Name {
                       loc: None,
@@ -189,6 +310,7 @@ fn kitchen_sink_schema_ast() {
           },
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("three"),
@@ -196,6 +318,7 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("argument"),
@@ -212,6 +335,7 @@ fn kitchen_sink_schema_ast() {
               },
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("other"),
@@ -238,6 +362,7 @@ fn kitchen_sink_schema_ast() {
           },
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("four"),
@@ -245,6 +370,7 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("argument"),
@@ -259,6 +385,7 @@ fn kitchen_sink_schema_ast() {
                 default_value: Some(Value::String(StringValue {
                   loc: None,
                   value: String::from("string"),
+                  block: false,
                 })),
                 directives: vec![],
               },
@@ -274,6 +401,7 @@ fn kitchen_sink_schema_ast() {
           },
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("five"),
@@ -281,13 +409,14 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("argument"),
                 },
                 typ: Type::List(ListType {
                   loc: None,
-                  typ: Box::new(Type::Named(NamedType {
+                  type_: Box::new(Type::Named(NamedType {
                     loc: None,
                     name: Name {
                       loc: None,
@@ -301,10 +430,12 @@ fn kitchen_sink_schema_ast() {
                     Value::String(StringValue {
                       loc: None,
                       value: String::from("string"),
+                      block: false,
                     }),
                     Value::String(StringValue {
                       loc: None,
                       value: String::from("string"),
+                      block: false,
                     }),
                   ],
                 })),
@@ -322,6 +453,7 @@ fn kitchen_sink_schema_ast() {
           },
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("six"),
@@ -329,6 +461,7 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("argument"),
@@ -352,6 +485,7 @@ fn kitchen_sink_schema_ast() {
                       value: Value::String(StringValue {
                         loc: None,
                         value: String::from("value"),
+                        block: false,
                       }),
                     },
                   ],
@@ -370,6 +504,7 @@ fn kitchen_sink_schema_ast() {
           },
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("seven"),
@@ -377,6 +512,7 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("argument"),
@@ -407,6 +543,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Object(ObjectTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("AnnotatedObject"),
@@ -429,6 +566,7 @@ fn kitchen_sink_schema_ast() {
                 value: Value::String(StringValue {
                   loc: None,
                   value: String::from("value"),
+                  block: false,
                 }),
               },
             ],
@@ -437,6 +575,7 @@ fn kitchen_sink_schema_ast() {
         fields: vec![
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("annotatedField"),
@@ -444,6 +583,7 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("arg"),
@@ -458,6 +598,7 @@ fn kitchen_sink_schema_ast() {
                 default_value: Some(Value::String(StringValue {
                   loc: None,
                   value: String::from("default"),
+                  block: false,
                 })),
                 directives: vec![
                   Directive {
@@ -493,14 +634,17 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Interface(InterfaceTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("Bar"),
         },
+        interfaces: vec![],
         directives: vec![],
         fields: vec![
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("one"),
@@ -517,6 +661,7 @@ fn kitchen_sink_schema_ast() {
           },
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("four"),
@@ -524,6 +669,7 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("argument"),
@@ -538,6 +684,7 @@ fn kitchen_sink_schema_ast() {
                 default_value: Some(Value::String(StringValue {
                   loc: None,
                   value: String::from("string"),
+                  block: false,
                 })),
                 directives: vec![],
               },
@@ -555,10 +702,56 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Interface(InterfaceTypeDefinition {
         loc: None,
+        description: None,
+        name: Name {
+          loc: None,
+          value: String::from("Baz"),
+        },
+        interfaces: vec![
+          NamedType {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("Bar"),
+            },
+          },
+          NamedType {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("Node"),
+            },
+          },
+        ],
+        directives: vec![],
+        fields: vec![
+          FieldDefinition {
+            loc: None,
+            description: None,
+            name: Name {
+              loc: None,
+              value: String::from("one"),
+            },
+            arguments: vec![],
+            typ: Type::Named(NamedType {
+              loc: None,
+              name: Name {
+                loc: None,
+                value: String::from("Type"),
+              },
+            }),
+            directives: vec![],
+          },
+        ],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Interface(InterfaceTypeDefinition {
+        loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("AnnotatedInterface"),
         },
+        interfaces: vec![],
         directives: vec![
           Directive {
             loc: None,
@@ -572,6 +765,7 @@ fn kitchen_sink_schema_ast() {
         fields: vec![
           FieldDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("annotatedField"),
@@ -579,6 +773,7 @@ fn kitchen_sink_schema_ast() {
             arguments: vec![
               InputValueDefinition {
                 loc: None,
+                description: None,
                 name: Name {
                   loc: None,
                   value: String::from("arg"),
@@ -625,6 +820,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Union(UnionTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("Feed"),
@@ -656,6 +852,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Union(UnionTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("AnnotatedUnion"),
@@ -689,6 +886,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Scalar(ScalarTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("CustomScalar"),
@@ -697,6 +895,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Scalar(ScalarTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("AnnotatedScalar"),
@@ -714,6 +913,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Enum(EnumTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("Site"),
@@ -722,6 +922,11 @@ fn kitchen_sink_schema_ast() {
         values: vec![
           EnumValueDefinition {
             loc: None,
+            description: Some(StringValue {
+              loc: None,
+              value: String::from("The desktop site."),
+              block: false,
+            }),
             name: Name {
               loc: None,
               value: String::from("DESKTOP"),
@@ -730,6 +935,7 @@ fn kitchen_sink_schema_ast() {
           },
           EnumValueDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("MOBILE"),
@@ -740,6 +946,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Enum(EnumTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("AnnotatedEnum"),
@@ -757,6 +964,7 @@ fn kitchen_sink_schema_ast() {
         values: vec![
           EnumValueDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("ANNOTATED_VALUE"),
@@ -774,6 +982,7 @@ fn kitchen_sink_schema_ast() {
           },
           EnumValueDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("OTHER_VALUE"),
@@ -784,6 +993,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::InputObject(InputObjectTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("InputType"),
@@ -792,13 +1002,14 @@ fn kitchen_sink_schema_ast() {
         fields: vec![
           InputValueDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("key"),
             },
             typ: Type::NonNull(NonNullType {
               loc: None,
-              typ: Box::new(NullableType::Named(NamedType {
+              type_: Box::new(NullableType::Named(NamedType {
                 loc: None,
                 name: Name {
                   loc: None,
@@ -811,6 +1022,7 @@ fn kitchen_sink_schema_ast() {
           },
           InputValueDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("answer"),
@@ -824,7 +1036,7 @@ fn kitchen_sink_schema_ast() {
             }),
             default_value: Some(Value::Int(IntValue {
               loc: None,
-              value: 42,
+              value: String::from("42"),
             })),
             directives: vec![],
           },
@@ -832,6 +1044,7 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::InputObject(InputObjectTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("AnnotatedInput"),
@@ -849,6 +1062,7 @@ fn kitchen_sink_schema_ast() {
         fields: vec![
           InputValueDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("annotatedField"),
@@ -874,80 +1088,314 @@ fn kitchen_sink_schema_ast() {
           },
         ],
       }))),
-      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtensionDefinition {
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Object(ObjectTypeExtension {
         loc: None,
-        definition: ObjectTypeDefinition {
+        name: Name {
           loc: None,
-          name: Name {
+          value: String::from("Foo"),
+        },
+        interfaces: vec![],
+        directives: vec![],
+        fields: vec![
+          FieldDefinition {
             loc: None,
-            value: String::from("Foo"),
-          },
-          interfaces: vec![],
-          directives: vec![],
-          fields: vec![
-            FieldDefinition {
+            description: None,
+            name: Name {
               loc: None,
-              name: Name {
+              value: String::from("seven"),
+            },
+            arguments: vec![
+              InputValueDefinition {
                 loc: None,
-                value: String::from("seven"),
-              },
-              arguments: vec![
-                InputValueDefinition {
+                description: None,
+                name: Name {
                   loc: None,
-                  name: Name {
-                    loc: None,
-                    value: String::from("argument"),
-                  },
-                  typ: Type::List(ListType {
+                  value: String::from("argument"),
+                },
+                typ: Type::List(ListType {
+                  loc: None,
+                  type_: Box::new(Type::Named(NamedType {
                     loc: None,
-                    typ: Box::new(Type::Named(NamedType {
+                    name: Name {
                       loc: None,
-                      name: Name {
-                        loc: None,
-                        value: String::from("String"),
-                      },
-                    })),
-                  }),
-                  default_value: None,
-                  directives: vec![],
-                },
-              ],
-              typ: Type::Named(NamedType {
+                      value: String::from("String"),
+                    },
+                  })),
+                }),
+                default_value: None,
+                directives: vec![],
+              },
+            ],
+            typ: Type::Named(NamedType {
+              loc: None,
+              name: Name {
                 loc: None,
-                name: Name {
-                  loc: None,
-                  value: String::from("Type"),
-                },
-              }),
-              directives: vec![],
+                value: String::from("Type"),
+              },
+            }),
+            directives: vec![],
+          },
+        ],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Object(ObjectTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("Foo"),
+        },
+        interfaces: vec![],
+        directives: vec![
+          Directive {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("onType"),
             },
-          ],
+            arguments: vec![],
+          },
+        ],
+        fields: vec![],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Interface(InterfaceTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("Bar"),
         },
-      })),
-      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtensionDefinition {
+        interfaces: vec![],
+        directives: vec![],
+        fields: vec![
+          FieldDefinition {
+            loc: None,
+            description: None,
+            name: Name {
+              loc: None,
+              value: String::from("five"),
+            },
+            arguments: vec![],
+            typ: Type::Named(NamedType {
+              loc: None,
+              name: Name {
+                loc: None,
+                value: String::from("Type"),
+              },
+            }),
+            directives: vec![],
+          },
+        ],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Interface(InterfaceTypeExtension {
         loc: None,
-        definition: ObjectTypeDefinition {
+        name: Name {
           loc: None,
-          name: Name {
+          value: String::from("Bar"),
+        },
+        interfaces: vec![],
+        directives: vec![
+          Directive {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("onInterface"),
+            },
+            arguments: vec![],
+          },
+        ],
+        fields: vec![],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Interface(InterfaceTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("Bar"),
+        },
+        interfaces: vec![
+          NamedType {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("Node"),
+            },
+          },
+        ],
+        directives: vec![],
+        fields: vec![],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Union(UnionTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("Feed"),
+        },
+        directives: vec![],
+        types: vec![
+          NamedType {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("Photo"),
+            },
+          },
+          NamedType {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("Video"),
+            },
+          },
+        ],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Union(UnionTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("Feed"),
+        },
+        directives: vec![
+          Directive {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("onUnion"),
+            },
+            arguments: vec![],
+          },
+        ],
+        types: vec![],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Scalar(ScalarTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("CustomScalar"),
+        },
+        directives: vec![
+          Directive {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("onScalar"),
+            },
+            arguments: vec![],
+          },
+        ],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Enum(EnumTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("Site"),
+        },
+        directives: vec![],
+        values: vec![
+          EnumValueDefinition {
             loc: None,
-            value: String::from("Foo"),
+            description: None,
+            name: Name {
+              loc: None,
+              value: String::from("VR"),
+            },
+            directives: vec![],
           },
-          interfaces: vec![],
-          directives: vec![
-            Directive {
+        ],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::Enum(EnumTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("Site"),
+        },
+        directives: vec![
+          Directive {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("onEnum"),
+            },
+            arguments: vec![],
+          },
+        ],
+        values: vec![],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::InputObject(InputObjectTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("InputType"),
+        },
+        directives: vec![],
+        fields: vec![
+          InputValueDefinition {
+            loc: None,
+            description: None,
+            name: Name {
+              loc: None,
+              value: String::from("other"),
+            },
+            typ: Type::Named(NamedType {
               loc: None,
               name: Name {
                 loc: None,
-                value: String::from("onType"),
+                value: String::from("String"),
               },
-              arguments: vec![],
+            }),
+            default_value: None,
+            directives: vec![],
+          },
+        ],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::TypeExtension(TypeExtension::InputObject(InputObjectTypeExtension {
+        loc: None,
+        name: Name {
+          loc: None,
+          value: String::from("InputType"),
+        },
+        directives: vec![
+          Directive {
+            loc: None,
+            name: Name {
+              loc: None,
+              value: String::from("onInputObjectType"),
             },
-          ],
-          fields: vec![],
+            arguments: vec![],
+          },
+        ],
+        fields: vec![],
+      }))),
+      Definition::TypeSystem(TypeSystemDefinition::SchemaExtension(SchemaExtension {
+        loc: None,
+        directives: vec![
+        Directive {
+          loc: None,
+          name: Name {
+            loc: None,
+            value: String::from("onSchema"),
+          },
+          arguments: vec![],
         },
+      ],
+        operation_types: vec![],
+      })),
+      Definition::TypeSystem(TypeSystemDefinition::SchemaExtension(SchemaExtension {
+        loc: None,
+        directives: vec![],
+        operation_types: vec![
+          OperationTypeDefinition {
+            loc: None,
+            operation: OperationType::Mutation,
+            typ: NamedType {
+              loc: None,
+              name: Name {
+                loc: None,
+                value: String::from("OtherMutationType"),
+              },
+            },
+          },
+        ],
       })),
       Definition::TypeSystem(TypeSystemDefinition::Type(TypeDefinition::Object(ObjectTypeDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("NoFields"),
@@ -958,6 +1406,11 @@ fn kitchen_sink_schema_ast() {
       }))),
       Definition::TypeSystem(TypeSystemDefinition::Directive(DirectiveDefinition {
         loc: None,
+        description: Some(StringValue {
+          loc: None,
+          value: String::from("Directs the executor to skip this field or fragment when the `if` argument is true."),
+          block: false,
+        }),
         name: Name {
           loc: None,
           value: String::from("skip"),
@@ -965,13 +1418,14 @@ fn kitchen_sink_schema_ast() {
         arguments: vec![
           InputValueDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("if"),
             },
             typ: Type::NonNull(NonNullType {
               loc: None,
-              typ: Box::new(NullableType::Named(NamedType {
+              type_: Box::new(NullableType::Named(NamedType {
                 loc: None,
                 name: Name {
                   loc: None,
@@ -983,23 +1437,16 @@ fn kitchen_sink_schema_ast() {
             directives: vec![],
           },
         ],
+        repeatable: false,
         locations: vec![
-          Name {
-            loc: None,
-            value: String::from("FIELD"),
-          },
-          Name {
-            loc: None,
-            value: String::from("FRAGMENT_SPREAD"),
-          },
-          Name {
-            loc: None,
-            value: String::from("INLINE_FRAGMENT"),
-          },
+          DirectiveLocation::Field,
+          DirectiveLocation::FragmentSpread,
+          DirectiveLocation::InlineFragment,
         ],
       })),
       Definition::TypeSystem(TypeSystemDefinition::Directive(DirectiveDefinition {
         loc: None,
+        description: None,
         name: Name {
           loc: None,
           value: String::from("include"),
@@ -1007,13 +1454,14 @@ fn kitchen_sink_schema_ast() {
         arguments: vec![
           InputValueDefinition {
             loc: None,
+            description: None,
             name: Name {
               loc: None,
               value: String::from("if"),
             },
             typ: Type::NonNull(NonNullType {
               loc: None,
-              typ: Box::new(NullableType::Named(NamedType {
+              type_: Box::new(NullableType::Named(NamedType {
                 loc: None,
                 name: Name {
                   loc: None,
@@ -1025,19 +1473,11 @@ fn kitchen_sink_schema_ast() {
             directives: vec![],
           },
         ],
+        repeatable: false,
         locations: vec![
-          Name {
-            loc: None,
-            value: String::from("FIELD"),
-          },
-          Name {
-            loc: None,
-            value: String::from("FRAGMENT_SPREAD"),
-          },
-          Name {
-            loc: None,
-            value: String::from("INLINE_FRAGMENT"),
-          },
+          DirectiveLocation::Field,
+          DirectiveLocation::FragmentSpread,
+          DirectiveLocation::InlineFragment,
         ],
       })),
     ],
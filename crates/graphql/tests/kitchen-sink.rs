@@ -1,6 +1,6 @@
 extern crate graphql;
 
-use graphql::language::{parse_without_location, print};
+use graphql::language::{parse, parse_without_location, parse_borrowed, print, tokenize, LexemeKind};
 use graphql::language::ast::*;
 
 const SOURCE: &'static str =
@@ -33,7 +33,7 @@ mutation likeStory {
   }
 }
 
-query StoryLikeSubscription($input: StoryLikeSubscribeInput) {
+subscription StoryLikeSubscription($input: StoryLikeSubscribeInput) {
   storyLikeSubscribe(input: $input) {
     story {
       likers {
@@ -47,11 +47,13 @@ query StoryLikeSubscription($input: StoryLikeSubscribeInput) {
 }
 
 fragment frag on Friend {
-  foo(size: $size, bar: $b, obj: {key: "value"})
+  foo(size: $size, bar: $b, obj: {key: "value", block: """
+    block string uses \"""
+  """})
 }
 
 {
-  unnamed(truthy: true, falsey: false, nullish: null)
+  unnamed(truthy: true, falsey: false, nullish: null, long: 9007199254740993, ratio: 0.1, avogadro: 6.02214076E23)
   query
 }
 "#;
@@ -67,6 +69,31 @@ fn kitchen_sink_parse_and_print_2x() {
   assert_eq!(print1, print2);
 }
 
+#[test]
+fn kitchen_sink_parse_borrowed() {
+  let borrowed = parse_borrowed(SOURCE).unwrap();
+  assert_eq!(Ok(borrowed.into_owned()), parse(SOURCE.chars()));
+}
+
+#[test]
+fn kitchen_sink_line_terminators() {
+  let expected = parse(SOURCE.chars()).unwrap();
+  let lines = |document: &Document| document.definitions.iter().map(|definition| definition.loc().map(|loc| (loc.start.line, loc.end.line))).collect::<Vec<_>>();
+  for source in &[SOURCE.replace('\n', "\r\n"), SOURCE.replace('\n', "\r"), format!("\u{FEFF}{}", SOURCE.replace('\n', "\r\n"))] {
+    let document = parse(source.chars()).unwrap();
+    assert_eq!(lines(&document), lines(&expected));
+    assert_eq!(parse_without_location(source.chars()).unwrap(), parse_without_location(SOURCE.chars()).unwrap());
+    assert_eq!(parse_borrowed(source).unwrap().into_owned(), document);
+  }
+}
+
+#[test]
+fn kitchen_sink_tokenize() {
+  let lexemes = tokenize(SOURCE).collect::<Vec<_>>();
+  assert_eq!(lexemes.iter().map(|lexeme| lexeme.text).collect::<String>(), SOURCE);
+  assert!(lexemes.iter().all(|lexeme| !matches!(lexeme.kind, LexemeKind::Error(_))));
+}
+
 #[test]
 fn kitchen_sink_ast() {
   let document = Document {
@@ -89,7 +116,7 @@ fn kitchen_sink_ast() {
                 value: String::from("foo"),
               },
             },
-            typ: Type::Named(NamedType {
+            type_: Type::Named(NamedType {
               loc: None,
               name: Name {
                 loc: None,
@@ -97,6 +124,7 @@ fn kitchen_sink_ast() {
               },
             }),
             default_value: None,
+            directives: vec![],
           },
           VariableDefinition {
             loc: None,
@@ -107,7 +135,7 @@ fn kitchen_sink_ast() {
                 value: String::from("site"),
               },
             },
-            typ: Type::Named(NamedType {
+            type_: Type::Named(NamedType {
               loc: None,
               name: Name {
                 loc: None,
@@ -118,6 +146,7 @@ fn kitchen_sink_ast() {
               loc: None,
               value: String::from("MOBILE"),
             })),
+            directives: vec![],
           },
         ],
         directives: vec![],
@@ -146,11 +175,11 @@ fn kitchen_sink_ast() {
                     values: vec![
                       Value::Int(IntValue {
                         loc: None,
-                        value: 123,
+                        value: String::from("123"),
                       }),
                       Value::Int(IntValue {
                         loc: None,
-                        value: 456,
+                        value: String::from("456"),
                       }),
                     ],
                   }),
@@ -235,7 +264,7 @@ fn kitchen_sink_ast() {
                                     },
                                     value: Value::Int(IntValue {
                                       loc: None,
-                                      value: 10,
+                                      value: String::from("10"),
                                     }),
                                   },
                                   Argument {
@@ -298,6 +327,8 @@ fn kitchen_sink_ast() {
                                         loc: None,
                                         value: String::from("frag"),
                                       },
+                                      #[cfg(feature = "fragment_variables")]
+                                      arguments: vec![],
                                       directives: vec![],
                                     }),
                                   ],
@@ -409,7 +440,7 @@ fn kitchen_sink_ast() {
                   },
                   value: Value::Int(IntValue {
                     loc: None,
-                    value: 123,
+                    value: String::from("123"),
                   }),
                 },
               ],
@@ -460,7 +491,7 @@ fn kitchen_sink_ast() {
       }),
       Definition::Operation(OperationDefinition {
         loc: None,
-        operation: OperationType::Query,
+        operation: OperationType::Subscription,
         name: Some(Name {
           loc: None,
           value: String::from("StoryLikeSubscription"),
@@ -475,7 +506,7 @@ fn kitchen_sink_ast() {
                 value: String::from("input"),
               },
             },
-            typ: Type::Named(NamedType {
+            type_: Type::Named(NamedType {
               loc: None,
               name: Name {
                 loc: None,
@@ -483,6 +514,7 @@ fn kitchen_sink_ast() {
               },
             }),
             default_value: None,
+            directives: vec![],
           },
         ],
         directives: vec![],
@@ -595,6 +627,8 @@ fn kitchen_sink_ast() {
           loc: None,
           value: String::from("frag"),
         },
+        #[cfg(feature = "fragment_variables")]
+        variable_definitions: vec![],
         type_condition: NamedType {
           loc: None,
           name: Name {
@@ -660,6 +694,19 @@ fn kitchen_sink_ast() {
                         value: Value::String(StringValue {
                           loc: None,
                           value: String::from("value"),
+                          block: false,
+                        }),
+                      },
+                      ObjectField {
+                        loc: None,
+                        name: Name {
+                          loc: None,
+                          value: String::from("block"),
+                        },
+                        value: Value::String(StringValue {
+                          loc: None,
+                          value: String::from("block string uses \"\"\""),
+                          block: true,
                         }),
                       },
                     ],
@@ -721,6 +768,39 @@ fn kitchen_sink_ast() {
                     loc: None,
                   }),
                 },
+                Argument {
+                  loc: None,
+                  name: Name {
+                    loc: None,
+                    value: String::from("long"),
+                  },
+                  value: Value::Int(IntValue {
+                    loc: None,
+                    value: String::from("9007199254740993"),
+                  }),
+                },
+                Argument {
+                  loc: None,
+                  name: Name {
+                    loc: None,
+                    value: String::from("ratio"),
+                  },
+                  value: Value::Float(FloatValue {
+                    loc: None,
+                    value: String::from("0.1"),
+                  }),
+                },
+                Argument {
+                  loc: None,
+                  name: Name {
+                    loc: None,
+                    value: String::from("avogadro"),
+                  },
+                  value: Value::Float(FloatValue {
+                    loc: None,
+                    value: String::from("6.02214076E23"),
+                  }),
+                },
               ],
               directives: vec![],
               selection_set: None,
@@ -0,0 +1,472 @@
+use graphql::language::ast;
+
+/// A node which can be written as the Rust code that constructs it. Nodes
+/// are constructed with the constructors and builder methods of the AST, so
+/// the code does not depend on which fields the features of the `graphql`
+/// crate add. Nodes are constructed without locations.
+pub trait Expand {
+  /// Writes the Rust code that constructs this node.
+  fn expand(&self, code: &mut String);
+}
+
+/// Writes a call to a function of the AST module, like `Field::new`.
+fn call(code: &mut String, function: &str, arguments: &[&dyn Expand]) {
+  code.push_str("::graphql::language::ast::");
+  code.push_str(function);
+  code.push('(');
+  for (i, argument) in arguments.iter().enumerate() {
+    if i > 0 {
+      code.push_str(", ");
+    }
+    argument.expand(code);
+  }
+  code.push(')');
+}
+
+/// Writes a call to a builder method for every node in a list.
+fn method_each<T>(code: &mut String, method: &str, nodes: &[T]) where T: Expand {
+  for node in nodes {
+    code.push('.');
+    code.push_str(method);
+    code.push('(');
+    node.expand(code);
+    code.push(')');
+  }
+}
+
+/// Writes a call to a builder method if there is a node.
+fn method_some<T>(code: &mut String, method: &str, node: &Option<T>) where T: Expand {
+  if let Some(ref node) = *node {
+    method_each(code, method, &[node]);
+  }
+}
+
+impl<T> Expand for &T where T: Expand {
+  fn expand(&self, code: &mut String) {
+    (**self).expand(code);
+  }
+}
+
+impl<T> Expand for Box<T> where T: Expand {
+  fn expand(&self, code: &mut String) {
+    (**self).expand(code);
+  }
+}
+
+/// Text is written as a string literal, which the constructors convert.
+impl Expand for String {
+  fn expand(&self, code: &mut String) {
+    // The debug format of a string is a valid Rust string literal.
+    code.push_str(&format!("{:?}", self));
+  }
+}
+
+/// Names are written as string literals, which the constructors convert.
+impl Expand for ast::Name {
+  fn expand(&self, code: &mut String) {
+    self.value.expand(code);
+  }
+}
+
+impl Expand for ast::OperationType {
+  fn expand(&self, code: &mut String) {
+    code.push_str(&format!("::graphql::language::ast::OperationType::{:?}", self));
+  }
+}
+
+impl Expand for ast::DirectiveLocation {
+  fn expand(&self, code: &mut String) {
+    code.push_str(&format!("::graphql::language::ast::DirectiveLocation::{:?}", self));
+  }
+}
+
+/// Implements `Expand` for a node enum by converting the node of the variant
+/// into the enum.
+macro_rules! expand_enum {
+  ($enum_name:ident { $($variant_name:ident,)* }) => (
+    impl Expand for ast::$enum_name {
+      fn expand(&self, code: &mut String) {
+        match *self {
+          $(
+            ast::$enum_name::$variant_name(ref node) => call(code, concat!(stringify!($enum_name), "::from"), &[node]),
+          )*
+        }
+      }
+    }
+  )
+}
+
+impl Expand for ast::Document {
+  fn expand(&self, code: &mut String) {
+    call(code, "Document::new", &[]);
+    method_each(code, "definition", &self.definitions);
+  }
+}
+
+impl Expand for ast::Definition {
+  fn expand(&self, code: &mut String) {
+    match *self {
+      ast::Definition::Operation(ref node) => call(code, "Definition::from", &[node]),
+      ast::Definition::Fragment(ref node) => call(code, "Definition::from", &[node]),
+
+      // The type system AST extension is an experimental non-spec addition.
+      #[cfg(feature = "type_system")]
+      ast::Definition::TypeSystem(ref node) => call(code, "Definition::from", &[node]),
+    }
+  }
+}
+
+impl Expand for ast::OperationDefinition {
+  fn expand(&self, code: &mut String) {
+    call(code, "OperationDefinition::new", &[&self.operation]);
+    method_some(code, "name", &self.name);
+    method_each(code, "variable", &self.variable_definitions);
+    method_each(code, "directive", &self.directives);
+    method_each(code, "select", &self.selection_set.selections);
+  }
+}
+
+impl Expand for ast::VariableDefinition {
+  fn expand(&self, code: &mut String) {
+    call(code, "VariableDefinition::new", &[&self.variable.name, &self.type_]);
+    method_some(code, "default_value", &self.default_value);
+    method_each(code, "directive", &self.directives);
+  }
+}
+
+expand_enum!(Selection { Field, FragmentSpread, InlineFragment, });
+
+impl Expand for ast::Field {
+  fn expand(&self, code: &mut String) {
+    call(code, "Field::new", &[&self.name]);
+    method_some(code, "alias", &self.alias);
+    method_each(code, "arg", &self.arguments);
+    method_each(code, "directive", &self.directives);
+    if let Some(ref selection_set) = self.selection_set {
+      method_each(code, "select", &selection_set.selections);
+    }
+  }
+}
+
+/// Arguments are written as the arguments of the `arg` builder method.
+impl Expand for ast::Argument {
+  fn expand(&self, code: &mut String) {
+    self.name.expand(code);
+    code.push_str(", ");
+    self.value.expand(code);
+  }
+}
+
+impl Expand for ast::FragmentSpread {
+  fn expand(&self, code: &mut String) {
+    call(code, "FragmentSpread::new", &[&self.name]);
+    // Fragment variables are an experimental non-spec addition.
+    #[cfg(feature = "fragment_variables")]
+    method_each(code, "arg", &self.arguments);
+    method_each(code, "directive", &self.directives);
+  }
+}
+
+impl Expand for ast::InlineFragment {
+  fn expand(&self, code: &mut String) {
+    call(code, "InlineFragment::new", &[]);
+    method_some(code, "on", &self.type_condition);
+    method_each(code, "directive", &self.directives);
+    method_each(code, "select", &self.selection_set.selections);
+  }
+}
+
+impl Expand for ast::FragmentDefinition {
+  fn expand(&self, code: &mut String) {
+    call(code, "FragmentDefinition::new", &[&self.name, &self.type_condition]);
+    // Fragment variables are an experimental non-spec addition.
+    #[cfg(feature = "fragment_variables")]
+    method_each(code, "variable", &self.variable_definitions);
+    method_each(code, "directive", &self.directives);
+    method_each(code, "select", &self.selection_set.selections);
+  }
+}
+
+expand_enum!(Value { Variable, Int, Float, String, Boolean, Null, Enum, List, Object, });
+
+impl Expand for ast::Variable {
+  fn expand(&self, code: &mut String) {
+    call(code, "Variable::new", &[&self.name]);
+  }
+}
+
+impl Expand for ast::IntValue {
+  fn expand(&self, code: &mut String) {
+    call(code, "IntValue::new", &[&self.value]);
+  }
+}
+
+impl Expand for ast::FloatValue {
+  fn expand(&self, code: &mut String) {
+    call(code, "FloatValue::new", &[&self.value]);
+  }
+}
+
+impl Expand for ast::StringValue {
+  fn expand(&self, code: &mut String) {
+    call(code, if self.block { "StringValue::block" } else { "StringValue::new" }, &[&self.value]);
+  }
+}
+
+impl Expand for ast::BooleanValue {
+  fn expand(&self, code: &mut String) {
+    code.push_str(&format!("::graphql::language::ast::BooleanValue::new({})", self.value));
+  }
+}
+
+impl Expand for ast::NullValue {
+  fn expand(&self, code: &mut String) {
+    call(code, "NullValue::new", &[]);
+  }
+}
+
+impl Expand for ast::EnumValue {
+  fn expand(&self, code: &mut String) {
+    call(code, "EnumValue::new", &[&self.value]);
+  }
+}
+
+impl Expand for ast::ListValue {
+  fn expand(&self, code: &mut String) {
+    code.push_str("::graphql::language::ast::ListValue::new(<[::graphql::language::ast::Value]>::into_vec(::std::boxed::Box::new([");
+    for value in &self.values {
+      value.expand(code);
+      code.push_str(", ");
+    }
+    code.push_str("])))");
+  }
+}
+
+impl Expand for ast::ObjectValue {
+  fn expand(&self, code: &mut String) {
+    call(code, "ObjectValue::new", &[]);
+    method_each(code, "field", &self.fields);
+  }
+}
+
+/// Object fields are written as the arguments of the `field` builder method.
+impl Expand for ast::ObjectField {
+  fn expand(&self, code: &mut String) {
+    self.name.expand(code);
+    code.push_str(", ");
+    self.value.expand(code);
+  }
+}
+
+impl Expand for ast::Directive {
+  fn expand(&self, code: &mut String) {
+    call(code, "Directive::new", &[&self.name]);
+    method_each(code, "arg", &self.arguments);
+  }
+}
+
+expand_enum!(Type { Named, List, NonNull, });
+expand_enum!(NullableType { Named, List, });
+
+impl Expand for ast::NamedType {
+  fn expand(&self, code: &mut String) {
+    call(code, "NamedType::new", &[&self.name]);
+  }
+}
+
+impl Expand for ast::ListType {
+  fn expand(&self, code: &mut String) {
+    call(code, "ListType::new", &[&self.type_]);
+  }
+}
+
+impl Expand for ast::NonNullType {
+  fn expand(&self, code: &mut String) {
+    call(code, "NonNullType::new", &[&self.type_]);
+  }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Type System Definition
+////////////////////////////////////////////////////////////////////////////////
+//
+// The type system AST extension is an experimental non-spec addition.
+#[cfg(feature = "type_system")]
+mod type_system {
+  use graphql::language::ast;
+
+  use super::{Expand, call, method_each, method_some};
+
+  expand_enum!(TypeSystemDefinition { Schema, Type, SchemaExtension, TypeExtension, Directive, });
+  expand_enum!(TypeDefinition { Scalar, Object, Interface, Union, Enum, InputObject, });
+  expand_enum!(TypeExtension { Scalar, Object, Interface, Union, Enum, InputObject, });
+
+  impl Expand for ast::SchemaDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "SchemaDefinition::new", &[]);
+      method_some(code, "description", &self.description);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "operation", &self.operation_types);
+    }
+  }
+
+  /// Operation types are written as the arguments of the `operation` builder
+  /// method.
+  impl Expand for ast::OperationTypeDefinition {
+    fn expand(&self, code: &mut String) {
+      self.operation.expand(code);
+      code.push_str(", ");
+      self.typ.expand(code);
+    }
+  }
+
+  impl Expand for ast::ScalarTypeDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "ScalarTypeDefinition::new", &[&self.name]);
+      method_some(code, "description", &self.description);
+      method_each(code, "directive", &self.directives);
+    }
+  }
+
+  impl Expand for ast::ObjectTypeDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "ObjectTypeDefinition::new", &[&self.name]);
+      method_some(code, "description", &self.description);
+      method_each(code, "implements", &self.interfaces);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "field", &self.fields);
+    }
+  }
+
+  impl Expand for ast::FieldDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "FieldDefinition::new", &[&self.name, &self.typ]);
+      method_some(code, "description", &self.description);
+      method_each(code, "arg", &self.arguments);
+      method_each(code, "directive", &self.directives);
+    }
+  }
+
+  impl Expand for ast::InputValueDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "InputValueDefinition::new", &[&self.name, &self.typ]);
+      method_some(code, "description", &self.description);
+      method_some(code, "default_value", &self.default_value);
+      method_each(code, "directive", &self.directives);
+    }
+  }
+
+  impl Expand for ast::InterfaceTypeDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "InterfaceTypeDefinition::new", &[&self.name]);
+      method_some(code, "description", &self.description);
+      method_each(code, "implements", &self.interfaces);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "field", &self.fields);
+    }
+  }
+
+  impl Expand for ast::UnionTypeDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "UnionTypeDefinition::new", &[&self.name]);
+      method_some(code, "description", &self.description);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "member", &self.types);
+    }
+  }
+
+  impl Expand for ast::EnumTypeDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "EnumTypeDefinition::new", &[&self.name]);
+      method_some(code, "description", &self.description);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "value", &self.values);
+    }
+  }
+
+  impl Expand for ast::EnumValueDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "EnumValueDefinition::new", &[&self.name]);
+      method_some(code, "description", &self.description);
+      method_each(code, "directive", &self.directives);
+    }
+  }
+
+  impl Expand for ast::InputObjectTypeDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "InputObjectTypeDefinition::new", &[&self.name]);
+      method_some(code, "description", &self.description);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "field", &self.fields);
+    }
+  }
+
+  impl Expand for ast::SchemaExtension {
+    fn expand(&self, code: &mut String) {
+      call(code, "SchemaExtension::new", &[]);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "operation", &self.operation_types);
+    }
+  }
+
+  impl Expand for ast::ScalarTypeExtension {
+    fn expand(&self, code: &mut String) {
+      call(code, "ScalarTypeExtension::new", &[&self.name]);
+      method_each(code, "directive", &self.directives);
+    }
+  }
+
+  impl Expand for ast::ObjectTypeExtension {
+    fn expand(&self, code: &mut String) {
+      call(code, "ObjectTypeExtension::new", &[&self.name]);
+      method_each(code, "implements", &self.interfaces);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "field", &self.fields);
+    }
+  }
+
+  impl Expand for ast::InterfaceTypeExtension {
+    fn expand(&self, code: &mut String) {
+      call(code, "InterfaceTypeExtension::new", &[&self.name]);
+      method_each(code, "implements", &self.interfaces);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "field", &self.fields);
+    }
+  }
+
+  impl Expand for ast::UnionTypeExtension {
+    fn expand(&self, code: &mut String) {
+      call(code, "UnionTypeExtension::new", &[&self.name]);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "member", &self.types);
+    }
+  }
+
+  impl Expand for ast::EnumTypeExtension {
+    fn expand(&self, code: &mut String) {
+      call(code, "EnumTypeExtension::new", &[&self.name]);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "value", &self.values);
+    }
+  }
+
+  impl Expand for ast::InputObjectTypeExtension {
+    fn expand(&self, code: &mut String) {
+      call(code, "InputObjectTypeExtension::new", &[&self.name]);
+      method_each(code, "directive", &self.directives);
+      method_each(code, "field", &self.fields);
+    }
+  }
+
+  impl Expand for ast::DirectiveDefinition {
+    fn expand(&self, code: &mut String) {
+      call(code, "DirectiveDefinition::new", &[&self.name]);
+      method_some(code, "description", &self.description);
+      method_each(code, "arg", &self.arguments);
+      if self.repeatable {
+        code.push_str(".repeatable()");
+      }
+      method_each(code, "location", &self.locations);
+    }
+  }
+}
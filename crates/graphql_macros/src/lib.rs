@@ -0,0 +1,195 @@
+//! Macros which parse GraphQL documents at compile time.
+//!
+//! The `graphql!` macro parses an executable document and expands to the
+//! `graphql::language::ast::Document` for it, so a syntax error in a query is
+//! a compile error instead of an error in production. The nodes of the
+//! document have no locations.
+//!
+//! ```ignore
+//! #[macro_use]
+//! extern crate graphql_macros;
+//! extern crate graphql;
+//!
+//! let document = graphql!{
+//!   query User($id: ID!) {
+//!     user(id: $id) { name }
+//!   }
+//! };
+//! ```
+//!
+//! GraphQL written directly in the macro is read as Rust tokens, which works
+//! for most documents. Use Rust comments instead of `#` comments, and write
+//! any document Rust can’t tokenize, like one with block strings, as a single
+//! string literal instead: `graphql!(r#"{ a(b: """c""") }"#)`.
+//!
+//! The `graphql_sdl!` macro does the same for documents of type system
+//! definitions. Syntax extensions are only accepted with the feature of this
+//! crate for that extension: `type_system` for `graphql_sdl!`, and
+//! `fragment_variables` for fragment variables. Each feature also enables the
+//! extension in the `graphql` crate.
+
+extern crate proc_macro;
+extern crate graphql;
+
+mod source;
+mod expand;
+
+use proc_macro::{TokenStream, TokenTree, Delimiter, Spacing, Span, Group, Ident, Literal, Punct};
+use graphql::language::parse;
+#[cfg(not(feature = "fragment_variables"))]
+use graphql::language::{visit, Visitor};
+use graphql::language::ast::{self, Node};
+
+use source::MacroSource;
+use expand::Expand;
+
+/// Parses an executable document at compile time, and expands to its AST.
+#[proc_macro]
+pub fn graphql(input: TokenStream) -> TokenStream {
+  expand_document(input, false)
+}
+
+/// Parses a document of type system definitions at compile time, and expands
+/// to its AST.
+// The type system AST extension is an experimental non-spec addition.
+#[cfg(feature = "type_system")]
+#[proc_macro]
+pub fn graphql_sdl(input: TokenStream) -> TokenStream {
+  expand_document(input, true)
+}
+
+/// Parses the document in the input of a macro and expands to its AST, or to
+/// a compile error at the span of the first error in the document.
+fn expand_document(input: TokenStream, type_system: bool) -> TokenStream {
+  let source = MacroSource::new(input);
+  let document = match parse(source.text.chars()) {
+    Ok(document) => document,
+    Err(error) => {
+      // Positions in a string literal help find the error in the literal, but
+      // positions in text made from tokens are meaningless.
+      let message = if source.literal { error.to_string() } else { error.message() };
+      let span = error.position().map_or(Span::call_site(), |position| source.span(position.offset));
+      return compile_error(&message, span);
+    },
+  };
+  for definition in &document.definitions {
+    if is_type_system(definition) != type_system {
+      let message = if type_system {
+        "Only type system definitions are allowed in `graphql_sdl!`."
+      } else if cfg!(feature = "type_system") {
+        "Type system definitions are not allowed in `graphql!`, use `graphql_sdl!` instead."
+      } else {
+        "Type system definitions are not allowed in `graphql!`, use `graphql_sdl!` with the `type_system` feature instead."
+      };
+      return compile_error(message, source.span(definition.loc().map_or(0, |loc| loc.start.offset)));
+    }
+  }
+  // Fragment variables are an experimental non-spec addition. Another crate
+  // may enable them in the `graphql` crate, but without our feature we can’t
+  // expand them.
+  #[cfg(not(feature = "fragment_variables"))]
+  let document = {
+    let mut fragment_variables = FragmentVariables { offset: None, spread: false, definition: false };
+    let document = visit(&mut fragment_variables, document);
+    if let Some(offset) = fragment_variables.offset {
+      return compile_error("Fragment variables are only allowed with the `fragment_variables` feature.", source.span(offset));
+    }
+    document
+  };
+  let mut code = String::new();
+  document.expand(&mut code);
+  code.parse().expect("expanded an invalid AST")
+}
+
+/// Whether a definition is a type system definition.
+fn is_type_system(definition: &ast::Definition) -> bool {
+  match *definition {
+    ast::Definition::Operation(_) |
+    ast::Definition::Fragment(_) => false,
+
+    // The type system AST extension is an experimental non-spec addition.
+    #[cfg(feature = "type_system")]
+    ast::Definition::TypeSystem(_) => true,
+  }
+}
+
+/// A visitor which finds the offset of the first fragment variable, or
+/// fragment spread argument, in a document.
+#[cfg(not(feature = "fragment_variables"))]
+struct FragmentVariables {
+  /// The offset of the first fragment variable we found.
+  offset: Option<usize>,
+  /// Whether we are in a fragment spread, but not yet in its directives.
+  /// Arguments we visit here are fragment spread arguments.
+  spread: bool,
+  /// Whether we are in a fragment definition. Variable definitions we visit
+  /// here are fragment variables.
+  definition: bool,
+}
+
+#[cfg(not(feature = "fragment_variables"))]
+impl FragmentVariables {
+  /// Remembers a fragment variable at a node, unless we already found one.
+  fn found<N>(&mut self, node: &N) where N: Node {
+    if self.offset.is_none() {
+      self.offset = Some(node.loc().map_or(0, |loc| loc.start.offset));
+    }
+  }
+}
+
+#[cfg(not(feature = "fragment_variables"))]
+impl Visitor for FragmentVariables {
+  fn enter_fragment_spread(&mut self, node: ast::FragmentSpread) -> ast::FragmentSpread {
+    self.spread = true;
+    node
+  }
+
+  fn leave_fragment_spread(&mut self, node: ast::FragmentSpread) -> ast::FragmentSpread {
+    self.spread = false;
+    node
+  }
+
+  fn enter_directive(&mut self, node: ast::Directive) -> ast::Directive {
+    self.spread = false;
+    node
+  }
+
+  fn enter_argument(&mut self, node: ast::Argument) -> ast::Argument {
+    if self.spread {
+      self.found(&node);
+    }
+    node
+  }
+
+  fn enter_fragment_definition(&mut self, node: ast::FragmentDefinition) -> ast::FragmentDefinition {
+    self.definition = true;
+    node
+  }
+
+  fn leave_fragment_definition(&mut self, node: ast::FragmentDefinition) -> ast::FragmentDefinition {
+    self.definition = false;
+    node
+  }
+
+  fn enter_variable_definition(&mut self, node: ast::VariableDefinition) -> ast::VariableDefinition {
+    if self.definition {
+      self.found(&node);
+    }
+    node
+  }
+}
+
+/// Creates an invocation of `compile_error!` with a message at a span.
+fn compile_error(message: &str, span: Span) -> TokenStream {
+  let mut literal = Literal::string(message);
+  literal.set_span(span);
+  let mut group = Group::new(Delimiter::Parenthesis, TokenStream::from(TokenTree::Literal(literal)));
+  group.set_span(span);
+  let mut bang = Punct::new('!', Spacing::Alone);
+  bang.set_span(span);
+  vec![
+    TokenTree::Ident(Ident::new("compile_error", span)),
+    TokenTree::Punct(bang),
+    TokenTree::Group(group),
+  ].into_iter().collect()
+}
@@ -0,0 +1,188 @@
+use proc_macro::{TokenStream, TokenTree, Delimiter, Spacing, Span};
+
+/// The GraphQL source text written in a macro invocation, along with the
+/// spans of the Rust tokens it was written with.
+pub struct MacroSource {
+  /// The GraphQL source text.
+  pub text: String,
+  /// Whether the source text was written as a single string literal. If so,
+  /// every part of the text has the span of that literal.
+  pub literal: bool,
+  /// The byte offset in the text at which every token starts, along with the
+  /// span of the token.
+  spans: Vec<(usize, Span)>,
+}
+
+impl MacroSource {
+  /// Gets the source text from the input of a macro. The input may be a
+  /// single string literal, or GraphQL written directly as Rust tokens.
+  pub fn new(input: TokenStream) -> Self {
+    let tokens = input.into_iter().collect::<Vec<_>>();
+    if tokens.len() == 1 {
+      if let TokenTree::Literal(ref literal) = tokens[0] {
+        if let Some(text) = unescape(&literal.to_string()) {
+          return MacroSource {
+            text,
+            literal: true,
+            spans: vec![(0, literal.span())],
+          };
+        }
+      }
+    }
+    let mut source = MacroSource {
+      text: String::new(),
+      literal: false,
+      spans: Vec::new(),
+    };
+    source.push_tokens(tokens);
+    source
+  }
+
+  /// The span of the token at a byte offset in the source text.
+  pub fn span(&self, offset: usize) -> Span {
+    self.spans.iter().rev().find(|&&(start, _)| start <= offset).map_or(Span::call_site(), |&(_, span)| span)
+  }
+
+  /// Pushes the GraphQL text of some Rust tokens.
+  fn push_tokens<I>(&mut self, tokens: I) where I: IntoIterator<Item=TokenTree> {
+    for token in tokens {
+      match token {
+        TokenTree::Group(group) => {
+          let (open, close) = match group.delimiter() {
+            Delimiter::Parenthesis => ("(", ")"),
+            Delimiter::Brace => ("{", "}"),
+            Delimiter::Bracket => ("[", "]"),
+            Delimiter::None => ("", ""),
+          };
+          self.push(open, group.span_open());
+          self.push_tokens(group.stream());
+          self.push(close, group.span_close());
+        },
+        TokenTree::Ident(ident) => {
+          let name = ident.to_string();
+          self.push(name.trim_start_matches("r#"), ident.span());
+        },
+        TokenTree::Punct(punct) => {
+          self.push(&punct.as_char().to_string(), punct.span());
+          // An ellipsis is three joint dots. The minus of a negative number is
+          // never joint with the number, but GraphQL needs them together.
+          if punct.spacing() == Spacing::Joint || punct.as_char() == '-' {
+            self.text.pop();
+          }
+        },
+        TokenTree::Literal(literal) => {
+          let text = literal.to_string();
+          match unescape(&text) {
+            Some(value) => self.push(&quote(&value), literal.span()),
+            None => self.push(&text, literal.span()),
+          }
+        },
+      }
+    }
+  }
+
+  /// Pushes the text of a single token followed by a space.
+  fn push(&mut self, text: &str, span: Span) {
+    if text.is_empty() {
+      return;
+    }
+    self.spans.push((self.text.len(), span));
+    self.text.push_str(text);
+    self.text.push(' ');
+  }
+}
+
+/// Gets the value of a Rust string literal, including raw string literals.
+/// Returns `None` if the literal is not a string literal.
+fn unescape(literal: &str) -> Option<String> {
+  if let Some(raw) = literal.strip_prefix('r') {
+    let hashes = raw.chars().take_while(|&c| c == '#').count();
+    let body = &raw[hashes..];
+    let end = format!("\"{}", "#".repeat(hashes));
+    if body.len() < 2 + hashes || !body.starts_with('"') || !body.ends_with(&end) {
+      return None;
+    }
+    return Some(String::from(&body[1..body.len() - 1 - hashes]));
+  }
+  if literal.len() < 2 || !literal.starts_with('"') || !literal.ends_with('"') {
+    return None;
+  }
+  let mut value = String::new();
+  let mut chars = literal[1..literal.len() - 1].chars().peekable();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      value.push(c);
+      continue;
+    }
+    match chars.next() {
+      Some('n') => value.push('\n'),
+      Some('r') => value.push('\r'),
+      Some('t') => value.push('\t'),
+      Some('0') => value.push('\0'),
+      Some('\\') => value.push('\\'),
+      Some('\'') => value.push('\''),
+      Some('"') => value.push('"'),
+      Some('x') => {
+        let digits = chars.by_ref().take(2).collect::<String>();
+        match u8::from_str_radix(&digits, 16) {
+          Ok(byte) => value.push(byte as char),
+          Err(_) => return None,
+        }
+      },
+      Some('u') => {
+        let digits = chars.by_ref().skip(1).take_while(|&c| c != '}').collect::<String>();
+        match u32::from_str_radix(&digits, 16).ok().and_then(::std::char::from_u32) {
+          Some(c) => value.push(c),
+          None => return None,
+        }
+      },
+      // An escaped line break skips the whitespace at the start of the next
+      // line.
+      Some('\n') => while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+      },
+      _ => return None,
+    }
+  }
+  Some(value)
+}
+
+/// Writes a value as a GraphQL string.
+fn quote(value: &str) -> String {
+  let mut quoted = String::from("\"");
+  for c in value.chars() {
+    match c {
+      '"' => quoted.push_str("\\\""),
+      '\\' => quoted.push_str("\\\\"),
+      '\n' => quoted.push_str("\\n"),
+      '\r' => quoted.push_str("\\r"),
+      '\t' => quoted.push_str("\\t"),
+      c if c < ' ' => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+      c => quoted.push(c),
+    }
+  }
+  quoted.push('"');
+  quoted
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{unescape, quote};
+
+  #[test]
+  fn test_unescape() {
+    assert_eq!(unescape(r#""a""#), Some(String::from("a")));
+    assert_eq!(unescape(r#""\"\\\n\t\x41\u{1F600}""#), Some(String::from("\"\\\n\tA😀")));
+    assert_eq!(unescape("\"a\\\n    b\""), Some(String::from("ab")));
+    assert_eq!(unescape(r####"r#"a"b"#"####), Some(String::from("a\"b")));
+    assert_eq!(unescape(r#"r"a\n""#), Some(String::from("a\\n")));
+    assert_eq!(unescape(r#"b"a""#), None);
+    assert_eq!(unescape("'a'"), None);
+    assert_eq!(unescape("42"), None);
+  }
+
+  #[test]
+  fn test_quote() {
+    assert_eq!(quote("a\"b\\c\nd\u{1}é"), r#""a\"b\\c\nd\u0001é""#);
+  }
+}
@@ -0,0 +1,85 @@
+#[macro_use]
+extern crate graphql_macros;
+extern crate graphql;
+
+use graphql::language::parse_without_location;
+
+#[test]
+fn test_graphql() {
+  let document = graphql!{
+    query Q($a: [Int!] = [-1, 2]) @b {
+      c: d(e: $a, f: "g\n\"h\"", i: [1.5, -2.5e3, true, null, J], k: {l: "m"}) {
+        ...N
+        ... on O @p(q: false) { r }
+        ... @s { t }
+      }
+    }
+    // Rust comments are skipped.
+    mutation { u }
+    subscription { v }
+    fragment N on O { w }
+  };
+  let source = r#"
+    query Q($a: [Int!] = [-1, 2]) @b {
+      c: d(e: $a, f: "g\n\"h\"", i: [1.5, -2.5e3, true, null, J], k: {l: "m"}) {
+        ...N
+        ... on O @p(q: false) { r }
+        ... @s { t }
+      }
+    }
+    mutation { u }
+    subscription { v }
+    fragment N on O { w }
+  "#;
+  assert_eq!(document, parse_without_location(source.chars()).unwrap());
+}
+
+#[test]
+fn test_graphql_keyword_names() {
+  let document = graphql!{ { type: fn(in: true, r#match: Self) } };
+  assert_eq!(document, parse_without_location("{ type: fn(in: true, match: Self) }".chars()).unwrap());
+}
+
+#[test]
+fn test_graphql_string_literal() {
+  let source = "{ a(b: \"\"\"\n  c\n\"\"\") # d\n}";
+  assert_eq!(graphql!("{ a(b: \"\"\"\n  c\n\"\"\") # d\n}"), parse_without_location(source.chars()).unwrap());
+  assert_eq!(graphql!(r#"{ a(b: """c""") }"#), parse_without_location(r#"{ a(b: """c""") }"#.chars()).unwrap());
+}
+
+#[test]
+#[cfg(feature = "fragment_variables")]
+fn test_graphql_fragment_variables() {
+  let document = graphql!{ { ...A(b: 1) } fragment A($b: Int) on C { d(e: $b) } };
+  assert_eq!(document, parse_without_location("{ ...A(b: 1) } fragment A($b: Int) on C { d(e: $b) }".chars()).unwrap());
+}
+
+#[test]
+#[cfg(feature = "type_system")]
+fn test_graphql_sdl() {
+  let document = graphql_sdl!{
+    "A" schema { query: Q }
+    extend schema @b
+    type Q implements I @c { d(e: Int = 1): [String!]! }
+    interface I { d: String }
+    union U = Q | R
+    enum E { F G }
+    input H { i: Int = -1 }
+    scalar S
+    extend type Q { j: Int }
+    directive @k(l: Int) repeatable on FIELD | ENUM_VALUE
+  };
+  let source = r#"
+    "A" schema { query: Q }
+    extend schema @b
+    type Q implements I @c { d(e: Int = 1): [String!]! }
+    interface I { d: String }
+    union U = Q | R
+    enum E { F G }
+    input H { i: Int = -1 }
+    scalar S
+    extend type Q { j: Int }
+    directive @k(l: Int) repeatable on FIELD | ENUM_VALUE
+  "#;
+  assert_eq!(document, parse_without_location(source.chars()).unwrap());
+}